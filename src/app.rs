@@ -1,29 +1,91 @@
-use crate::gpu::GpuMonitor;
-use crate::memory::MemoryInfo;
-use crate::process::{ProcessDetails, ProcessInfo, SortMode, fetch_process_details};
+use crate::broker::{Broker, Request, Response};
+use crate::cgroup::{WorkloadGroup, group_by_workload};
+use crate::config::Config;
+use crate::context::ProcessContext;
+use crate::cpu::{CpuBreakdown, CpuTopology};
+use crate::events::EventLog;
+use crate::gpu::GpuActivity;
+use crate::host::HostInfo;
+use crate::inspect::{InspectView, MapSort, ThreadSort};
+use crate::memory::{MemoryInfo, MemoryPressure};
+use crate::power::PowerInfo;
+use crate::powermetrics::SocPower;
+use crate::process::{MemoryColumn, ProcessDetails, ProcessInfo, SortMode};
+use crate::services::ServiceMonitor;
+use crate::thermal::ThermalInfo;
+use crate::watch::WatchList;
 use crate::{DataCommand, DataUpdate};
 use crossterm::event::{self, Event, KeyCode, KeyEvent};
 use ratatui::widgets::TableState;
 use std::collections::{HashSet, VecDeque};
-use std::sync::mpsc::{self, Receiver, Sender};
-use std::thread;
-use std::time::{Duration, Instant};
+use std::sync::mpsc::{Receiver, Sender};
+
+use std::time::{Duration, SystemTime};
 
 const MAX_TIMELINE_OFFSET: usize = 900; // Allow scrolling back 15 minutes
 
+/// Optional extra series drawn over the oscilloscope timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineTrace {
+    Off,
+    Power,
+    Thermal,
+    /// CPU line replaced by stacked user/system/iowait lines.
+    CpuTime,
+}
+
+impl TimelineTrace {
+    pub fn next(self) -> Self {
+        match self {
+            TimelineTrace::Off => TimelineTrace::Power,
+            TimelineTrace::Power => TimelineTrace::Thermal,
+            TimelineTrace::Thermal => TimelineTrace::CpuTime,
+            TimelineTrace::CpuTime => TimelineTrace::Off,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct App {
     // Data from background thread
     pub cpu_core_histories: Vec<VecDeque<f32>>,
+    pub cpu_frequencies: Vec<u64>,
+    pub cpu_topology: Option<CpuTopology>,
+    /// Time-in-state split per tick, where the OS reports it.
+    pub cpu_breakdown_history: VecDeque<Option<CpuBreakdown>>,
     pub gpu_core_histories: Vec<VecDeque<f32>>,
     pub gpu_overall_history: VecDeque<f32>,
+    pub gpu_vram: Option<(u64, u64)>,
+    pub gpu_activity: Option<GpuActivity>,
+    pub soc_power: Option<SocPower>,
     pub memory_usage_history: VecDeque<f32>,
+    /// Used memory minus reclaimable cache, as a percentage of total.
+    pub memory_app_history: VecDeque<f32>,
+    /// Pressure level per tick, aligned with `memory_usage_history`.
+    pub memory_pressure_history: VecDeque<MemoryPressure>,
+    /// Plot `memory_app_history` instead of raw used memory.
+    pub memory_excludes_cache: bool,
+    pub power_info: Option<PowerInfo>,
+    /// Package watts per tick (0 when unmeasured).
+    pub power_history: VecDeque<f32>,
+    pub thermal_info: Option<ThermalInfo>,
+    pub host_info: Option<HostInfo>,
+    /// Agent address when the data comes from `--connect`.
+    pub remote: Option<String>,
+    /// The agent hung up; what is on screen are its last readings.
+    pub disconnected: bool,
+    /// CPU/package °C per tick (0 when no sensor).
+    pub thermal_history: VecDeque<f32>,
     cpu_average_history: VecDeque<f32>,
     processes: Vec<ProcessInfo>,
 
     // Static info (doesn't change)
-    pub gpu_monitor: GpuMonitor,         // For GPU availability check
+    /// The collector's GPU as (chip name, core count), when it has one.
+    pub gpu: Option<(String, usize)>,
     pub memory_info: Option<MemoryInfo>, // Updated from background thread
+    pub watch: WatchList,                // Expected processes/ports from config
+    pub events: EventLog,                // Spawn/exit/restart history
+    pub services: ServiceMonitor,        // systemd units from cgroup paths
 
     // UI state
     pub gpu_visible: bool,
@@ -32,6 +94,10 @@ pub struct App {
     pub running: bool,
     pub paused: bool,
     pub timeline_offset: usize,
+    pub timeline_trace: TimelineTrace,
+    pub memory_column: MemoryColumn,
+    /// The data source measures PSS and USS, so `M` offers them.
+    pub smaps_rollup: bool,
     pub filter_mode: bool,
     pub filter_input: String,
     pub filtered_indices: Vec<usize>,
@@ -39,50 +105,81 @@ pub struct App {
     pub kill_target_pid: Option<u32>,
     pub kill_target_name: String,
     pub help_mode: bool,
+    pub events_mode: bool,
+    pub events_scroll: usize,
+    pub events_status: Option<String>,
+    pub services_mode: bool,
+    pub services_scroll: usize,
+    /// Outcome of the last signal sent, until the next key press.
+    pub signal_status: Option<String>,
     pub pinned_pids: HashSet<u32>,
     sort_mode: SortMode,
 
     // Breakout / details panel state
     pub expanded_pid: Option<u32>,
-    pub selected_details: Option<ProcessDetails>,
-    details_tx: Sender<u32>,
-    details_rx: Receiver<ProcessDetails>,
-    details_last_fetched: Option<Instant>,
+    /// Show environment, limits, cgroup, namespaces and start time in the
+    /// breakout (key `d`).
+    pub breakout_context: bool,
+    /// Show services and containers with summed usage instead of
+    /// processes (key `c`).
+    pub group_by_workload: bool,
+    /// Inspector view open over the expanded process, if any.
+    pub inspect_view: Option<InspectView>,
+    pub inspect_scroll: usize,
+    /// Case-insensitive filter over the inspector rows; typing while
+    /// `inspect_filter_mode` is set.
+    pub inspect_filter: String,
+    pub inspect_filter_mode: bool,
+    pub map_sort: MapSort,
+    pub thread_sort: ThreadSort,
+    /// Breakout details, inspector views, context sections and signals.
+    pub broker: Broker,
 
     // Channel to send commands to background thread
     command_tx: Sender<DataCommand>,
 }
 
 impl App {
-    pub fn new(command_tx: Sender<DataCommand>) -> Self {
-        let gpu_monitor = GpuMonitor::new();
-        let gpu_core_count = gpu_monitor.get_core_count();
-
+    /// `gpu` describes the GPU the collector samples; the app never probes
+    /// the hardware itself.
+    pub fn new(
+        command_tx: Sender<DataCommand>,
+        config: Config,
+        gpu: Option<(String, usize)>,
+    ) -> Self {
         let mut table_state = TableState::default();
         table_state.select(Some(0));
 
-        let (details_req_tx, details_req_rx) = mpsc::channel::<u32>();
-        let (details_res_tx, details_res_rx) = mpsc::channel::<ProcessDetails>();
-        thread::spawn(move || {
-            while let Ok(pid) = details_req_rx.recv() {
-                let details = fetch_process_details(pid);
-                if details_res_tx.send(details).is_err() {
-                    break;
-                }
-            }
-        });
-
         App {
             // Data will be populated from background thread
             cpu_core_histories: Vec::new(),
-            gpu_core_histories: (0..gpu_core_count).map(|_| VecDeque::new()).collect(),
+            cpu_frequencies: Vec::new(),
+            cpu_topology: None,
+            cpu_breakdown_history: VecDeque::new(),
+            gpu_core_histories: Vec::new(), // Only populated with measured per-core data
             gpu_overall_history: VecDeque::new(),
+            gpu_vram: None,
+            gpu_activity: None,
+            soc_power: None,
             memory_usage_history: VecDeque::new(),
+            memory_app_history: VecDeque::new(),
+            memory_pressure_history: VecDeque::new(),
+            memory_excludes_cache: false,
+            power_info: None,
+            power_history: VecDeque::new(),
+            thermal_info: None,
+            host_info: None,
+            remote: None,
+            disconnected: false,
+            thermal_history: VecDeque::new(),
             cpu_average_history: VecDeque::new(),
             processes: Vec::new(),
 
-            gpu_monitor,
+            gpu,
             memory_info: None,
+            watch: WatchList::new(config.watch),
+            events: EventLog::new(),
+            services: ServiceMonitor::new(),
 
             gpu_visible: true,
             selected_process: 0,
@@ -90,6 +187,9 @@ impl App {
             running: true,
             paused: false,
             timeline_offset: 0,
+            timeline_trace: TimelineTrace::Off,
+            memory_column: MemoryColumn::Resident,
+            smaps_rollup: crate::process::HAS_SMAPS_ROLLUP,
             filter_mode: false,
             filter_input: String::new(),
             filtered_indices: Vec::new(),
@@ -97,14 +197,25 @@ impl App {
             kill_target_pid: None,
             kill_target_name: String::new(),
             help_mode: false,
+            events_mode: false,
+            events_scroll: 0,
+            events_status: None,
+            services_mode: false,
+            services_scroll: 0,
+            signal_status: None,
             pinned_pids: HashSet::new(),
             sort_mode: SortMode::Cpu,
 
             expanded_pid: None,
-            selected_details: None,
-            details_tx: details_req_tx,
-            details_rx: details_res_rx,
-            details_last_fetched: None,
+            breakout_context: false,
+            group_by_workload: false,
+            inspect_view: None,
+            inspect_scroll: 0,
+            inspect_filter: String::new(),
+            inspect_filter_mode: false,
+            map_sort: MapSort::default(),
+            thread_sort: ThreadSort::default(),
+            broker: Broker::new(),
 
             command_tx,
         }
@@ -120,10 +231,22 @@ impl App {
         // Drain all available updates (non-blocking)
         while let Ok(update) = rx.try_recv() {
             match update {
+                DataUpdate::CpuTopology { topology } => {
+                    self.cpu_topology = Some(topology);
+                    updated = true;
+                }
                 DataUpdate::Cpu {
                     core_values,
                     average_value,
+                    frequencies_mhz,
+                    breakdown,
                 } => {
+                    self.cpu_frequencies = frequencies_mhz;
+                    self.cpu_breakdown_history.push_back(breakdown);
+                    if self.cpu_breakdown_history.len() > MAX_HISTORY {
+                        self.cpu_breakdown_history.pop_front();
+                    }
+
                     // Initialise history vectors if needed
                     if self.cpu_core_histories.len() != core_values.len() {
                         self.cpu_core_histories = (0..core_values.len())
@@ -150,7 +273,14 @@ impl App {
                 DataUpdate::Gpu {
                     core_values,
                     overall_value,
+                    vram,
+                    activity,
+                    soc,
                 } => {
+                    self.gpu_vram = vram;
+                    self.gpu_activity = activity;
+                    self.soc_power = soc;
+
                     // Initialise history vectors if needed
                     if self.gpu_core_histories.len() != core_values.len() {
                         self.gpu_core_histories = (0..core_values.len())
@@ -174,17 +304,55 @@ impl App {
                     }
                     updated = true;
                 }
-                DataUpdate::Memory { usage_value, info } => {
+                DataUpdate::Memory {
+                    usage_value,
+                    pressure,
+                    info,
+                } => {
                     self.memory_usage_history.push_back(usage_value);
                     if self.memory_usage_history.len() > MAX_HISTORY {
                         self.memory_usage_history.pop_front();
                     }
-                    self.memory_info = Some(info);
+                    self.memory_pressure_history.push_back(pressure);
+                    if self.memory_pressure_history.len() > MAX_HISTORY {
+                        self.memory_pressure_history.pop_front();
+                    }
+                    self.memory_app_history
+                        .push_back(info.used_excluding_cache_percentage() as f32);
+                    if self.memory_app_history.len() > MAX_HISTORY {
+                        self.memory_app_history.pop_front();
+                    }
+                    self.memory_info = Some(*info);
+                    updated = true;
+                }
+                DataUpdate::Power { info } => {
+                    self.power_history
+                        .push_back(info.package_watts.unwrap_or(0.0));
+                    if self.power_history.len() > MAX_HISTORY {
+                        self.power_history.pop_front();
+                    }
+                    self.power_info = (!info.is_empty()).then_some(info);
+                    updated = true;
+                }
+                DataUpdate::Thermal { info } => {
+                    self.thermal_history
+                        .push_back(info.cpu().map_or(0.0, |s| s.celsius));
+                    if self.thermal_history.len() > MAX_HISTORY {
+                        self.thermal_history.pop_front();
+                    }
+                    self.thermal_info = (!info.is_empty()).then_some(info);
+                    updated = true;
+                }
+                DataUpdate::Host { info } => {
+                    self.host_info = Some(info);
                     updated = true;
                 }
                 DataUpdate::Processes { processes } => {
                     self.processes = processes;
                     self.update_filtered_indices();
+                    self.watch.update(&self.processes);
+                    self.events.observe(&self.processes, SystemTime::now());
+                    self.services.observe(&self.processes, SystemTime::now());
 
                     // Reset selection if out of bounds
                     let process_count = self.processes.len();
@@ -196,9 +364,7 @@ impl App {
                     if let Some(pid) = self.expanded_pid
                         && !self.processes.iter().any(|p| p.pid == pid)
                     {
-                        self.expanded_pid = None;
-                        self.selected_details = None;
-                        self.details_last_fetched = None;
+                        self.collapse();
                     }
 
                     updated = true;
@@ -206,24 +372,19 @@ impl App {
             }
         }
 
-        // Drain detail responses; keep only if still matches expanded_pid
-        while let Ok(details) = self.details_rx.try_recv() {
-            if self.expanded_pid == Some(details.pid) {
-                self.selected_details = Some(details);
-                updated = true;
+        for request in self.broker.poll() {
+            if let Request::Terminate(pid) = request
+                && let Some(Response::Terminate(outcome)) = self.broker.get(request)
+            {
+                self.signal_status = Some(outcome.describe(pid));
             }
+            updated = true;
         }
 
-        // Periodic refresh of detail data while breakout is open
+        // Ask for whatever the breakout shows; the broker skips anything
+        // in flight or still current
         if let Some(pid) = self.expanded_pid {
-            let stale = self
-                .details_last_fetched
-                .map(|t| t.elapsed() >= Duration::from_secs(2))
-                .unwrap_or(true);
-            if stale {
-                self.details_last_fetched = Some(Instant::now());
-                let _ = self.details_tx.send(pid);
-            }
+            self.request_details(pid);
         }
 
         updated
@@ -242,7 +403,9 @@ impl App {
         Ok(false)
     }
 
-    fn handle_key_event(&mut self, key: KeyEvent) {
+    pub fn handle_key_event(&mut self, key: KeyEvent) {
+        self.signal_status = None;
+
         // Handle help mode
         if self.help_mode {
             match key.code {
@@ -254,12 +417,129 @@ impl App {
             return;
         }
 
+        // Handle event log panel
+        if self.events_mode {
+            let max_scroll = self.events.len().saturating_sub(1);
+            match key.code {
+                KeyCode::Char('e') | KeyCode::Char('q') | KeyCode::Esc => {
+                    self.events_mode = false;
+                    self.events_status = None;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.events_scroll = (self.events_scroll + 1).min(max_scroll);
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.events_scroll = self.events_scroll.saturating_sub(1);
+                }
+                KeyCode::PageDown => {
+                    self.events_scroll = (self.events_scroll + 10).min(max_scroll);
+                }
+                KeyCode::PageUp => {
+                    self.events_scroll = self.events_scroll.saturating_sub(10);
+                }
+                KeyCode::Char('g') | KeyCode::Home => self.events_scroll = 0,
+                KeyCode::Char('G') | KeyCode::End => self.events_scroll = max_scroll,
+                KeyCode::Char('x') => {
+                    let dir = std::env::current_dir().unwrap_or_else(|_| std::env::temp_dir());
+                    self.events_status = Some(match self.events.export(&dir) {
+                        Ok(path) => format!("exported to {}", path.display()),
+                        Err(err) => format!("export failed: {}", err),
+                    });
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle the services panel
+        if self.services_mode {
+            let max_scroll = self.services.line_count().saturating_sub(1);
+            match key.code {
+                KeyCode::Char('u') | KeyCode::Char('q') | KeyCode::Esc => {
+                    self.services_mode = false;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.services_scroll = (self.services_scroll + 1).min(max_scroll);
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.services_scroll = self.services_scroll.saturating_sub(1);
+                }
+                KeyCode::PageDown => {
+                    self.services_scroll = (self.services_scroll + 10).min(max_scroll);
+                }
+                KeyCode::PageUp => {
+                    self.services_scroll = self.services_scroll.saturating_sub(10);
+                }
+                KeyCode::Char('g') | KeyCode::Home => self.services_scroll = 0,
+                KeyCode::Char('G') | KeyCode::End => self.services_scroll = max_scroll,
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle the inspector over the expanded process
+        if let Some(view) = self.inspect_view {
+            if self.inspect_filter_mode {
+                match key.code {
+                    KeyCode::Esc => {
+                        self.inspect_filter_mode = false;
+                        self.inspect_filter.clear();
+                    }
+                    KeyCode::Enter => self.inspect_filter_mode = false,
+                    KeyCode::Backspace => {
+                        self.inspect_filter.pop();
+                    }
+                    KeyCode::Char(c) => self.inspect_filter.push(c),
+                    _ => {}
+                }
+                self.inspect_scroll = 0;
+                return;
+            }
+
+            let rows = self.inspect_rows();
+            let max_scroll = rows.saturating_sub(1);
+            match key.code {
+                KeyCode::Char('i') | KeyCode::Char('q') | KeyCode::Esc => {
+                    self.close_inspector();
+                }
+                KeyCode::Tab => {
+                    if let Some(pid) = self.expanded_pid {
+                        self.show_inspector(pid, view.next());
+                    }
+                }
+                KeyCode::Char('/') => {
+                    self.inspect_filter_mode = true;
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    self.inspect_scroll = (self.inspect_scroll + 1).min(max_scroll);
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.inspect_scroll = self.inspect_scroll.saturating_sub(1);
+                }
+                KeyCode::PageDown => {
+                    self.inspect_scroll = (self.inspect_scroll + 10).min(max_scroll);
+                }
+                KeyCode::PageUp => {
+                    self.inspect_scroll = self.inspect_scroll.saturating_sub(10);
+                }
+                KeyCode::Char('g') | KeyCode::Home => self.inspect_scroll = 0,
+                KeyCode::Char('G') | KeyCode::End => self.inspect_scroll = max_scroll,
+                KeyCode::Char('s') => match view {
+                    InspectView::Maps => self.map_sort = self.map_sort.next(),
+                    InspectView::Files => {}
+                    InspectView::Threads => self.thread_sort = self.thread_sort.next(),
+                },
+                _ => {}
+            }
+            return;
+        }
+
         // Handle kill confirmation mode
         if self.kill_confirmation_mode {
             match key.code {
                 KeyCode::Char('y') | KeyCode::Char('Y') => {
                     if let Some(pid) = self.kill_target_pid {
-                        self.kill_process(pid);
+                        self.broker.request(Request::Terminate(pid));
                     }
                     self.kill_confirmation_mode = false;
                     self.kill_target_pid = None;
@@ -300,6 +580,24 @@ impl App {
             return;
         }
 
+        // The grouped view has no selection, so keys acting on the selected
+        // process would hit a row the user cannot see
+        if self.group_by_workload
+            && matches!(
+                key.code,
+                KeyCode::Enter
+                    | KeyCode::Char('i' | 'd' | 'K' | 'j' | 'k' | 'g' | 'G')
+                    | KeyCode::Up
+                    | KeyCode::Down
+                    | KeyCode::PageUp
+                    | KeyCode::PageDown
+                    | KeyCode::Home
+                    | KeyCode::End
+            )
+        {
+            return;
+        }
+
         // Normal mode key handling
         match key.code {
             KeyCode::Char('q') | KeyCode::Esc => {
@@ -315,23 +613,42 @@ impl App {
                         self.pinned_pids.insert(pid);
                     }
                     if self.expanded_pid == Some(pid) {
-                        self.expanded_pid = None;
-                        self.selected_details = None;
-                        self.details_last_fetched = None;
+                        self.collapse();
+                    } else {
+                        self.expand(pid);
+                    }
+                }
+            }
+            KeyCode::Char('i') => {
+                self.open_inspector(InspectView::Maps);
+            }
+            KeyCode::Char('d') => {
+                self.breakout_context = !self.breakout_context;
+                if let Some(pid) = self.expanded_pid {
+                    if self.breakout_context {
+                        self.request_details(pid);
                     } else {
-                        self.expanded_pid = Some(pid);
-                        self.selected_details = None;
-                        self.details_last_fetched = Some(Instant::now());
-                        let _ = self.details_tx.send(pid);
+                        self.broker.cancel(Request::Context(pid));
                     }
                 }
             }
+            KeyCode::Char('c') => {
+                self.group_by_workload = !self.group_by_workload;
+            }
             KeyCode::Char('?') => {
                 self.help_mode = true;
             }
             KeyCode::Char('/') => {
                 self.filter_mode = true;
             }
+            KeyCode::Char('e') => {
+                self.events_mode = true;
+                self.events_scroll = 0;
+            }
+            KeyCode::Char('u') => {
+                self.services_mode = true;
+                self.services_scroll = 0;
+            }
             KeyCode::Char(' ') => {
                 self.paused = !self.paused;
                 if self.paused {
@@ -350,6 +667,18 @@ impl App {
             KeyCode::Char('-') => {
                 self.timeline_offset = (self.timeline_offset + 30).min(MAX_TIMELINE_OFFSET);
             }
+            KeyCode::Char('t') => {
+                self.timeline_trace = self.timeline_trace.next();
+            }
+            KeyCode::Char('m') => {
+                self.memory_excludes_cache = !self.memory_excludes_cache;
+            }
+            KeyCode::Char('M') => {
+                self.memory_column = self.memory_column.next(self.smaps_rollup);
+                let _ = self
+                    .command_tx
+                    .send(DataCommand::SetMemoryColumn(self.memory_column));
+            }
             KeyCode::Char('v') => {
                 self.gpu_visible = !self.gpu_visible;
                 let _ = self
@@ -415,6 +744,101 @@ impl App {
         }
     }
 
+    /// Open `view` over the selected process, expanding it if needed.
+    fn open_inspector(&mut self, view: InspectView) {
+        let processes = self.get_filtered_processes();
+        let Some(pid) = processes.get(self.selected_process).map(|p| p.pid) else {
+            return;
+        };
+        if self.expanded_pid != Some(pid) {
+            self.expand(pid);
+        }
+        self.show_inspector(pid, view);
+    }
+
+    /// Switch the inspector to `view` and fetch its data straight away.
+    fn show_inspector(&mut self, pid: u32, view: InspectView) {
+        self.close_inspector();
+        self.inspect_view = Some(view);
+        self.inspect_scroll = 0;
+        self.inspect_filter.clear();
+        self.inspect_filter_mode = false;
+        self.request_details(pid);
+    }
+
+    /// Close the inspector, dropping its fetch if one is still running.
+    fn close_inspector(&mut self) {
+        if let (Some(pid), Some(view)) = (self.expanded_pid, self.inspect_view.take()) {
+            self.broker.cancel(Request::for_view(view, pid));
+        }
+    }
+
+    fn expand(&mut self, pid: u32) {
+        self.collapse();
+        self.expanded_pid = Some(pid);
+        self.request_details(pid);
+    }
+
+    /// Close the breakout and the inspector over it, and forget what was
+    /// fetched for them.
+    fn collapse(&mut self) {
+        self.close_inspector();
+        if let Some(pid) = self.expanded_pid.take() {
+            self.broker.forget(pid);
+        }
+    }
+
+    /// Ask the broker for everything currently on screen for `pid`.
+    fn request_details(&mut self, pid: u32) {
+        self.broker.request(Request::Details(pid));
+        if let Some(view) = self.inspect_view {
+            self.broker.request(Request::for_view(view, pid));
+        }
+        if self.breakout_context {
+            self.broker.request(Request::Context(pid));
+        }
+    }
+
+    /// Breakout details of the expanded process, once fetched.
+    pub fn details(&self) -> Option<&ProcessDetails> {
+        match self.broker.get(Request::Details(self.expanded_pid?)) {
+            Some(Response::Details(details)) => Some(details),
+            _ => None,
+        }
+    }
+
+    /// Context sections of the expanded process while they are shown.
+    pub fn context(&self) -> Option<&ProcessContext> {
+        if !self.breakout_context {
+            return None;
+        }
+        match self.broker.get(Request::Context(self.expanded_pid?)) {
+            Some(Response::Context(context)) => Some(context),
+            _ => None,
+        }
+    }
+
+    /// The answer behind the open inspector view; `None` until it arrives.
+    pub fn inspection(&self) -> Option<&Response> {
+        let view = self.inspect_view?;
+        self.broker.get(Request::for_view(view, self.expanded_pid?))
+    }
+
+    /// Number of rows the inspector can scroll through after filtering.
+    fn inspect_rows(&self) -> usize {
+        let filter = self.inspect_filter.as_str();
+        match self.inspection() {
+            Some(Response::Maps(Some(maps))) => maps.iter().filter(|m| m.matches(filter)).count(),
+            Some(Response::Files(Some(files))) => {
+                files.iter().filter(|f| f.matches(filter)).count()
+            }
+            Some(Response::Threads(Some(threads))) => {
+                threads.iter().filter(|t| t.matches(filter)).count()
+            }
+            _ => 0,
+        }
+    }
+
     /// Returns current CPU usage for each core (last recorded value)
     pub fn get_cpu_usages(&self) -> Vec<(String, f32)> {
         self.cpu_core_histories
@@ -474,7 +898,7 @@ impl App {
     }
 
     pub fn is_gpu_visible(&self) -> bool {
-        self.gpu_visible && self.gpu_monitor.is_available()
+        self.gpu_visible && self.gpu.is_some()
     }
 
     pub fn get_cpu_average_history(&self) -> &VecDeque<f32> {
@@ -485,24 +909,6 @@ impl App {
         self.sort_mode
     }
 
-    fn kill_process(&self, pid: u32) {
-        let pid = pid as i32;
-        std::thread::spawn(move || {
-            unsafe {
-                // Send SIGTERM first to allow graceful shutdown
-                libc::kill(pid, libc::SIGTERM);
-            }
-            // Wait briefly, then escalate to SIGKILL if still alive
-            std::thread::sleep(std::time::Duration::from_secs(2));
-            unsafe {
-                // kill() with signal 0 checks if process exists without sending a signal
-                if libc::kill(pid, 0) == 0 {
-                    libc::kill(pid, libc::SIGKILL);
-                }
-            }
-        });
-    }
-
     pub fn update_filtered_indices(&mut self) {
         if self.filter_input.is_empty() {
             self.filtered_indices.clear();
@@ -520,6 +926,9 @@ impl App {
                             .ports
                             .iter()
                             .any(|port| port.port.to_string().contains(&filter_lower))
+                        || proc
+                            .workload()
+                            .is_some_and(|w| w.label().to_lowercase().contains(&filter_lower))
                 })
                 .map(|(i, _)| i)
                 .collect();
@@ -532,6 +941,11 @@ impl App {
         }
     }
 
+    /// The filtered processes summed per service, container or pod.
+    pub fn workload_groups(&self) -> Vec<WorkloadGroup> {
+        group_by_workload(self.get_filtered_processes())
+    }
+
     pub fn get_filtered_processes(&self) -> Vec<&ProcessInfo> {
         let mut processes: Vec<&ProcessInfo> =
             if self.filtered_indices.is_empty() && !self.filter_input.is_empty() {
@@ -556,3 +970,224 @@ impl App {
         processes
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::fixture;
+    use crossterm::event::KeyModifiers;
+
+    fn key(app: &mut App, code: KeyCode) {
+        app.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn feed(app: &mut App, updates: Vec<DataUpdate>) -> bool {
+        let (tx, rx) = std::sync::mpsc::channel();
+        for update in updates {
+            tx.send(update).unwrap();
+        }
+        app.process_updates(&rx)
+    }
+
+    #[test]
+    fn test_updates_extend_histories() {
+        let (mut app, _rx) = fixture::app();
+        assert!(!feed(&mut app, vec![]));
+        for _ in 0..3 {
+            feed(
+                &mut app,
+                vec![DataUpdate::Cpu {
+                    core_values: vec![10.0, 20.0],
+                    average_value: 15.0,
+                    frequencies_mhz: vec![3000, 3000],
+                    breakdown: None,
+                }],
+            );
+        }
+        assert_eq!(app.cpu_core_histories.len(), 2);
+        assert_eq!(app.cpu_average_history.len(), 3);
+        assert_eq!(app.get_cpu_usages()[1].1, 20.0);
+    }
+
+    #[test]
+    fn test_selection_clamps_and_breakout_clears_on_exit() {
+        let (mut app, _rx) = fixture::app();
+        let procs: Vec<ProcessInfo> = (1..=5)
+            .map(|pid| fixture::process(pid, "worker", 1.0, 10))
+            .collect();
+        feed(
+            &mut app,
+            vec![DataUpdate::Processes {
+                processes: procs.clone(),
+            }],
+        );
+        key(&mut app, KeyCode::Char('G'));
+        assert_eq!(app.get_selected_process(), 4);
+        app.expanded_pid = Some(5);
+
+        feed(
+            &mut app,
+            vec![DataUpdate::Processes {
+                processes: procs[..2].to_vec(),
+            }],
+        );
+        assert_eq!(app.get_selected_process(), 1);
+        assert_eq!(app.expanded_pid, None);
+    }
+
+    #[test]
+    fn test_pause_and_sort_keys_send_commands() {
+        let (mut app, rx) = fixture::app();
+        key(&mut app, KeyCode::Char(' '));
+        assert!(app.is_paused());
+        key(&mut app, KeyCode::Char('s'));
+        assert!(matches!(app.get_sort_mode(), SortMode::Memory));
+        key(&mut app, KeyCode::Char(' '));
+
+        let commands: Vec<DataCommand> = rx.try_iter().collect();
+        assert!(matches!(
+            commands.as_slice(),
+            [
+                DataCommand::Pause,
+                DataCommand::ChangeSortMode,
+                DataCommand::Resume
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_filter_narrows_processes() {
+        let (mut app, _rx) = fixture::app();
+        feed(
+            &mut app,
+            vec![DataUpdate::Processes {
+                processes: vec![
+                    fixture::process(10, "postgres", 1.0, 100),
+                    fixture::process(11, "nginx", 1.0, 100),
+                ],
+            }],
+        );
+        key(&mut app, KeyCode::Char('/'));
+        for c in "ngi".chars() {
+            key(&mut app, KeyCode::Char(c));
+        }
+        let filtered = app.get_filtered_processes();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].pid, 11);
+    }
+
+    #[test]
+    fn test_inspector_opens_scrolls_and_sorts() {
+        use crate::inspect::MappedFile;
+
+        let (mut app, _rx) = fixture::app();
+        feed(
+            &mut app,
+            vec![DataUpdate::Processes {
+                processes: vec![fixture::process(4242, "postgres", 1.0, 100)],
+            }],
+        );
+        key(&mut app, KeyCode::Char('i'));
+        assert_eq!(app.expanded_pid, Some(4242));
+        assert_eq!(app.inspect_view, Some(InspectView::Maps));
+
+        let map = |name: &str| MappedFile {
+            name: name.to_string(),
+            regions: 1,
+            size: 4096,
+            rss: Some(4096),
+            pss: Some(4096),
+            swap: None,
+        };
+        app.broker.insert(
+            Request::Maps(4242),
+            Response::Maps(Some(vec![
+                map("[heap]"),
+                map("[stack]"),
+                map("/usr/lib/libc.so.6"),
+            ])),
+        );
+        for _ in 0..5 {
+            key(&mut app, KeyCode::Char('j'));
+        }
+        assert_eq!(app.inspect_scroll, 2, "scroll stops at the last row");
+        key(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.map_sort, MapSort::Pss);
+        assert!(
+            matches!(app.get_sort_mode(), SortMode::Cpu),
+            "list sort untouched"
+        );
+
+        // Tab cycles to files, then threads, where s sorts threads instead
+        key(&mut app, KeyCode::Tab);
+        key(&mut app, KeyCode::Tab);
+        assert_eq!(app.inspect_view, Some(InspectView::Threads));
+        assert_eq!(app.inspect_scroll, 0);
+        key(&mut app, KeyCode::Char('s'));
+        assert_eq!(app.thread_sort, ThreadSort::Time);
+        assert_eq!(app.map_sort, MapSort::Pss);
+
+        key(&mut app, KeyCode::Esc);
+        assert_eq!(app.inspect_view, None);
+        key(&mut app, KeyCode::Char('d'));
+        assert!(app.breakout_context, "d toggles the context sections");
+        assert!(app.is_running(), "Esc closes the inspector, not oversee");
+        assert_eq!(app.expanded_pid, Some(4242));
+    }
+
+    #[test]
+    fn test_kill_reports_signal_outcome() {
+        let (mut app, _rx) = fixture::app();
+        feed(
+            &mut app,
+            vec![DataUpdate::Processes {
+                processes: vec![fixture::process(4242, "postgres", 1.0, 100)],
+            }],
+        );
+        key(&mut app, KeyCode::Char('K'));
+        key(&mut app, KeyCode::Char('y'));
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while app.signal_status.is_none() {
+            assert!(std::time::Instant::now() < deadline, "no signal outcome");
+            feed(&mut app, vec![]);
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(
+            app.signal_status.as_deref(),
+            Some("4242 not signalled: permission denied")
+        );
+        key(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.signal_status, None, "cleared by the next key");
+    }
+
+    #[test]
+    fn test_grouped_view_ignores_process_keys() {
+        let (mut app, _rx) = fixture::app();
+        feed(
+            &mut app,
+            vec![DataUpdate::Processes {
+                processes: vec![
+                    fixture::process(1, "init", 1.0, 100),
+                    fixture::process(4242, "postgres", 1.0, 100),
+                ],
+            }],
+        );
+        key(&mut app, KeyCode::Char('c'));
+        for code in [
+            KeyCode::Char('j'),
+            KeyCode::Enter,
+            KeyCode::Char('i'),
+            KeyCode::Char('K'),
+        ] {
+            key(&mut app, code);
+        }
+        assert_eq!(app.selected_process, 0);
+        assert_eq!(app.expanded_pid, None);
+        assert_eq!(app.inspect_view, None);
+        assert!(!app.kill_confirmation_mode, "no kill of a hidden row");
+
+        key(&mut app, KeyCode::Char('c'));
+        key(&mut app, KeyCode::Char('j'));
+        assert_eq!(app.selected_process, 1);
+    }
+}
@@ -0,0 +1,490 @@
+//! On-demand work for single processes, answered off the UI thread.
+//!
+//! The collector streams system-wide data every tick; anything only wanted
+//! for the process the user is looking at (breakout details, inspector
+//! views, context sections, signals) goes through here instead. Callers
+//! simply ask for whatever is on screen every frame: the broker drops
+//! requests that are already in flight or were answered recently enough for
+//! their kind, and discards answers to requests cancelled in the meantime.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::context::{self, ProcessContext};
+use crate::inspect::{self, InspectView, MappedFile, OpenFile, ThreadInfo, ThreadSampler};
+use crate::process::{self, ProcessDetails};
+
+/// How long SIGTERM gets before escalating to SIGKILL.
+const TERMINATE_GRACE: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Request {
+    /// fd and thread counts and memory, for the breakout.
+    Details(u32),
+    Maps(u32),
+    Files(u32),
+    Threads(u32),
+    /// Environment, limits, cgroup, namespaces and start time.
+    Context(u32),
+    /// SIGTERM, then SIGKILL if the process outlives the grace period.
+    Terminate(u32),
+}
+
+impl Request {
+    /// The request behind an inspector view.
+    pub fn for_view(view: InspectView, pid: u32) -> Self {
+        match view {
+            InspectView::Maps => Request::Maps(pid),
+            InspectView::Files => Request::Files(pid),
+            InspectView::Threads => Request::Threads(pid),
+        }
+    }
+
+    pub fn pid(self) -> u32 {
+        match self {
+            Request::Details(pid)
+            | Request::Maps(pid)
+            | Request::Files(pid)
+            | Request::Threads(pid)
+            | Request::Context(pid)
+            | Request::Terminate(pid) => pid,
+        }
+    }
+
+    /// How long an answer stays current; asking again sooner is a no-op.
+    fn max_age(self) -> Duration {
+        match self {
+            Request::Details(_) | Request::Maps(_) | Request::Files(_) | Request::Threads(_) => {
+                Duration::from_secs(2)
+            }
+            // Environment and limits rarely change after start
+            Request::Context(_) => Duration::from_secs(10),
+            // Every ask is a fresh signal
+            Request::Terminate(_) => Duration::ZERO,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Response {
+    Details(ProcessDetails),
+    /// `None` when unreadable, here and below.
+    Maps(Option<Vec<MappedFile>>),
+    Files(Option<Vec<OpenFile>>),
+    Threads(Option<Vec<ThreadInfo>>),
+    Context(ProcessContext),
+    Terminate(SignalOutcome),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalOutcome {
+    /// Exited within the grace period after SIGTERM.
+    Terminated,
+    /// Ignored SIGTERM and was sent SIGKILL.
+    Killed,
+    /// `kill` failed with this errno (ESRCH, EPERM, ...).
+    Failed(i32),
+}
+
+impl SignalOutcome {
+    pub fn describe(self, pid: u32) -> String {
+        match self {
+            SignalOutcome::Terminated => format!("{} terminated", pid),
+            SignalOutcome::Killed => format!("{} ignored SIGTERM, killed", pid),
+            SignalOutcome::Failed(errno) if errno == libc::EPERM => {
+                format!("{} not signalled: permission denied", pid)
+            }
+            SignalOutcome::Failed(errno) if errno == libc::ESRCH => {
+                format!("{} not signalled: no such process", pid)
+            }
+            SignalOutcome::Failed(errno) => format!("{} not signalled: errno {}", pid, errno),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Job {
+    id: u64,
+    request: Request,
+    cancelled: Arc<AtomicBool>,
+}
+
+#[derive(Debug)]
+struct InFlight {
+    id: u64,
+    cancelled: Arc<AtomicBool>,
+}
+
+#[derive(Debug)]
+struct Answer {
+    response: Response,
+    at: Instant,
+}
+
+/// Where answers go: the job id, the request and its response.
+pub type AnswerSender = Sender<(u64, Request, Response)>;
+
+#[derive(Debug)]
+pub struct Broker {
+    jobs: Sender<Job>,
+    answers: Receiver<(u64, Request, Response)>,
+    in_flight: HashMap<Request, InFlight>,
+    cache: HashMap<Request, Answer>,
+    next_id: u64,
+}
+
+impl Broker {
+    /// Broker backed by the real fetchers on this machine.
+    pub fn new() -> Self {
+        let (answer_tx, answers) = mpsc::channel();
+        Self::forwarding(local_dispatch(answer_tx), answers)
+    }
+
+    /// Broker answering with `handler` on its worker thread; tests pass a
+    /// fake so nothing touches the host.
+    #[cfg(test)]
+    pub fn with_handler(mut handler: impl FnMut(Request) -> Response + Send + 'static) -> Self {
+        let (answer_tx, answers) = mpsc::channel();
+        Self::forwarding(
+            move |id, request| {
+                let _ = answer_tx.send((id, request, handler(request)));
+            },
+            answers,
+        )
+    }
+
+    /// Broker handing each job to `dispatch` on its worker thread, with the
+    /// answers arriving on `answers` whenever and from wherever they are
+    /// produced.
+    pub fn forwarding(
+        mut dispatch: impl FnMut(u64, Request) + Send + 'static,
+        answers: Receiver<(u64, Request, Response)>,
+    ) -> Self {
+        let (jobs, job_rx) = mpsc::channel::<Job>();
+        thread::spawn(move || {
+            for job in job_rx {
+                // Cancelled while queued: don't bother
+                if !job.cancelled.load(Ordering::Relaxed) {
+                    dispatch(job.id, job.request);
+                }
+            }
+        });
+        Broker {
+            jobs,
+            answers,
+            in_flight: HashMap::new(),
+            cache: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Ask for `request` unless it is already in flight or its last answer
+    /// is still current.
+    pub fn request(&mut self, request: Request) {
+        if self.in_flight.contains_key(&request) {
+            return;
+        }
+        if let Some(answer) = self.cache.get(&request)
+            && answer.at.elapsed() < request.max_age()
+        {
+            return;
+        }
+        self.send(request);
+    }
+
+    fn send(&mut self, request: Request) {
+        self.next_id += 1;
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let job = Job {
+            id: self.next_id,
+            request,
+            cancelled: Arc::clone(&cancelled),
+        };
+        if self.jobs.send(job).is_ok() {
+            self.in_flight.insert(
+                request,
+                InFlight {
+                    id: self.next_id,
+                    cancelled,
+                },
+            );
+        }
+    }
+
+    /// Drop `request` if it is in flight: skipped if still queued, its
+    /// answer discarded otherwise.
+    pub fn cancel(&mut self, request: Request) {
+        if let Some(in_flight) = self.in_flight.remove(&request) {
+            in_flight.cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Cancel everything about `pid` and drop its cached answers. Signals
+    /// already sent are left to finish.
+    pub fn forget(&mut self, pid: u32) {
+        let pending: Vec<Request> = self
+            .in_flight
+            .keys()
+            .filter(|r| r.pid() == pid && !matches!(r, Request::Terminate(_)))
+            .copied()
+            .collect();
+        for request in pending {
+            self.cancel(request);
+        }
+        self.cache.retain(|r, _| r.pid() != pid);
+    }
+
+    /// Collect answers from the worker. Returns the requests answered since
+    /// the last poll.
+    pub fn poll(&mut self) -> Vec<Request> {
+        let mut answered = Vec::new();
+        while let Ok((id, request, response)) = self.answers.try_recv() {
+            // Superseded or cancelled
+            if self.in_flight.get(&request).is_none_or(|f| f.id != id) {
+                continue;
+            }
+            self.in_flight.remove(&request);
+            self.cache.insert(
+                request,
+                Answer {
+                    response,
+                    at: Instant::now(),
+                },
+            );
+            answered.push(request);
+        }
+        answered
+    }
+
+    /// Latest answer to `request`, however old.
+    pub fn get(&self, request: Request) -> Option<&Response> {
+        self.cache.get(&request).map(|a| &a.response)
+    }
+
+    /// Store an answer directly, as if the worker had produced it.
+    #[cfg(test)]
+    pub fn insert(&mut self, request: Request, response: Response) {
+        self.cache.insert(
+            request,
+            Answer {
+                response,
+                at: Instant::now(),
+            },
+        );
+    }
+}
+
+/// Answer requests about this machine's processes. Fetches run in turn on
+/// the caller's thread; each signal gets a thread of its own, so the grace
+/// period never holds up the fetches queued behind it.
+pub fn local_dispatch(answers: AnswerSender) -> impl FnMut(u64, Request) + Send + 'static {
+    let mut sampler = ThreadSampler::default();
+    move |id, request| {
+        let response = match request {
+            Request::Details(pid) => Response::Details(process::fetch_process_details(pid)),
+            Request::Maps(pid) => Response::Maps(inspect::fetch_maps(pid)),
+            Request::Files(pid) => Response::Files(inspect::fetch_open_files(pid)),
+            Request::Threads(pid) => Response::Threads(inspect::fetch_threads(pid, &mut sampler)),
+            Request::Context(pid) => Response::Context(context::fetch_context(pid)),
+            Request::Terminate(pid) => {
+                let answers = answers.clone();
+                thread::spawn(move || {
+                    let _ = answers.send((id, request, Response::Terminate(terminate(pid))));
+                });
+                return;
+            }
+        };
+        let _ = answers.send((id, request, response));
+    }
+}
+
+/// SIGTERM for a graceful shutdown, escalating to SIGKILL if the process is
+/// still there after the grace period.
+fn terminate(pid: u32) -> SignalOutcome {
+    let pid = pid as libc::pid_t;
+    if unsafe { libc::kill(pid, libc::SIGTERM) } != 0 {
+        return SignalOutcome::Failed(errno());
+    }
+    let deadline = Instant::now() + TERMINATE_GRACE;
+    while Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(100));
+        // kill() with signal 0 checks if the process exists without
+        // signalling; an exited child its parent has not reaped still does
+        if unsafe { libc::kill(pid, 0) } != 0 || is_zombie(pid as u32) {
+            return SignalOutcome::Terminated;
+        }
+    }
+    if unsafe { libc::kill(pid, libc::SIGKILL) } != 0 {
+        // Exited just as the grace period ran out
+        return match errno() {
+            libc::ESRCH => SignalOutcome::Terminated,
+            errno => SignalOutcome::Failed(errno),
+        };
+    }
+    SignalOutcome::Killed
+}
+
+/// Whether `pid` has exited and only waits to be reaped.
+#[cfg(target_os = "linux")]
+fn is_zombie(pid: u32) -> bool {
+    std::fs::read_to_string(format!("/proc/{}/stat", pid))
+        .ok()
+        .and_then(|text| inspect::parse_task_stat(&text))
+        .is_some_and(|stat| stat.state == 'Z')
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_zombie(_pid: u32) -> bool {
+    false
+}
+
+fn errno() -> i32 {
+    std::io::Error::last_os_error().raw_os_error().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// A broker whose handler counts calls and waits for `gate` before
+    /// answering each one.
+    fn gated() -> (Broker, Sender<()>, Arc<Mutex<Vec<Request>>>) {
+        let (gate_tx, gate_rx) = mpsc::channel::<()>();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&calls);
+        let broker = Broker::with_handler(move |request| {
+            log.lock().unwrap().push(request);
+            let _ = gate_rx.recv();
+            Response::Maps(Some(Vec::new()))
+        });
+        (broker, gate_tx, calls)
+    }
+
+    fn wait_for(broker: &mut Broker, request: Request) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !broker.poll().contains(&request) {
+            assert!(Instant::now() < deadline, "no answer to {:?}", request);
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_deduplicates_and_caches_per_kind() {
+        let (mut broker, gate, calls) = gated();
+        broker.request(Request::Maps(1));
+        broker.request(Request::Maps(1));
+        gate.send(()).unwrap();
+        wait_for(&mut broker, Request::Maps(1));
+        assert!(matches!(
+            broker.get(Request::Maps(1)),
+            Some(Response::Maps(Some(_)))
+        ));
+
+        // Still current: no new fetch. Another kind or pid is its own entry.
+        broker.request(Request::Maps(1));
+        broker.request(Request::Files(1));
+        gate.send(()).unwrap();
+        wait_for(&mut broker, Request::Files(1));
+        assert_eq!(
+            *calls.lock().unwrap(),
+            [Request::Maps(1), Request::Files(1)]
+        );
+
+        assert!(Request::Context(1).max_age() > Request::Maps(1).max_age());
+        assert_eq!(Request::Terminate(1).max_age(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_cancelled_requests_are_skipped_or_discarded() {
+        let (mut broker, gate, calls) = gated();
+        // Maps is running when cancelled, Threads still queued behind it
+        broker.request(Request::Maps(1));
+        broker.request(Request::Threads(1));
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while calls.lock().unwrap().is_empty() {
+            assert!(Instant::now() < deadline);
+            thread::sleep(Duration::from_millis(5));
+        }
+        broker.forget(1);
+        broker.request(Request::Details(2));
+        gate.send(()).unwrap();
+        gate.send(()).unwrap();
+        wait_for(&mut broker, Request::Details(2));
+        assert!(broker.get(Request::Maps(1)).is_none(), "answer discarded");
+        assert_eq!(
+            *calls.lock().unwrap(),
+            [Request::Maps(1), Request::Details(2)],
+            "queued Threads never ran"
+        );
+
+        // forget() also drops cached answers
+        broker.insert(Request::Files(3), Response::Files(None));
+        assert!(matches!(
+            broker.get(Request::Files(3)),
+            Some(Response::Files(None))
+        ));
+        broker.forget(3);
+        assert!(broker.get(Request::Files(3)).is_none());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_signals_do_not_hold_up_fetches() {
+        use std::process::Command;
+
+        // Ignores SIGTERM, so terminating it takes the whole grace period
+        let mut stubborn = Command::new("sh")
+            .args(["-c", "trap '' TERM; sleep 5"])
+            .spawn()
+            .unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let (answer_tx, answers) = mpsc::channel();
+        let mut dispatch = local_dispatch(answer_tx);
+        let started = Instant::now();
+        dispatch(1, Request::Terminate(stubborn.id()));
+        dispatch(2, Request::Details(std::process::id()));
+
+        let (id, _, _) = answers.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(id, 2);
+        assert!(started.elapsed() < TERMINATE_GRACE);
+        let (id, _, response) = answers.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(id, 1);
+        assert!(matches!(
+            response,
+            Response::Terminate(SignalOutcome::Killed)
+        ));
+        stubborn.wait().unwrap();
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_unreaped_exit_counts_as_terminated() {
+        // Our own child: it stays a zombie until we wait for it
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let started = Instant::now();
+        assert_eq!(terminate(child.id()), SignalOutcome::Terminated);
+        assert!(started.elapsed() < TERMINATE_GRACE);
+        child.wait().unwrap();
+    }
+
+    #[test]
+    fn test_signal_outcomes() {
+        assert_eq!(terminate(u32::MAX >> 2), SignalOutcome::Failed(libc::ESRCH));
+        assert_eq!(
+            SignalOutcome::Failed(libc::EPERM).describe(1),
+            "1 not signalled: permission denied"
+        );
+        assert_eq!(
+            SignalOutcome::Killed.describe(42),
+            "42 ignored SIGTERM, killed"
+        );
+    }
+}
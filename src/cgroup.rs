@@ -0,0 +1,452 @@
+//! Container and service attribution from cgroup paths.
+//!
+//! On Linux every process sits in a cgroup whose path names what started it:
+//! systemd puts each service in `<unit>.service`, docker and podman create a
+//! scope (or directory) per container id, and the kubelet nests containers
+//! under a slice per pod. Parsing those paths lets the process list group
+//! processes into the services and containers people actually deploy, and
+//! the cgroup v2 controller files give their limits.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::context::parse_cgroup;
+use crate::process::ProcessInfo;
+
+/// Container ids are shown shortened, as `docker ps` does.
+const SHORT_ID: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Runtime {
+    Docker,
+    Podman,
+    Containerd,
+    CriO,
+}
+
+impl Runtime {
+    pub fn label(self) -> &'static str {
+        match self {
+            Runtime::Docker => "docker",
+            Runtime::Podman => "podman",
+            Runtime::Containerd => "containerd",
+            Runtime::CriO => "crio",
+        }
+    }
+}
+
+/// What a process belongs to, most specific first: a pod outranks its
+/// container, a container outranks the service that runs it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Workload {
+    /// Kubernetes pod by uid.
+    Pod {
+        uid: String,
+    },
+    Container {
+        runtime: Runtime,
+        id: String,
+    },
+    /// systemd service, e.g. `postgresql.service`.
+    Unit(String),
+}
+
+impl Workload {
+    pub fn label(&self) -> String {
+        match self {
+            Workload::Pod { uid } => format!("pod:{}", short(uid)),
+            Workload::Container { runtime, id } => format!("{}:{}", runtime.label(), short(id)),
+            Workload::Unit(name) => name.clone(),
+        }
+    }
+}
+
+fn short(id: &str) -> &str {
+    id.get(..SHORT_ID).unwrap_or(id)
+}
+
+/// Attribute a cgroup path such as
+/// `/system.slice/docker-3f2a….scope` or
+/// `/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod1a2b_….slice/cri-containerd-….scope`.
+pub fn classify(path: &str) -> Option<Workload> {
+    let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+
+    // Pods: "kubepods-<qos>-pod<uid>.slice" (systemd driver, dashes in the
+    // uid escaped to underscores) or "pod<uid>" (cgroupfs driver)
+    if components.iter().any(|c| c.starts_with("kubepods")) {
+        for component in &components {
+            let name = component.strip_suffix(".slice").unwrap_or(component);
+            if let Some(at) = name.rfind("pod") {
+                let uid = &name[at + 3..];
+                if !uid.is_empty() && (at == 0 || name[..at].ends_with('-')) {
+                    return Some(Workload::Pod {
+                        uid: uid.replace('_', "-"),
+                    });
+                }
+            }
+        }
+    }
+
+    // Containers, innermost first
+    for (i, component) in components.iter().enumerate().rev() {
+        let scope = component.strip_suffix(".scope").unwrap_or(component);
+        for (prefix, runtime) in [
+            ("docker-", Runtime::Docker),
+            ("libpod-", Runtime::Podman),
+            ("cri-containerd-", Runtime::Containerd),
+            ("crio-", Runtime::CriO),
+        ] {
+            if let Some(id) = scope.strip_prefix(prefix)
+                && is_container_id(id)
+            {
+                return Some(Workload::Container {
+                    runtime,
+                    id: id.to_string(),
+                });
+            }
+        }
+        // cgroupfs driver: "/docker/<id>"
+        if i > 0 && components[i - 1] == "docker" && is_container_id(component) {
+            return Some(Workload::Container {
+                runtime: Runtime::Docker,
+                id: component.to_string(),
+            });
+        }
+    }
+
+    // The innermost service; user services nest under user@<uid>.service
+    components
+        .iter()
+        .rev()
+        .find(|c| c.ends_with(".service") && !c.starts_with("user@"))
+        .map(|c| Workload::Unit(c.to_string()))
+}
+
+fn is_container_id(id: &str) -> bool {
+    id.len() >= SHORT_ID && id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Limits and usage of one cgroup v2 directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CgroupLimits {
+    /// `memory.max`; `None` when unlimited.
+    pub memory_max: Option<u64>,
+    /// `memory.current`, page cache included.
+    pub memory_current: Option<u64>,
+    /// `cpu.max` quota in cores; `None` when unlimited.
+    pub cpu_max: Option<f32>,
+}
+
+impl CgroupLimits {
+    /// Read the controller files of `path` under the cgroup v2 mount `root`.
+    /// Missing files (v1 hosts, disabled controllers) leave fields unset.
+    pub fn read(root: &Path, path: &str) -> Self {
+        let dir = root.join(path.trim_start_matches('/'));
+        let read = |name: &str| std::fs::read_to_string(dir.join(name)).ok();
+        CgroupLimits {
+            memory_max: read("memory.max").and_then(|t| parse_memory_max(&t)),
+            memory_current: read("memory.current").and_then(|t| t.trim().parse().ok()),
+            cpu_max: read("cpu.max").and_then(|t| parse_cpu_max(&t)),
+        }
+    }
+
+    pub fn is_limited(&self) -> bool {
+        self.memory_max.is_some() || self.cpu_max.is_some()
+    }
+}
+
+/// `memory.max`: bytes, or "max".
+pub fn parse_memory_max(text: &str) -> Option<u64> {
+    text.trim().parse().ok()
+}
+
+/// `cpu.max`: "<quota> <period>" in microseconds, quota "max" if unlimited.
+pub fn parse_cpu_max(text: &str) -> Option<f32> {
+    let mut fields = text.split_whitespace();
+    let quota: f32 = fields.next()?.parse().ok()?;
+    let period: f32 = fields.next()?.parse().ok()?;
+    (period > 0.0).then(|| quota / period)
+}
+
+/// A process's cgroup and what it says about the process.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessCgroup {
+    pub path: String,
+    pub workload: Option<Workload>,
+    pub limits: CgroupLimits,
+}
+
+/// The path to attribute from `/proc/<pid>/cgroup`: the unified hierarchy,
+/// else systemd's v1 hierarchy, which carries the same unit layout.
+pub fn process_cgroup_path(text: &str) -> Option<String> {
+    let entries = parse_cgroup(text);
+    entries
+        .iter()
+        .find(|e| e.hierarchy == 0)
+        .or_else(|| {
+            entries
+                .iter()
+                .find(|e| e.controllers.iter().any(|c| c == "name=systemd"))
+        })
+        .map(|e| e.path.clone())
+}
+
+/// Looks up each process's cgroup once and the limits of each cgroup once
+/// per full refresh.
+#[derive(Debug)]
+pub struct CgroupResolver {
+    proc_root: PathBuf,
+    cgroup_root: PathBuf,
+    /// Start time and path per pid; `None` where unreadable or not Linux.
+    paths: HashMap<u32, (u64, Option<String>)>,
+    limits: HashMap<String, CgroupLimits>,
+}
+
+impl CgroupResolver {
+    pub fn new(proc_root: impl Into<PathBuf>, cgroup_root: impl Into<PathBuf>) -> Self {
+        CgroupResolver {
+            proc_root: proc_root.into(),
+            cgroup_root: cgroup_root.into(),
+            paths: HashMap::new(),
+            limits: HashMap::new(),
+        }
+    }
+
+    pub fn system() -> Self {
+        Self::new("/proc", "/sys/fs/cgroup")
+    }
+
+    /// Start a full refresh: forget exited pids and re-read limits.
+    pub fn begin_refresh(&mut self, live: impl Fn(u32) -> bool) {
+        self.paths.retain(|pid, _| live(*pid));
+        self.limits.clear();
+    }
+
+    /// The cgroup of `pid`, which started at `start_time`. A different start
+    /// time means the pid was reused, so the cached path is read again.
+    pub fn resolve(&mut self, pid: u32, start_time: u64) -> Option<ProcessCgroup> {
+        let read = || {
+            std::fs::read_to_string(self.proc_root.join(pid.to_string()).join("cgroup"))
+                .ok()
+                .and_then(|text| process_cgroup_path(&text))
+        };
+        let path = match self.paths.get(&pid) {
+            Some((started, path)) if *started == start_time => path.clone(),
+            _ => {
+                let path = read();
+                self.paths.insert(pid, (start_time, path.clone()));
+                path
+            }
+        }?;
+        let cgroup_root = &self.cgroup_root;
+        let limits = *self
+            .limits
+            .entry(path.clone())
+            .or_insert_with(|| CgroupLimits::read(cgroup_root, &path));
+        Some(ProcessCgroup {
+            workload: classify(&path),
+            path,
+            limits,
+        })
+    }
+}
+
+/// Processes of one workload, or of none (`workload: None`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkloadGroup {
+    pub workload: Option<Workload>,
+    pub processes: usize,
+    pub cpu_usage: f32,
+    pub memory: u64,
+    /// Limits of the group's cgroup, when all its processes share one.
+    pub limits: Option<CgroupLimits>,
+}
+
+impl WorkloadGroup {
+    pub fn label(&self) -> String {
+        self.workload
+            .as_ref()
+            .map_or_else(|| "(host)".to_string(), Workload::label)
+    }
+}
+
+/// Sum CPU and resident memory per workload, busiest first.
+pub fn group_by_workload<'a>(
+    processes: impl IntoIterator<Item = &'a ProcessInfo>,
+) -> Vec<WorkloadGroup> {
+    let mut groups: Vec<WorkloadGroup> = Vec::new();
+    let mut index: HashMap<Option<Workload>, usize> = HashMap::new();
+    let mut paths: Vec<Option<&str>> = Vec::new();
+    for process in processes {
+        let cgroup = process.cgroup.as_ref();
+        let workload = cgroup.and_then(|c| c.workload.clone());
+        let i = *index.entry(workload.clone()).or_insert_with(|| {
+            groups.push(WorkloadGroup {
+                workload,
+                processes: 0,
+                cpu_usage: 0.0,
+                memory: 0,
+                limits: cgroup.map(|c| c.limits),
+            });
+            paths.push(cgroup.map(|c| c.path.as_str()));
+            groups.len() - 1
+        });
+        let group = &mut groups[i];
+        group.processes += 1;
+        group.cpu_usage += process.cpu_usage;
+        group.memory += process.memory;
+        // A pod spans one cgroup per container; no single limit applies
+        if paths[i] != cgroup.map(|c| c.path.as_str()) {
+            group.limits = None;
+        }
+    }
+    groups.sort_by(|a, b| {
+        b.cpu_usage
+            .total_cmp(&a.cpu_usage)
+            .then(b.memory.cmp(&a.memory))
+    });
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::fixture::{self, FixtureDir};
+
+    const DOCKER_ID: &str = "3f2a1b9c0d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8";
+
+    #[test]
+    fn test_classify_cgroup_paths() {
+        let container = |runtime| {
+            Some(Workload::Container {
+                runtime,
+                id: DOCKER_ID.to_string(),
+            })
+        };
+        assert_eq!(
+            classify(&format!("/system.slice/docker-{}.scope", DOCKER_ID)),
+            container(Runtime::Docker)
+        );
+        assert_eq!(
+            classify(&format!("/docker/{}", DOCKER_ID)),
+            container(Runtime::Docker)
+        );
+        assert_eq!(
+            classify(&format!(
+                "/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-{}.scope/container",
+                DOCKER_ID
+            )),
+            container(Runtime::Podman)
+        );
+        assert_eq!(
+            classify(&format!(
+                "/kubepods.slice/kubepods-burstable.slice/\
+                 kubepods-burstable-pod1a2b3c4d_5e6f_7081_92a3_b4c5d6e7f809.slice/\
+                 cri-containerd-{}.scope",
+                DOCKER_ID
+            )),
+            Some(Workload::Pod {
+                uid: "1a2b3c4d-5e6f-7081-92a3-b4c5d6e7f809".to_string()
+            })
+        );
+        assert_eq!(
+            classify("/kubepods/besteffort/pod8d9e0f1a-2b3c-4d5e-6f70-8192a3b4c5d6/abc"),
+            Some(Workload::Pod {
+                uid: "8d9e0f1a-2b3c-4d5e-6f70-8192a3b4c5d6".to_string()
+            })
+        );
+        assert_eq!(
+            classify("/system.slice/postgresql.service"),
+            Some(Workload::Unit("postgresql.service".to_string()))
+        );
+        assert_eq!(
+            classify("/user.slice/user-1000.slice/user@1000.service/app.slice/syncthing.service"),
+            Some(Workload::Unit("syncthing.service".to_string()))
+        );
+        assert_eq!(
+            classify("/user.slice/user-1000.slice/session-2.scope"),
+            None
+        );
+        assert_eq!(classify("/"), None);
+
+        assert_eq!(
+            container(Runtime::Docker).unwrap().label(),
+            "docker:3f2a1b9c0d4e"
+        );
+        assert_eq!(
+            classify("/kubepods/pod8d9e0f1a-2b3c-4d5e-6f70-8192a3b4c5d6")
+                .unwrap()
+                .label(),
+            "pod:8d9e0f1a-2b3"
+        );
+    }
+
+    #[test]
+    fn test_resolve_limits_and_group() {
+        let fx = FixtureDir::new("cgroup");
+        let scope = format!("system.slice/docker-{}.scope", DOCKER_ID);
+        fx.write("proc/10/cgroup", &format!("0::/{}\n", scope));
+        fx.write("proc/11/cgroup", &format!("0::/{}\n", scope));
+        fx.write(
+            "proc/20/cgroup",
+            "12:memory:/system.slice/nginx.service\n1:name=systemd:/system.slice/nginx.service\n",
+        );
+        fx.write(&format!("sys/{}/memory.max", scope), "536870912\n");
+        fx.write(&format!("sys/{}/memory.current", scope), "268435456\n");
+        fx.write(&format!("sys/{}/cpu.max", scope), "150000 100000\n");
+        fx.write("sys/system.slice/nginx.service/memory.max", "max\n");
+        fx.write("sys/system.slice/nginx.service/cpu.max", "max 100000\n");
+
+        let mut resolver = CgroupResolver::new(fx.path().join("proc"), fx.path().join("sys"));
+        let docker = resolver.resolve(10, 1_000).unwrap();
+        assert_eq!(docker.limits.memory_max, Some(512 * 1024 * 1024));
+        assert_eq!(docker.limits.memory_current, Some(256 * 1024 * 1024));
+        assert_eq!(docker.limits.cpu_max, Some(1.5));
+        let nginx = resolver.resolve(20, 1_000).unwrap();
+        assert_eq!(
+            nginx.workload,
+            Some(Workload::Unit("nginx.service".to_string()))
+        );
+        assert!(!nginx.limits.is_limited());
+        assert!(resolver.resolve(99, 1_000).is_none());
+
+        // Cached while the same process holds the pid; re-read once reused
+        fx.write("proc/20/cgroup", &format!("0::/{}\n", scope));
+        assert_eq!(resolver.resolve(20, 1_000).unwrap().path, nginx.path);
+        assert_eq!(
+            resolver.resolve(20, 2_000).unwrap().path,
+            format!("/{}", scope)
+        );
+
+        let with = |pid: u32, cpu: f32, mem_mb: u64, cgroup: Option<ProcessCgroup>| ProcessInfo {
+            cgroup,
+            ..fixture::process(pid, "p", cpu, mem_mb)
+        };
+        let groups = group_by_workload(&[
+            with(10, 20.0, 100, Some(docker.clone())),
+            with(20, 5.0, 50, Some(nginx)),
+            with(11, 30.0, 200, Some(docker)),
+            with(30, 1.0, 10, None),
+        ]);
+        let summary: Vec<(String, usize, f32, u64)> = groups
+            .iter()
+            .map(|g| {
+                (
+                    g.label(),
+                    g.processes,
+                    g.cpu_usage,
+                    g.memory / (1024 * 1024),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("docker:3f2a1b9c0d4e".to_string(), 2, 50.0, 300),
+                ("nginx.service".to_string(), 1, 5.0, 50),
+                ("(host)".to_string(), 1, 1.0, 10),
+            ]
+        );
+        assert_eq!(groups[0].limits.and_then(|l| l.cpu_max), Some(1.5));
+    }
+}
@@ -0,0 +1,149 @@
+//! User configuration loaded from a small INI-style file.
+//!
+//! The file lives at `$OVERSEE_CONFIG`, or `$XDG_CONFIG_HOME/oversee/config`,
+//! or `~/.config/oversee/config`. A missing file is not an error: every
+//! section is optional and oversee runs with defaults.
+//!
+//! ```text
+//! [watch]
+//! process = postgres
+//! process = node server.js
+//! port = 5432
+//!
+//! [gpu]
+//! cores = 38
+//!
+//! [fleet]
+//! host = buildbox-1
+//! host = buildbox-2:7400
+//! ```
+
+use std::path::PathBuf;
+
+use crate::watch::WatchTarget;
+
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// Processes and listening ports the user expects to be running.
+    pub watch: Vec<WatchTarget>,
+    /// GPU core count to use instead of the detected one.
+    pub gpu_cores: Option<usize>,
+    /// Agents `oversee fleet` connects to when none are given.
+    pub fleet: Vec<String>,
+}
+
+impl Config {
+    /// Load the config from the default location, falling back to defaults
+    /// when the file is absent or unreadable.
+    pub fn load() -> Self {
+        match config_path().and_then(|p| std::fs::read_to_string(p).ok()) {
+            Some(contents) => Self::parse(&contents),
+            None => Self::default(),
+        }
+    }
+
+    /// Parse config contents. Unknown sections, unknown keys and malformed
+    /// lines are ignored so an old binary tolerates a newer config.
+    pub fn parse(contents: &str) -> Self {
+        let mut config = Config::default();
+        let mut section = String::new();
+
+        for raw in contents.lines() {
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = name.trim().to_lowercase();
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+
+            match (section.as_str(), key.as_str()) {
+                ("watch", "process") => config.watch.push(WatchTarget::Process(value.to_string())),
+                ("watch", "port") => {
+                    if let Ok(port) = value.parse::<u16>() {
+                        config.watch.push(WatchTarget::Port(port));
+                    }
+                }
+                ("gpu", "cores") => {
+                    config.gpu_cores = value.parse::<usize>().ok().filter(|&n| n > 0);
+                }
+                ("fleet", "host") => config.fleet.push(value.to_string()),
+                _ => {}
+            }
+        }
+
+        config
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("OVERSEE_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+    if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME") {
+        return Some(PathBuf::from(dir).join("oversee").join("config"));
+    }
+    std::env::var_os("HOME").map(|home| {
+        PathBuf::from(home)
+            .join(".config")
+            .join("oversee")
+            .join("config")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_watch_section() {
+        let config = Config::parse(
+            "# dev services\n\
+             [watch]\n\
+             process = postgres\n\
+             process = node server.js\n\
+             port = 5432\n\
+             port = not-a-port\n\
+             \n\
+             [other]\n\
+             process = ignored\n",
+        );
+        assert_eq!(
+            config.watch,
+            vec![
+                WatchTarget::Process("postgres".to_string()),
+                WatchTarget::Process("node server.js".to_string()),
+                WatchTarget::Port(5432),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_gpu_cores_override() {
+        assert_eq!(Config::parse("[gpu]\ncores = 38\n").gpu_cores, Some(38));
+        assert_eq!(Config::parse("[gpu]\ncores = 0\n").gpu_cores, None);
+        assert_eq!(Config::parse("cores = 38\n").gpu_cores, None);
+    }
+
+    #[test]
+    fn test_parse_fleet_hosts() {
+        let config = Config::parse("[fleet]\nhost = buildbox-1\nhost = 10.0.0.7:7400\n");
+        assert_eq!(config.fleet, ["buildbox-1", "10.0.0.7:7400"]);
+        assert!(Config::parse("host = buildbox-1\n").fleet.is_empty());
+    }
+
+    #[test]
+    fn test_parse_empty_is_default() {
+        assert!(Config::parse("").watch.is_empty());
+        assert!(Config::parse("process = outside-section").watch.is_empty());
+    }
+}
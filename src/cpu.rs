@@ -1,9 +1,206 @@
+use std::collections::BTreeMap;
+use std::path::Path;
 use std::thread;
 use sysinfo::{CpuRefreshKind, RefreshKind, System};
 
+/// One logical CPU at the latest refresh.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoreSample {
+    pub label: String,
+    pub usage: f32,
+    /// Current clock in MHz; 0 where the platform does not report it.
+    pub frequency_mhz: u64,
+}
+
+/// A group of logical CPUs of the same core type, e.g. the E or P cores.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoreCluster {
+    /// Short name: "P", "E", or "M" for a middle tier.
+    pub name: String,
+    /// Logical CPU indices, ascending.
+    pub cpus: Vec<usize>,
+}
+
+/// Static CPU description gathered once at startup.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CpuTopology {
+    pub brand: String,
+    pub physical_cores: Option<usize>,
+    pub logical_cores: usize,
+    /// Core-type clusters, ordered by first CPU. Empty on homogeneous systems.
+    pub clusters: Vec<CoreCluster>,
+}
+
+/// Cumulative CPU time counters in clock ticks, summed over all CPUs.
+/// macOS only tracks user, nice, system and idle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+}
+
+impl CpuTimes {
+    fn as_array(&self) -> [u64; 8] {
+        [
+            self.user,
+            self.nice,
+            self.system,
+            self.idle,
+            self.iowait,
+            self.irq,
+            self.softirq,
+            self.steal,
+        ]
+    }
+}
+
+/// Share of CPU time spent in each state over one interval, in percent.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CpuBreakdown {
+    pub user: f32,
+    pub nice: f32,
+    pub system: f32,
+    pub idle: f32,
+    pub iowait: f32,
+    pub irq: f32,
+    pub softirq: f32,
+    pub steal: f32,
+}
+
+impl CpuBreakdown {
+    /// Breakdown of the time elapsed between two counter snapshots. A
+    /// counter that went backwards (iowait may, per proc(5)) counts as zero;
+    /// `None` only when no time passed at all.
+    pub fn between(previous: &CpuTimes, current: &CpuTimes) -> Option<Self> {
+        let before = previous.as_array();
+        let after = current.as_array();
+        let mut delta = [0u64; 8];
+        for i in 0..8 {
+            delta[i] = after[i].saturating_sub(before[i]);
+        }
+        let total: u64 = delta.iter().sum();
+        if total == 0 {
+            return None;
+        }
+        let pct = |ticks: u64| (ticks as f64 * 100.0 / total as f64) as f32;
+        Some(CpuBreakdown {
+            user: pct(delta[0]),
+            nice: pct(delta[1]),
+            system: pct(delta[2]),
+            idle: pct(delta[3]),
+            iowait: pct(delta[4]),
+            irq: pct(delta[5]),
+            softirq: pct(delta[6]),
+            steal: pct(delta[7]),
+        })
+    }
+
+    /// Interrupt handling plus time stolen by the hypervisor.
+    pub fn other(&self) -> f32 {
+        self.irq + self.softirq + self.steal
+    }
+}
+
+/// Aggregate counters from the first `cpu ` line of `/proc/stat`.
+pub fn parse_proc_stat(text: &str) -> Option<CpuTimes> {
+    let line = text.lines().find(|l| l.starts_with("cpu "))?;
+    let fields: Vec<u64> = line
+        .split_whitespace()
+        .skip(1)
+        .map_while(|f| f.parse().ok())
+        .collect();
+    // Older kernels stop before steal; missing fields count as zero.
+    let field = |i: usize| fields.get(i).copied().unwrap_or(0);
+    if fields.len() < 4 {
+        return None;
+    }
+    Some(CpuTimes {
+        user: field(0),
+        nice: field(1),
+        system: field(2),
+        idle: field(3),
+        iowait: field(4),
+        irq: field(5),
+        softirq: field(6),
+        steal: field(7),
+    })
+}
+
+fn read_cpu_times() -> Option<CpuTimes> {
+    proc_stat()
+        .and_then(|text| parse_proc_stat(&text))
+        .or_else(mach_cpu_times)
+}
+
+#[cfg(target_os = "linux")]
+fn proc_stat() -> Option<String> {
+    std::fs::read_to_string("/proc/stat").ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn proc_stat() -> Option<String> {
+    None
+}
+
+/// Per-processor tick counters from `host_processor_info`, summed.
+#[cfg(target_os = "macos")]
+#[allow(deprecated)] // libc points the mach port helpers at the mach2 crate, which we don't depend on
+fn mach_cpu_times() -> Option<CpuTimes> {
+    let mut cpu_count: libc::natural_t = 0;
+    let mut info: libc::processor_info_array_t = std::ptr::null_mut();
+    let mut info_count: libc::mach_msg_type_number_t = 0;
+    let result = unsafe {
+        libc::host_processor_info(
+            libc::mach_host_self(),
+            libc::PROCESSOR_CPU_LOAD_INFO,
+            &mut cpu_count,
+            &mut info,
+            &mut info_count,
+        )
+    };
+    if result != libc::KERN_SUCCESS || info.is_null() {
+        return None;
+    }
+
+    let states = libc::CPU_STATE_MAX as usize;
+    let ticks = unsafe { std::slice::from_raw_parts(info, info_count as usize) };
+    let mut times = CpuTimes::default();
+    for cpu in ticks.chunks_exact(states).take(cpu_count as usize) {
+        // Counters are 32-bit and wrap; the breakdown rejects wrapped deltas.
+        let state = |s: libc::c_int| cpu[s as usize] as u32 as u64;
+        times.user += state(libc::CPU_STATE_USER);
+        times.system += state(libc::CPU_STATE_SYSTEM);
+        times.idle += state(libc::CPU_STATE_IDLE);
+        times.nice += state(libc::CPU_STATE_NICE);
+    }
+
+    unsafe {
+        libc::vm_deallocate(
+            libc::mach_task_self(),
+            info as libc::vm_address_t,
+            info_count as usize * std::mem::size_of::<libc::integer_t>(),
+        );
+    }
+    Some(times)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn mach_cpu_times() -> Option<CpuTimes> {
+    None
+}
+
 #[derive(Debug)]
 pub struct CpuMonitor {
     system: System,
+    topology: CpuTopology,
+    last_times: Option<CpuTimes>,
+    breakdown: Option<CpuBreakdown>,
 }
 
 impl CpuMonitor {
@@ -20,11 +217,35 @@ impl CpuMonitor {
         // Second refresh to get actual usage
         system.refresh_cpu_usage();
 
-        CpuMonitor { system }
+        let topology = CpuTopology {
+            brand: system
+                .cpus()
+                .first()
+                .map(|cpu| clean_brand(cpu.brand()))
+                .unwrap_or_default(),
+            physical_cores: system.physical_core_count(),
+            logical_cores: system.cpus().len(),
+            clusters: detect_clusters(),
+        };
+
+        CpuMonitor {
+            system,
+            topology,
+            last_times: read_cpu_times(),
+            breakdown: None,
+        }
     }
 
     pub fn refresh(&mut self) {
-        self.system.refresh_cpu_usage();
+        self.system
+            .refresh_cpu_specifics(CpuRefreshKind::new().with_cpu_usage().with_frequency());
+
+        let times = read_cpu_times();
+        self.breakdown = match (&self.last_times, &times) {
+            (Some(previous), Some(current)) => CpuBreakdown::between(previous, current),
+            _ => None,
+        };
+        self.last_times = times;
     }
 
     #[allow(dead_code)]
@@ -32,14 +253,27 @@ impl CpuMonitor {
         self.system.cpus().len()
     }
 
-    pub fn cpu_usages(&self) -> Vec<(String, f32)> {
+    pub fn cores(&self) -> Vec<CoreSample> {
         self.system
             .cpus()
             .iter()
             .enumerate()
-            .map(|(i, cpu)| (format!("CPU {}", i), cpu.cpu_usage()))
+            .map(|(i, cpu)| CoreSample {
+                label: format!("CPU {}", i),
+                usage: cpu.cpu_usage(),
+                frequency_mhz: cpu.frequency(),
+            })
             .collect()
     }
+
+    pub fn topology(&self) -> &CpuTopology {
+        &self.topology
+    }
+
+    /// Time-in-state split since the previous refresh.
+    pub fn breakdown(&self) -> Option<CpuBreakdown> {
+        self.breakdown
+    }
 }
 
 impl Default for CpuMonitor {
@@ -47,3 +281,291 @@ impl Default for CpuMonitor {
         Self::new()
     }
 }
+
+/// Drop trademark noise from a brand string ("Intel(R) Core(TM) i7" -> "Intel Core i7").
+fn clean_brand(brand: &str) -> String {
+    brand
+        .replace("(R)", "")
+        .replace("(TM)", "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse a kernel CPU list such as `0-3,8,10-11`.
+pub fn parse_cpu_list(list: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+    for part in list.trim().split(',').filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                if let (Ok(start), Ok(end)) = (start.parse::<usize>(), end.parse::<usize>()) {
+                    cpus.extend(start..=end);
+                }
+            }
+            None => cpus.extend(part.parse::<usize>().ok()),
+        }
+    }
+    cpus
+}
+
+/// Core-type clusters from sysfs under `devices` (normally `/sys/devices`).
+///
+/// Intel hybrid parts publish their P and E cores as the `cpu_core` and
+/// `cpu_atom` PMUs. Arm big.LITTLE systems give each CPU a relative
+/// `cpu_capacity`, so CPUs are grouped by capacity, biggest first.
+pub fn linux_clusters(devices: &Path) -> Vec<CoreCluster> {
+    let read = |rel: &str| std::fs::read_to_string(devices.join(rel)).ok();
+
+    if let (Some(p), Some(e)) = (read("cpu_core/cpus"), read("cpu_atom/cpus")) {
+        return ordered(vec![
+            CoreCluster {
+                name: "P".to_string(),
+                cpus: parse_cpu_list(&p),
+            },
+            CoreCluster {
+                name: "E".to_string(),
+                cpus: parse_cpu_list(&e),
+            },
+        ]);
+    }
+
+    let Ok(entries) = std::fs::read_dir(devices.join("system/cpu")) else {
+        return Vec::new();
+    };
+    let mut by_capacity: BTreeMap<u64, Vec<usize>> = BTreeMap::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(cpu) = name
+            .to_str()
+            .and_then(|n| n.strip_prefix("cpu"))
+            .and_then(|n| n.parse::<usize>().ok())
+        else {
+            continue;
+        };
+        if let Some(capacity) = std::fs::read_to_string(entry.path().join("cpu_capacity"))
+            .ok()
+            .and_then(|c| c.trim().parse::<u64>().ok())
+        {
+            by_capacity.entry(capacity).or_default().push(cpu);
+        }
+    }
+    if by_capacity.len() < 2 {
+        return Vec::new();
+    }
+
+    let tiers = by_capacity.len();
+    let clusters = by_capacity
+        .into_values()
+        .rev()
+        .enumerate()
+        .map(|(rank, mut cpus)| {
+            cpus.sort_unstable();
+            let name = match rank {
+                0 => "P",
+                r if r == tiers - 1 => "E",
+                _ => "M",
+            };
+            CoreCluster {
+                name: name.to_string(),
+                cpus,
+            }
+        })
+        .collect();
+    ordered(clusters)
+}
+
+/// Clusters from macOS `hw.perflevelN` as `(name, logical cpu count)`,
+/// level 0 being the fastest. The kernel numbers the slowest cores first.
+pub fn macos_clusters(levels: &[(String, usize)]) -> Vec<CoreCluster> {
+    if levels.len() < 2 {
+        return Vec::new();
+    }
+    let mut next_cpu = 0;
+    let clusters = levels
+        .iter()
+        .rev()
+        .map(|(name, count)| {
+            let cpus = (next_cpu..next_cpu + count).collect();
+            next_cpu += count;
+            CoreCluster {
+                name: name.chars().next().unwrap_or('?').to_string(),
+                cpus,
+            }
+        })
+        .collect();
+    ordered(clusters)
+}
+
+fn ordered(mut clusters: Vec<CoreCluster>) -> Vec<CoreCluster> {
+    clusters.retain(|c| !c.cpus.is_empty());
+    clusters.sort_by_key(|c| c.cpus[0]);
+    clusters
+}
+
+fn detect_clusters() -> Vec<CoreCluster> {
+    match perf_levels() {
+        Some(levels) => macos_clusters(&levels),
+        None => linux_clusters(Path::new("/sys/devices")),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn perf_levels() -> Option<Vec<(String, usize)>> {
+    fn sysctl_u32(name: &str) -> Option<u32> {
+        let name = std::ffi::CString::new(name).ok()?;
+        let mut value: u32 = 0;
+        let mut length = std::mem::size_of::<u32>();
+        let result = unsafe {
+            libc::sysctlbyname(
+                name.as_ptr(),
+                &mut value as *mut _ as *mut libc::c_void,
+                &mut length,
+                std::ptr::null_mut(),
+                0,
+            )
+        };
+        (result == 0).then_some(value)
+    }
+
+    fn sysctl_string(name: &str) -> Option<String> {
+        let name = std::ffi::CString::new(name).ok()?;
+        let mut buf = [0u8; 64];
+        let mut length = buf.len();
+        let result = unsafe {
+            libc::sysctlbyname(
+                name.as_ptr(),
+                buf.as_mut_ptr() as *mut libc::c_void,
+                &mut length,
+                std::ptr::null_mut(),
+                0,
+            )
+        };
+        if result != 0 {
+            return None;
+        }
+        let text = &buf[..length.min(buf.len())];
+        let end = text.iter().position(|&b| b == 0).unwrap_or(text.len());
+        Some(String::from_utf8_lossy(&text[..end]).into_owned())
+    }
+
+    let count = sysctl_u32("hw.nperflevels")?;
+    (0..count)
+        .map(|level| {
+            let name = sysctl_string(&format!("hw.perflevel{}.name", level))?;
+            let cpus = sysctl_u32(&format!("hw.perflevel{}.logicalcpu", level))?;
+            Some((name, cpus as usize))
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "macos"))]
+fn perf_levels() -> Option<Vec<(String, usize)>> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::fixture::FixtureDir;
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("0-3,8,10-11\n"), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(parse_cpu_list(""), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_linux_hybrid_and_capacity_clusters() {
+        let intel = FixtureDir::new("cpu-hybrid");
+        intel.write("cpu_core/cpus", "0-7\n");
+        intel.write("cpu_atom/cpus", "8-15\n");
+        let clusters = linux_clusters(intel.path());
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(
+            (clusters[0].name.as_str(), clusters[0].cpus.len()),
+            ("P", 8)
+        );
+        assert_eq!(clusters[1].cpus, (8..16).collect::<Vec<_>>());
+
+        // Arm tri-cluster: little cores first, one prime core last
+        let arm = FixtureDir::new("cpu-capacity");
+        for (cpu, capacity) in [(0, 446), (1, 446), (2, 871), (3, 871), (4, 1024)] {
+            arm.write(
+                &format!("system/cpu/cpu{}/cpu_capacity", cpu),
+                &format!("{}\n", capacity),
+            );
+        }
+        let names: Vec<(String, Vec<usize>)> = linux_clusters(arm.path())
+            .into_iter()
+            .map(|c| (c.name, c.cpus))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("E".to_string(), vec![0, 1]),
+                ("M".to_string(), vec![2, 3]),
+                ("P".to_string(), vec![4]),
+            ]
+        );
+
+        let homogeneous = FixtureDir::new("cpu-flat");
+        homogeneous.write("system/cpu/cpu0/cpu_capacity", "1024\n");
+        homogeneous.write("system/cpu/cpu1/cpu_capacity", "1024\n");
+        assert!(linux_clusters(homogeneous.path()).is_empty());
+    }
+
+    #[test]
+    fn test_macos_perf_levels_number_efficiency_cores_first() {
+        let clusters = macos_clusters(&[
+            ("Performance".to_string(), 8),
+            ("Efficiency".to_string(), 2),
+        ]);
+        assert_eq!(clusters[0].name, "E");
+        assert_eq!(clusters[0].cpus, vec![0, 1]);
+        assert_eq!(clusters[1].name, "P");
+        assert_eq!(clusters[1].cpus, (2..10).collect::<Vec<_>>());
+
+        assert!(macos_clusters(&[("Performance".to_string(), 8)]).is_empty());
+    }
+
+    #[test]
+    fn test_breakdown_from_proc_stat() {
+        let before = parse_proc_stat(
+            "cpu  1000 0 500 8000 100 0 50 0 0 0\ncpu0 500 0 250 4000 50 0 25 0 0 0\nintr 1\n",
+        )
+        .unwrap();
+        let after = parse_proc_stat("cpu  1300 0 600 8450 200 0 100 0 0 0\n").unwrap();
+        let breakdown = CpuBreakdown::between(&before, &after).unwrap();
+        assert_eq!(breakdown.user, 30.0);
+        assert_eq!(breakdown.system, 10.0);
+        assert_eq!(breakdown.idle, 45.0);
+        assert_eq!(breakdown.iowait, 10.0);
+        assert_eq!(breakdown.other(), 5.0);
+
+        // Old kernels without steal/guest columns
+        let short = parse_proc_stat("cpu  1 2 3 4\n").unwrap();
+        assert_eq!(short.steal, 0);
+        assert!(CpuBreakdown::between(&after, &before).is_none());
+        assert!(CpuBreakdown::between(&after, &after).is_none());
+
+        // iowait dipping does not lose the rest of the breakdown
+        let dipped = parse_proc_stat(
+            "cpu  1400 0 700 8500 150 0 100 0 0 0
+",
+        )
+        .unwrap();
+        let breakdown = CpuBreakdown::between(&after, &dipped).unwrap();
+        assert_eq!(breakdown.iowait, 0.0);
+        assert_eq!(breakdown.user, 40.0);
+        assert_eq!(breakdown.idle, 20.0);
+    }
+
+    #[test]
+    fn test_clean_brand() {
+        assert_eq!(
+            clean_brand("12th Gen Intel(R) Core(TM) i7-12700H"),
+            "12th Gen Intel Core i7-12700H"
+        );
+        assert_eq!(clean_brand("Apple M1 Pro"), "Apple M1 Pro");
+    }
+}
@@ -0,0 +1,103 @@
+//! DRM client accounting parsed from `/proc/<pid>/fdinfo`.
+//!
+//! Kernel DRM drivers (amdgpu, i915, xe, msm, panfrost, ...) expose cumulative
+//! per-engine busy time for every open client. Sampling the counters twice
+//! gives utilisation over the interval.
+
+use std::collections::HashMap;
+
+/// Engine busy counters for one DRM client, as reported in `fdinfo`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DrmClient {
+    pub client_id: u64,
+    /// PCI address of the device the client belongs to, e.g. `0000:00:02.0`.
+    pub pdev: Option<String>,
+    /// Cumulative busy time per engine in nanoseconds, e.g. `gfx`, `render`.
+    pub engines: HashMap<String, u64>,
+}
+
+/// Parse a `/proc/<pid>/fdinfo/<fd>` file. Returns `None` for descriptors
+/// that are not DRM clients.
+pub fn parse_drm_fdinfo(contents: &str) -> Option<DrmClient> {
+    let mut client = DrmClient::default();
+    let mut is_drm = false;
+
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if key == "drm-client-id" {
+            client.client_id = value.parse().ok()?;
+            is_drm = true;
+        } else if key == "drm-pdev" {
+            client.pdev = Some(value.to_string());
+        } else if let Some(engine) = key.strip_prefix("drm-engine-") {
+            // Capacity lines ("drm-engine-capacity-*") carry engine counts, not time.
+            if engine.starts_with("capacity-") {
+                continue;
+            }
+            if let Some(ns) = value
+                .strip_suffix("ns")
+                .and_then(|v| v.trim().parse::<u64>().ok())
+            {
+                client.engines.insert(engine.to_string(), ns);
+            }
+        }
+    }
+
+    is_drm.then_some(client)
+}
+
+/// Utilisation of the busiest engine between two samples of the same client.
+pub fn busiest_engine_percent(prev: &DrmClient, cur: &DrmClient, elapsed_ns: u64) -> f32 {
+    if elapsed_ns == 0 {
+        return 0.0;
+    }
+    cur.engines
+        .iter()
+        .map(|(engine, &ns)| {
+            let before = prev.engines.get(engine).copied().unwrap_or(ns);
+            ns.saturating_sub(before) as f64 / elapsed_ns as f64 * 100.0
+        })
+        .fold(0.0_f64, f64::max)
+        .min(100.0) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const I915_FDINFO: &str = "pos:\t0\n\
+        flags:\t02100002\n\
+        mnt_id:\t25\n\
+        drm-driver:\ti915\n\
+        drm-client-id:\t42\n\
+        drm-pdev:\t0000:00:02.0\n\
+        drm-engine-render:\t2500000000 ns\n\
+        drm-engine-video:\t100000000 ns\n\
+        drm-engine-capacity-video:\t2\n";
+
+    #[test]
+    fn test_parse_drm_fdinfo() {
+        let client = parse_drm_fdinfo(I915_FDINFO).expect("drm client");
+        assert_eq!(client.client_id, 42);
+        assert_eq!(client.pdev.as_deref(), Some("0000:00:02.0"));
+        assert_eq!(client.engines.get("render"), Some(&2_500_000_000));
+        assert_eq!(client.engines.get("video"), Some(&100_000_000));
+        assert!(!client.engines.contains_key("capacity-video"));
+
+        assert!(parse_drm_fdinfo("pos:\t0\nflags:\t02\n").is_none());
+    }
+
+    #[test]
+    fn test_busiest_engine_percent() {
+        let prev = parse_drm_fdinfo(I915_FDINFO).unwrap();
+        let mut cur = prev.clone();
+        // Render busy for 0.5s and video for 0.1s over a 1s window.
+        cur.engines.insert("render".to_string(), 3_000_000_000);
+        cur.engines.insert("video".to_string(), 200_000_000);
+        assert_eq!(busiest_engine_percent(&prev, &cur, 1_000_000_000), 50.0);
+        assert_eq!(busiest_engine_percent(&prev, &cur, 0), 0.0);
+    }
+}
@@ -0,0 +1,436 @@
+//! Process lifecycle event log.
+//!
+//! Each process snapshot is diffed against the previous one to record
+//! spawns, exits and restarts (the same command re-spawned under a new pid).
+//! Restarts are matched against exits from a short trailing window so a
+//! crash-looping service is caught even when its exit and respawn land on
+//! different ticks. Only processes that outlived a snapshot can be restarted;
+//! short commands run over and over (prompt hooks, cron jobs, compiler
+//! workers) are plain spawns.
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::process::ProcessInfo;
+
+const MAX_EVENTS: usize = 1000;
+/// How long an exited command stays eligible to be paired with a respawn.
+const RESTART_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventKind {
+    Spawned,
+    /// Exited, with the last CPU% and resident memory we observed.
+    Exited {
+        cpu_usage: f32,
+        memory: u64,
+    },
+    /// Same command came back under a new pid.
+    Restarted {
+        old_pid: u32,
+        count: u32,
+    },
+}
+
+impl EventKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EventKind::Spawned => "spawn",
+            EventKind::Exited { .. } => "exit",
+            EventKind::Restarted { .. } => "restart",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProcessEvent {
+    pub at: SystemTime,
+    pub pid: u32,
+    pub name: String,
+    pub cmd: String,
+    pub kind: EventKind,
+}
+
+impl ProcessEvent {
+    /// One-line description without the timestamp, shared by the UI and export.
+    pub fn describe(&self) -> String {
+        match &self.kind {
+            EventKind::Spawned => format!("{} {}", self.pid, self.cmd),
+            EventKind::Exited { cpu_usage, memory } => format!(
+                "{} {} (last cpu {:.1}%, mem {:.0} MB)",
+                self.pid,
+                self.cmd,
+                cpu_usage,
+                *memory as f64 / (1024.0 * 1024.0)
+            ),
+            EventKind::Restarted { old_pid, count } => {
+                format!("{}→{} {} (restart #{})", old_pid, self.pid, self.cmd, count)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Snapshot {
+    /// Consecutive snapshots the pid has appeared in.
+    seen: u32,
+    name: String,
+    cmd: String,
+    cpu_usage: f32,
+    memory: u64,
+}
+
+#[derive(Debug, Clone)]
+struct RecentExit {
+    at: SystemTime,
+    pid: u32,
+    cmd: String,
+}
+
+#[derive(Debug, Default)]
+pub struct EventLog {
+    events: VecDeque<ProcessEvent>,
+    previous: HashMap<u32, Snapshot>,
+    recent_exits: VecDeque<RecentExit>,
+    /// Restarts per command, with the time of the latest paired exit.
+    restart_counts: HashMap<String, (u32, SystemTime)>,
+    primed: bool,
+}
+
+impl EventLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Events oldest first.
+    pub fn events(&self) -> &VecDeque<ProcessEvent> {
+        &self.events
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Diff a fresh snapshot against the previous one. The first snapshot only
+    /// primes the baseline so startup doesn't log every running process.
+    pub fn observe(&mut self, processes: &[ProcessInfo], now: SystemTime) {
+        let current: HashMap<u32, Snapshot> = processes
+            .iter()
+            .map(|p| {
+                (
+                    p.pid,
+                    Snapshot {
+                        seen: self.previous.get(&p.pid).map_or(1, |s| s.seen + 1),
+                        name: p.name.clone(),
+                        cmd: p.cmd.clone(),
+                        cpu_usage: p.cpu_usage,
+                        memory: p.memory,
+                    },
+                )
+            })
+            .collect();
+
+        if !self.primed {
+            self.previous = current;
+            self.primed = true;
+            return;
+        }
+
+        let recent = |at: SystemTime| {
+            now.duration_since(at)
+                .map(|age| age <= RESTART_WINDOW)
+                .unwrap_or(true)
+        };
+        self.recent_exits.retain(|exit| recent(exit.at));
+        // A command that has stopped exiting starts counting afresh
+        self.restart_counts.retain(|_, (_, at)| recent(*at));
+
+        // Exits first so a same-tick respawn can be paired with its exit.
+        let mut exited: Vec<(u32, Snapshot)> = self
+            .previous
+            .iter()
+            .filter(|(pid, _)| !current.contains_key(pid))
+            .map(|(pid, snap)| (*pid, snap.clone()))
+            .collect();
+        exited.sort_by_key(|(pid, _)| *pid);
+        for (pid, snap) in exited {
+            self.push(ProcessEvent {
+                at: now,
+                pid,
+                name: snap.name.clone(),
+                cmd: snap.cmd.clone(),
+                kind: EventKind::Exited {
+                    cpu_usage: snap.cpu_usage,
+                    memory: snap.memory,
+                },
+            });
+            if snap.seen > 1 {
+                self.recent_exits.push_back(RecentExit {
+                    at: now,
+                    pid,
+                    cmd: snap.cmd.clone(),
+                });
+            }
+        }
+
+        let mut spawned: Vec<(&u32, &Snapshot)> = current
+            .iter()
+            .filter(|(pid, _)| !self.previous.contains_key(pid))
+            .collect();
+        spawned.sort_by_key(|(pid, _)| **pid);
+        let mut new_events = Vec::with_capacity(spawned.len());
+        for (&pid, snap) in spawned {
+            let restart_of = self
+                .recent_exits
+                .iter()
+                .position(|exit| exit.cmd == snap.cmd)
+                .and_then(|idx| self.recent_exits.remove(idx));
+            let kind = match restart_of {
+                Some(exit) => {
+                    let (count, at) = self
+                        .restart_counts
+                        .entry(snap.cmd.clone())
+                        .or_insert((0, exit.at));
+                    *count += 1;
+                    *at = exit.at;
+                    EventKind::Restarted {
+                        old_pid: exit.pid,
+                        count: *count,
+                    }
+                }
+                None => EventKind::Spawned,
+            };
+            new_events.push(ProcessEvent {
+                at: now,
+                pid,
+                name: snap.name.clone(),
+                cmd: snap.cmd.clone(),
+                kind,
+            });
+        }
+        for event in new_events {
+            self.push(event);
+        }
+
+        self.previous = current;
+    }
+
+    fn push(&mut self, event: ProcessEvent) {
+        self.events.push_back(event);
+        if self.events.len() > MAX_EVENTS {
+            self.events.pop_front();
+        }
+    }
+
+    /// Write the log as tab-separated lines into `dir`, returning the file path.
+    pub fn export(&self, dir: &Path) -> io::Result<PathBuf> {
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let path = dir.join(format!("oversee-events-{}.tsv", stamp));
+        let mut file = std::fs::File::create(&path)?;
+        writeln!(file, "time\tevent\tpid\tname\tdetail")?;
+        for event in &self.events {
+            writeln!(
+                file,
+                "{}\t{}\t{}\t{}\t{}",
+                format_clock(event.at),
+                event.kind.label(),
+                event.pid,
+                event.name,
+                event.describe()
+            )?;
+        }
+        Ok(path)
+    }
+}
+
+/// Format a wall-clock time as local `HH:MM:SS`.
+pub fn format_clock(at: SystemTime) -> String {
+    let (secs, tm) = local_time(at);
+    match tm {
+        Some(tm) => format!("{:02}:{:02}:{:02}", tm.tm_hour, tm.tm_min, tm.tm_sec),
+        None => {
+            let day = secs.rem_euclid(86_400);
+            format!("{:02}:{:02}:{:02}", day / 3600, (day % 3600) / 60, day % 60)
+        }
+    }
+}
+
+/// Local date and time, `YYYY-MM-DD HH:MM:SS`; falls back to the clock alone
+/// when the local time can't be resolved.
+pub fn format_date_time(at: SystemTime) -> String {
+    match local_time(at) {
+        (_, Some(tm)) => format!(
+            "{}-{:02}-{:02} {:02}:{:02}:{:02}",
+            tm.tm_year + 1900,
+            tm.tm_mon + 1,
+            tm.tm_mday,
+            tm.tm_hour,
+            tm.tm_min,
+            tm.tm_sec
+        ),
+        (_, None) => format_clock(at),
+    }
+}
+
+fn local_time(at: SystemTime) -> (libc::time_t, Option<libc::tm>) {
+    let secs = at
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as libc::time_t)
+        .unwrap_or(0);
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    let ok = unsafe { !libc::localtime_r(&secs, &mut tm).is_null() };
+    (secs, ok.then_some(tm))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proc(pid: u32, cmd: &str) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            name: cmd.split_whitespace().next().unwrap_or("").to_string(),
+            cmd: cmd.to_string(),
+            user: "dev".to_string(),
+            cpu_usage: 12.5,
+            gpu_usage: None,
+            energy_impact: None,
+            memory: 64 * 1024 * 1024,
+            ports: Vec::new(),
+            cwd: None,
+            exe: None,
+            run_time: 0,
+            thread_count: 0,
+            pss: None,
+            uss: None,
+            cgroup: None,
+        }
+    }
+
+    fn kinds(log: &EventLog) -> Vec<(u32, &'static str)> {
+        log.events()
+            .iter()
+            .map(|e| (e.pid, e.kind.label()))
+            .collect()
+    }
+
+    #[test]
+    fn test_first_snapshot_only_primes() {
+        let mut log = EventLog::new();
+        log.observe(&[proc(1, "init"), proc(2, "sshd")], UNIX_EPOCH);
+        assert_eq!(log.len(), 0);
+    }
+
+    #[test]
+    fn test_spawn_and_exit() {
+        let mut log = EventLog::new();
+        let t0 = UNIX_EPOCH + Duration::from_secs(1_000);
+        log.observe(&[proc(1, "init"), proc(2, "sshd")], t0);
+        log.observe(&[proc(1, "init"), proc(3, "vim notes.md")], t0);
+
+        assert_eq!(kinds(&log), vec![(2, "exit"), (3, "spawn")]);
+        match &log.events()[0].kind {
+            EventKind::Exited { cpu_usage, memory } => {
+                assert_eq!(*cpu_usage, 12.5);
+                assert_eq!(*memory, 64 * 1024 * 1024);
+            }
+            other => panic!("expected exit, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_restart_detected_across_ticks() {
+        let mut log = EventLog::new();
+        let t0 = UNIX_EPOCH + Duration::from_secs(1_000);
+        let at = |secs| t0 + Duration::from_secs(secs);
+        log.observe(&[proc(10, "node server.js")], t0);
+        log.observe(&[proc(10, "node server.js")], at(2));
+        // Crash on one tick, respawn on the next.
+        log.observe(&[], at(4));
+        log.observe(&[proc(11, "node server.js")], at(6));
+        log.observe(&[proc(11, "node server.js")], at(8));
+        // And again in the same tick.
+        log.observe(&[proc(12, "node server.js")], at(10));
+
+        assert_eq!(
+            kinds(&log),
+            vec![(10, "exit"), (11, "restart"), (11, "exit"), (12, "restart")]
+        );
+        assert_eq!(
+            log.events()[3].kind,
+            EventKind::Restarted {
+                old_pid: 11,
+                count: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_respawn_outside_window_is_a_spawn() {
+        let mut log = EventLog::new();
+        let t0 = UNIX_EPOCH + Duration::from_secs(1_000);
+        log.observe(&[proc(10, "cron")], t0);
+        log.observe(&[proc(10, "cron")], t0 + Duration::from_secs(2));
+        log.observe(&[], t0 + Duration::from_secs(4));
+        log.observe(&[proc(20, "cron")], t0 + Duration::from_secs(120));
+        assert_eq!(kinds(&log), vec![(10, "exit"), (20, "spawn")]);
+    }
+
+    #[test]
+    fn test_short_lived_repeats_are_spawns() {
+        let mut log = EventLog::new();
+        let t0 = UNIX_EPOCH + Duration::from_secs(1_000);
+        log.observe(&[], t0);
+        // A prompt hook running git status once per tick
+        for (tick, pid) in (1..=3).zip(100..) {
+            log.observe(
+                &[proc(pid, "git status")],
+                t0 + Duration::from_secs(2 * tick),
+            );
+        }
+        log.observe(&[], t0 + Duration::from_secs(8));
+        assert_eq!(
+            kinds(&log),
+            vec![
+                (100, "spawn"),
+                (100, "exit"),
+                (101, "spawn"),
+                (101, "exit"),
+                (102, "spawn"),
+                (102, "exit")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_restart_counts_expire_with_the_window() {
+        let mut log = EventLog::new();
+        let t0 = UNIX_EPOCH + Duration::from_secs(1_000);
+        let at = |secs| t0 + Duration::from_secs(secs);
+        log.observe(&[proc(10, "node server.js")], t0);
+        log.observe(&[proc(10, "node server.js")], at(2));
+        log.observe(&[proc(11, "node server.js")], at(4));
+        assert_eq!(log.restart_counts.len(), 1);
+
+        // Stable for longer than the window: the next restart is #1 again
+        log.observe(&[proc(11, "node server.js")], at(100));
+        assert!(log.restart_counts.is_empty());
+        log.observe(&[proc(12, "node server.js")], at(102));
+        assert_eq!(
+            log.events().back().unwrap().kind,
+            EventKind::Restarted {
+                old_pid: 11,
+                count: 1
+            }
+        );
+    }
+}
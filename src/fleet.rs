@@ -0,0 +1,326 @@
+//! Several agents side by side.
+//!
+//! `oversee fleet` connects to every listed agent and keeps a full `App` per
+//! host, fed by that host's connection exactly as `--connect` would. The
+//! fleet table reads its KPIs and sparklines from those apps, and drilling
+//! into a host simply hands the screen and keyboard to its app until it asks
+//! to quit. Hosts that cannot be reached are retried in the background so one
+//! slow or rebooting machine never stalls the others.
+
+use std::error::Error;
+use std::io;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent};
+
+use crate::DataCommand;
+use crate::app::App;
+use crate::config::Config;
+use crate::remote::{self, Connection};
+use crate::{tui, ui};
+
+/// Wait before reconnecting to a host that is down.
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HostState {
+    Connecting,
+    Up,
+    /// Unreachable or hung up, with the reason.
+    Down(String),
+}
+
+pub struct FleetHost {
+    /// As given, with the default port filled in.
+    pub addr: String,
+    pub state: HostState,
+    /// The host's full UI; kept while it is down so its last readings stay
+    /// on screen, replaced on reconnect.
+    pub app: Option<App>,
+    connection: Option<Connection>,
+    pending: Option<Receiver<io::Result<Connection>>>,
+    retry_at: Option<Instant>,
+}
+
+impl FleetHost {
+    /// The agent's host name once known, else the address.
+    pub fn label(&self) -> &str {
+        self.app
+            .as_ref()
+            .and_then(|app| app.host_info.as_ref())
+            .map_or(self.addr.as_str(), |info| info.name.as_str())
+    }
+}
+
+pub struct Fleet {
+    pub hosts: Vec<FleetHost>,
+    pub selected: usize,
+    /// Host whose full UI is on screen.
+    pub drilled: Option<usize>,
+    pub running: bool,
+    config: Config,
+}
+
+impl Fleet {
+    pub fn new(addrs: &[String], config: Config) -> Self {
+        let hosts = addrs
+            .iter()
+            .map(|addr| FleetHost {
+                addr: remote::with_default_port(addr),
+                state: HostState::Connecting,
+                app: None,
+                connection: None,
+                pending: None,
+                retry_at: None,
+            })
+            .collect();
+        Fleet {
+            hosts,
+            selected: 0,
+            drilled: None,
+            running: true,
+            config,
+        }
+    }
+
+    /// Start due connections, deliver updates to each host's app and notice
+    /// hosts that went away. Returns whether anything changed.
+    pub fn poll(&mut self, now: Instant) -> bool {
+        let mut changed = false;
+        for host in &mut self.hosts {
+            if host.connection.is_none()
+                && host.pending.is_none()
+                && host.retry_at.is_none_or(|at| now >= at)
+            {
+                let (tx, rx) = mpsc::channel();
+                let addr = host.addr.clone();
+                thread::spawn(move || {
+                    let _ = tx.send(remote::connect(addr.as_str()));
+                });
+                host.pending = Some(rx);
+            }
+
+            if let Some(pending) = &host.pending {
+                let result = match pending.try_recv() {
+                    Ok(result) => result,
+                    Err(TryRecvError::Empty) => continue,
+                    Err(TryRecvError::Disconnected) => {
+                        Err(io::Error::other("connect thread exited"))
+                    }
+                };
+                host.pending = None;
+                changed = true;
+                match result {
+                    Ok(mut connection) => {
+                        host.app = Some(connection.app(&host.addr, self.config.clone()));
+                        host.connection = Some(connection);
+                        host.state = HostState::Up;
+                    }
+                    Err(err) => {
+                        host.state = HostState::Down(err.to_string());
+                        host.retry_at = Some(now + RETRY_INTERVAL);
+                    }
+                }
+            }
+
+            if let (Some(connection), Some(app)) = (&host.connection, &mut host.app) {
+                changed |= app.process_updates(&connection.updates);
+                if connection.is_closed() {
+                    app.disconnected = true;
+                    host.connection = None;
+                    host.state = HostState::Down("connection lost".to_string());
+                    host.retry_at = Some(now + RETRY_INTERVAL);
+                    changed = true;
+                }
+            }
+        }
+
+        // Leave a host's UI once it has nothing left to show
+        if let Some(i) = self.drilled
+            && self.hosts[i].connection.is_none()
+        {
+            self.drilled = None;
+        }
+        changed
+    }
+
+    /// The drilled-into host's app.
+    pub fn drilled_app(&mut self) -> Option<&mut App> {
+        self.hosts.get_mut(self.drilled?)?.app.as_mut()
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) {
+        if let Some(app) = self.drilled_app() {
+            app.handle_key_event(key);
+            // Quitting a host's UI returns to the fleet
+            if !app.running {
+                app.running = true;
+                self.drilled = None;
+            }
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.running = false,
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.hosts.len().saturating_sub(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Char('g') | KeyCode::Home => self.selected = 0,
+            KeyCode::Char('G') | KeyCode::End => {
+                self.selected = self.hosts.len().saturating_sub(1);
+            }
+            KeyCode::Enter
+                if self
+                    .hosts
+                    .get(self.selected)
+                    .is_some_and(|h| h.connection.is_some()) =>
+            {
+                self.drilled = Some(self.selected);
+            }
+            _ => {}
+        }
+    }
+
+    /// Stop every agent-side collector this fleet started.
+    fn shutdown(self) {
+        for host in self.hosts {
+            if let Some(connection) = host.connection {
+                let _ = connection.commands.send(DataCommand::Stop);
+                let _ = connection.worker.join();
+            }
+        }
+    }
+}
+
+/// Run the fleet dashboard until the user quits.
+pub fn run(addrs: &[String], config: Config) -> Result<(), Box<dyn Error>> {
+    let mut fleet = Fleet::new(addrs, config);
+    let mut terminal = tui::TuiGuard::new()?;
+
+    fleet.poll(Instant::now());
+    terminal.draw(|f| ui::render_fleet(f, &mut fleet))?;
+
+    while fleet.running {
+        let mut event_occurred = false;
+        if event::poll(Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()?
+        {
+            fleet.handle_key_event(key);
+            event_occurred = true;
+        }
+        let data_updated = fleet.poll(Instant::now());
+        if data_updated || event_occurred {
+            terminal.draw(|f| ui::render_fleet(f, &mut fleet))?;
+        }
+    }
+
+    drop(terminal);
+    fleet.shutdown();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::fixture;
+    use crossterm::event::KeyModifiers;
+    use ratatui::{Terminal, backend::TestBackend};
+    use std::net::TcpListener;
+
+    fn agent(cpu: f32) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || {
+            remote::serve(listener, move || {
+                fixture::sources(vec![fixture::process(4242, "postgres", cpu, 512)])
+            })
+        });
+        addr
+    }
+
+    fn key(fleet: &mut Fleet, code: KeyCode) {
+        fleet.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn screen(terminal: &mut Terminal<TestBackend>, fleet: &mut Fleet) -> Vec<String> {
+        terminal.draw(|f| ui::render_fleet(f, fleet)).unwrap();
+        fixture::screen_text(terminal)
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_fleet_table_and_drill_down() {
+        // Nothing listens on a port we just bound and released
+        let refused = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .to_string();
+        let addrs = vec![agent(12.5), agent(3.0), refused.clone()];
+        let mut fleet = Fleet::new(&addrs, Config::default());
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let ready = |fleet: &Fleet| {
+            fleet.hosts[..2]
+                .iter()
+                .all(|h| h.app.as_ref().is_some_and(|a| a.host_info.is_some()))
+                && matches!(fleet.hosts[2].state, HostState::Down(_))
+        };
+        while !ready(&fleet) {
+            assert!(Instant::now() < deadline, "agents never reported");
+            fleet.poll(Instant::now());
+            thread::sleep(Duration::from_millis(20));
+        }
+        assert_eq!(fleet.hosts[0].state, HostState::Up);
+        assert_eq!(fleet.hosts[0].label(), "fixture-host");
+        assert_eq!(fleet.hosts[2].label(), refused);
+
+        let mut terminal = Terminal::new(TestBackend::new(160, 12)).unwrap();
+        let rows = screen(&mut terminal, &mut fleet);
+        assert!(rows.iter().any(|r| r.contains("fleet (2/3 up)")));
+        assert!(
+            rows.iter()
+                .any(|r| r.contains("HOST") && r.contains("PRESSURE"))
+        );
+        let up: Vec<&String> = rows.iter().filter(|r| r.contains("fixture-host")).collect();
+        assert_eq!(up.len(), 2);
+        assert!(up[0].contains(" 20%") && up[0].contains(" 40%") && up[0].contains(" 50%"));
+        assert!(up[0].contains("normal") && up[0].contains("1.50"));
+        let down = rows.iter().find(|r| r.contains(&refused)).unwrap();
+        assert!(down.contains("down"));
+
+        // A host without a GPU shows a dash rather than an idle 0%
+        fleet.hosts[0].app.as_mut().unwrap().gpu = None;
+        let rows = screen(&mut terminal, &mut fleet);
+        let no_gpu = rows.iter().find(|r| r.contains("fixture-host")).unwrap();
+        assert!(no_gpu.contains(" 20%    -  50%"));
+        assert!(!no_gpu.contains(" 40%"));
+
+        // Enter opens the selected host's full UI; q comes back
+        key(&mut fleet, KeyCode::Down);
+        key(&mut fleet, KeyCode::Enter);
+        assert_eq!(fleet.drilled, Some(1));
+        let mut terminal = Terminal::new(TestBackend::new(160, 60)).unwrap();
+        let rows = screen(&mut terminal, &mut fleet);
+        assert!(rows.iter().any(|r| r.contains("oversee @ fixture-host")));
+        assert!(rows.iter().any(|r| r.contains("postgres")));
+        key(&mut fleet, KeyCode::Char('q'));
+        assert_eq!(fleet.drilled, None);
+        assert!(fleet.running, "q in a host only leaves the host");
+
+        // A host that is down cannot be opened
+        key(&mut fleet, KeyCode::Char('G'));
+        key(&mut fleet, KeyCode::Enter);
+        assert_eq!(fleet.drilled, None);
+        key(&mut fleet, KeyCode::Char('q'));
+        assert!(!fleet.running);
+        fleet.shutdown();
+    }
+}
@@ -1,26 +1,14 @@
+use crate::config::Config;
+use crate::gpu_catalog;
+use crate::powermetrics::{self, PowermetricsSample, SampleStream, SocPower};
+use crate::process_metrics::{ProcessMetricSource, SharedProcessMetric};
 use std::collections::VecDeque;
-#[cfg(feature = "profile")]
-use std::fs::OpenOptions;
-#[cfg(feature = "profile")]
-use std::io::Write as IoWrite;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::io::BufReader;
+use std::process::Child;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread::{self, JoinHandle};
 use std::time::Duration;
-#[cfg(feature = "profile")]
-use std::time::Instant;
-
-/// Log timing data to /tmp/oversee-profile.log. Active only with `--features profile`.
-#[cfg(feature = "profile")]
-fn log_timing(label: &str, duration_ms: u128) {
-    if let Ok(mut file) = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open("/tmp/oversee-profile.log")
-    {
-        let _ = writeln!(file, "{}: {}ms", label, duration_ms);
-    }
-}
 
 #[derive(Debug, Clone)]
 pub struct GpuCoreInfo {
@@ -29,12 +17,34 @@ pub struct GpuCoreInfo {
 
 #[derive(Debug, Clone)]
 pub struct GpuInfo {
+    /// Measured per-core utilisation. Empty when the device only reports an
+    /// aggregate (Apple Silicon, Linux DRM); values are never synthesised.
     pub cores: Vec<GpuCoreInfo>,
     pub overall_utilization: f32, // 0-100%
+    /// Core count from the hardware spec, not the number of measured cores.
     #[allow(dead_code)] // Used for display/info purposes
     pub core_count: usize,
     #[allow(dead_code)] // Used for display/info purposes
     pub chip_name: String,
+    /// Dedicated video memory (used, total) in bytes, where the device has it.
+    pub vram: Option<(u64, u64)>,
+    /// Frequency, power and residency breakdown, where the backend reports it.
+    pub activity: Option<GpuActivity>,
+    /// CPU cluster frequencies and SoC power from the same sampler (Apple Silicon).
+    pub soc: Option<SocPower>,
+}
+
+/// GPU clock and power state from the powermetrics `gpu_power` sampler.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GpuActivity {
+    /// Average frequency while active.
+    pub frequency_mhz: Option<f32>,
+    pub power_mw: Option<f32>,
+    /// Share of the sample interval the GPU was active, 0-100%.
+    pub active_residency: Option<f32>,
+    pub idle_residency: Option<f32>,
+    /// Residency at each DVFS frequency step as `(MHz, %)`.
+    pub frequency_residency: Vec<(u32, f32)>,
 }
 
 impl Default for GpuCoreInfo {
@@ -50,84 +60,106 @@ impl Default for GpuInfo {
             overall_utilization: 0.0,
             core_count: 0,
             chip_name: "Unknown".to_string(),
+            vram: None,
+            activity: None,
+            soc: None,
         }
     }
 }
 
+/// A platform source of device-level GPU metrics.
+pub trait GpuBackend: Send {
+    fn chip_name(&self) -> &str;
+    fn core_count(&self) -> usize;
+    /// Pause or resume sampling while the GPU panel is hidden.
+    fn set_active(&mut self, active: bool);
+    /// Take a fresh reading.
+    fn sample(&mut self) -> GpuInfo;
+    /// Per-process GPU data published by this backend's sampler.
+    fn process_gpu_source(&self) -> Box<dyn ProcessMetricSource>;
+    /// Per-process energy impact, for backends whose sampler reports it.
+    fn process_energy_source(&self) -> Option<Box<dyn ProcessMetricSource>> {
+        None
+    }
+}
+
 /// Shared state for the background powermetrics thread
 struct PowermetricsState {
-    /// GPU utilization stored as u32 bits (reinterpreted as f32)
-    utilization_bits: AtomicU32,
+    /// Latest GPU and SoC readings from the powermetrics stream
+    latest: Mutex<Option<(Option<GpuActivity>, SocPower)>>,
     /// Signal to stop the background thread
     should_stop: AtomicBool,
-    /// When false, the background loop skips spawning `powermetrics`.
+    /// When false, the background loop stops the `powermetrics` stream.
     /// Updated from the data collector when the user toggles the GPU panel.
     active: AtomicBool,
+    /// Per-process GPU% from the powermetrics tasks sampler.
+    task_gpu: SharedProcessMetric,
+    /// Per-process energy impact from the same sampler.
+    task_energy: SharedProcessMetric,
+    /// The running `powermetrics`, so stopping can kill it instead of
+    /// waiting for the blocked read to return the next sample.
+    child: Mutex<Option<Child>>,
 }
 
 impl PowermetricsState {
     fn new() -> Self {
         Self {
-            utilization_bits: AtomicU32::new(0.0_f32.to_bits()),
+            latest: Mutex::new(None),
             should_stop: AtomicBool::new(false),
             active: AtomicBool::new(true),
+            task_gpu: SharedProcessMetric::new(),
+            task_energy: SharedProcessMetric::new(),
+            child: Mutex::new(None),
+        }
+    }
+
+    fn should_run(&self) -> bool {
+        !self.should_stop.load(Ordering::Relaxed) && self.active.load(Ordering::Relaxed)
+    }
+
+    /// Kill the running child; its stdout closes and the reader loop ends.
+    fn kill_child(&self) {
+        if let Ok(mut child) = self.child.lock()
+            && let Some(child) = child.as_mut()
+        {
+            let _ = child.kill();
         }
     }
 
-    fn get_utilization(&self) -> f32 {
-        f32::from_bits(self.utilization_bits.load(Ordering::Relaxed))
+    fn get_latest(&self) -> Option<(Option<GpuActivity>, SocPower)> {
+        self.latest.lock().ok()?.clone()
     }
 
-    fn set_utilization(&self, value: f32) {
-        self.utilization_bits
-            .store(value.to_bits(), Ordering::Relaxed);
+    fn publish(&self, sample: PowermetricsSample) {
+        if let Ok(mut latest) = self.latest.lock() {
+            *latest = Some((sample.gpu, sample.soc));
+        }
+        self.task_gpu.publish(sample.tasks);
+        self.task_energy.publish(sample.energy);
     }
 }
 
-pub struct GpuMonitor {
-    current_info: GpuInfo,
-    core_histories: Vec<VecDeque<f32>>,
-    available: bool,
+/// Apple Silicon backend reading a `powermetrics` stream on a background thread.
+pub struct PowermetricsBackend {
     core_count: usize,
     chip_name: String,
     /// Shared state with background thread
     state: Arc<PowermetricsState>,
     /// Handle to the background thread (for cleanup)
-    _background_thread: Option<JoinHandle<()>>,
+    _background_thread: JoinHandle<()>,
 }
 
-impl GpuMonitor {
-    pub fn new() -> Self {
-        let available = Self::is_apple_silicon();
-        let (chip_name, core_count) = Self::detect_gpu_cores();
-
-        let core_histories = (0..core_count)
-            .map(|_| VecDeque::with_capacity(300))
-            .collect();
-
-        let current_info = GpuInfo {
-            core_count,
-            chip_name: chip_name.clone(),
-            cores: (0..core_count).map(|_| GpuCoreInfo::default()).collect(),
-            ..Default::default()
-        };
-
+impl PowermetricsBackend {
+    /// `core_override` replaces the detected GPU core count (from config).
+    pub fn new(core_override: Option<usize>) -> Self {
+        let (chip_name, core_count) = gpu_catalog::detect(core_override);
         let state = Arc::new(PowermetricsState::new());
+        let state_clone = Arc::clone(&state);
+        let background_thread = thread::spawn(move || {
+            Self::powermetrics_background_loop(state_clone);
+        });
 
-        // Spawn background thread for powermetrics polling if GPU is available
-        let background_thread = if available {
-            let state_clone = Arc::clone(&state);
-            Some(thread::spawn(move || {
-                Self::powermetrics_background_loop(state_clone);
-            }))
-        } else {
-            None
-        };
-
-        GpuMonitor {
-            current_info,
-            core_histories,
-            available,
+        PowermetricsBackend {
             core_count,
             chip_name,
             state,
@@ -135,60 +167,40 @@ impl GpuMonitor {
         }
     }
 
-    pub fn is_available(&self) -> bool {
-        self.available
-    }
-
-    /// Toggle whether the background thread spawns `powermetrics`. When the GPU
-    /// panel is hidden we skip the subprocess to drop idle CPU.
-    pub fn set_active(&self, active: bool) {
-        self.state.active.store(active, Ordering::Relaxed);
-    }
-
-    pub fn refresh(&mut self) {
-        if !self.available {
-            return;
-        }
-
-        // Generate GPU info with per-core data (reads from shared state)
-        self.current_info = self.get_gpu_info();
-
-        // Add each core's data to history
-        for (i, core) in self.current_info.cores.iter().enumerate() {
-            if i < self.core_histories.len() {
-                self.core_histories[i].push_back(core.utilization);
-
-                // Keep only the last 300 points (5 minutes)
-                if self.core_histories[i].len() > 300 {
-                    self.core_histories[i].pop_front();
-                }
-            }
-        }
-    }
-
-    pub fn get_info(&self) -> &GpuInfo {
-        &self.current_info
-    }
-
-    pub fn get_core_count(&self) -> usize {
-        self.core_count
-    }
-
-    /// Background loop that polls powermetrics every 5 seconds
+    /// Background loop owning the powermetrics child. Samples are parsed as
+    /// they arrive; the child is stopped while the GPU panel is hidden and
+    /// restarted (after a back-off) if it exits, e.g. when not run as root.
     fn powermetrics_background_loop(state: Arc<PowermetricsState>) {
         // Initial delay to let the app start up
         thread::sleep(Duration::from_millis(500));
 
         while !state.should_stop.load(Ordering::Relaxed) {
-            // Skip the powermetrics subprocess entirely when the GPU panel is hidden.
-            // The 5s sleep below still runs so we resume promptly when re-activated.
             if state.active.load(Ordering::Relaxed)
-                && let Some(util) = Self::get_gpu_utilization_from_powermetrics()
+                && let Ok(mut child) = powermetrics::spawn()
             {
-                state.set_utilization(util);
+                let stdout = child.stdout.take();
+                if let Ok(mut slot) = state.child.lock() {
+                    *slot = Some(child);
+                }
+                // Stopped between the check above and publishing the child
+                if !state.should_run() {
+                    state.kill_child();
+                }
+                if let Some(stdout) = stdout {
+                    for sample in SampleStream::new(BufReader::new(stdout)) {
+                        if !state.should_run() {
+                            break;
+                        }
+                        state.publish(sample);
+                    }
+                }
+                if let Some(mut child) = state.child.lock().ok().and_then(|mut c| c.take()) {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                }
             }
 
-            // Poll every 5 seconds (increased from 2s for lower overhead)
+            // Back off before restarting; resume promptly once re-activated.
             for _ in 0..50 {
                 if state.should_stop.load(Ordering::Relaxed) {
                     return;
@@ -217,195 +229,331 @@ impl GpuMonitor {
             false
         }
     }
+}
 
-    // Detect GPU core count based on Apple Silicon chip
-    fn detect_gpu_cores() -> (String, usize) {
-        #[cfg(target_os = "macos")]
-        {
-            use std::process::Command;
+impl GpuBackend for PowermetricsBackend {
+    fn chip_name(&self) -> &str {
+        &self.chip_name
+    }
 
-            // Try to get chip name from system_profiler
-            if let Ok(output) = Command::new("system_profiler")
-                .arg("SPHardwareDataType")
-                .output()
-            {
-                let info = String::from_utf8_lossy(&output.stdout);
-
-                // Parse chip name and determine core count
-                if info.contains("Apple M1") {
-                    if info.contains("M1 Max") {
-                        return ("M1 Max".to_string(), 32);
-                    } else if info.contains("M1 Pro") {
-                        return ("M1 Pro".to_string(), 16);
-                    } else {
-                        return ("M1".to_string(), 8);
-                    }
-                } else if info.contains("Apple M2") {
-                    if info.contains("M2 Max") {
-                        return ("M2 Max".to_string(), 38);
-                    } else if info.contains("M2 Pro") {
-                        return ("M2 Pro".to_string(), 19);
-                    } else if info.contains("M2 Ultra") {
-                        return ("M2 Ultra".to_string(), 76);
-                    } else {
-                        return ("M2".to_string(), 10);
-                    }
-                } else if info.contains("Apple M3") {
-                    if info.contains("M3 Max") {
-                        return ("M3 Max".to_string(), 40);
-                    } else if info.contains("M3 Pro") {
-                        return ("M3 Pro".to_string(), 18);
-                    } else {
-                        return ("M3".to_string(), 10);
-                    }
-                } else if info.contains("Apple M4") {
-                    if info.contains("M4 Max") {
-                        return ("M4 Max".to_string(), 40);
-                    } else if info.contains("M4 Pro") {
-                        return ("M4 Pro".to_string(), 20);
-                    } else {
-                        return ("M4".to_string(), 10);
-                    }
-                }
-            }
+    fn core_count(&self) -> usize {
+        self.core_count
+    }
+
+    /// Toggle whether the background thread runs `powermetrics`. When the GPU
+    /// panel is hidden we stop the subprocess to drop idle CPU.
+    fn set_active(&mut self, active: bool) {
+        self.state.active.store(active, Ordering::Relaxed);
+        self.state.task_gpu.set_active(active);
+        self.state.task_energy.set_active(active);
+        if !active {
+            self.state.kill_child();
         }
+    }
 
-        // Fallback
-        ("Unknown".to_string(), 8)
+    /// Apple Silicon exposes no per-core GPU counters, so only the aggregate
+    /// active residency is reported.
+    fn sample(&mut self) -> GpuInfo {
+        let (activity, soc) = self.state.get_latest().unzip();
+        let activity = activity.flatten();
+        GpuInfo {
+            cores: Vec::new(),
+            overall_utilization: activity
+                .as_ref()
+                .and_then(|a| a.active_residency)
+                .unwrap_or(0.0),
+            core_count: self.core_count,
+            chip_name: self.chip_name.clone(),
+            vram: None,
+            activity,
+            soc,
+        }
     }
 
-    // Get real GPU info from shared state (updated by background thread)
-    fn get_gpu_info(&self) -> GpuInfo {
-        // Read utilization from shared state (lock-free)
-        let overall_utilization = self.state.get_utilization();
+    fn process_gpu_source(&self) -> Box<dyn ProcessMetricSource> {
+        Box::new(self.state.task_gpu.clone())
+    }
 
-        // Generate per-core utilization based on overall
-        // Apple Silicon doesn't expose per-core GPU stats, so we estimate
-        let mut cores = Vec::with_capacity(self.core_count);
-        for i in 0..self.core_count {
-            // Distribute load across cores with some variation
-            // First half of cores (performance) get slightly more
-            let core_factor = if i < self.core_count / 2 {
-                1.0 + (i as f32 * 0.02) // Slight increase for first cores
-            } else {
-                0.9 - ((i - self.core_count / 2) as f32 * 0.02) // Slight decrease
-            };
+    fn process_energy_source(&self) -> Option<Box<dyn ProcessMetricSource>> {
+        Some(Box::new(self.state.task_energy.clone()))
+    }
+}
 
-            let core_util = (overall_utilization * core_factor).clamp(0.0, 100.0);
+impl Drop for PowermetricsBackend {
+    fn drop(&mut self) {
+        // Signal the background thread to stop
+        self.state.should_stop.store(true, Ordering::Relaxed);
+        self.state.kill_child();
+    }
+}
+
+/// One backend shared by several collectors, as the agent runs one per
+/// client. Sampling stays on while any handle has the GPU panel visible.
+pub struct SharedGpuBackend {
+    backend: Arc<Mutex<Box<dyn GpuBackend>>>,
+    chip_name: String,
+    core_count: usize,
+    /// Handles currently active, across all clones.
+    active_handles: Arc<AtomicUsize>,
+    active: bool,
+}
 
-            cores.push(GpuCoreInfo {
-                utilization: core_util,
-            });
+impl SharedGpuBackend {
+    /// Wrap `backend`, idle until the first handle is taken.
+    pub fn new(mut backend: Box<dyn GpuBackend>) -> Self {
+        backend.set_active(false);
+        SharedGpuBackend {
+            chip_name: backend.chip_name().to_string(),
+            core_count: backend.core_count(),
+            backend: Arc::new(Mutex::new(backend)),
+            active_handles: Arc::new(AtomicUsize::new(0)),
+            active: false,
         }
+    }
 
-        GpuInfo {
-            cores,
-            overall_utilization,
-            core_count: self.core_count,
+    /// Another handle on the same backend, active from the start.
+    pub fn handle(&self) -> Self {
+        let mut handle = SharedGpuBackend {
+            backend: Arc::clone(&self.backend),
             chip_name: self.chip_name.clone(),
+            core_count: self.core_count,
+            active_handles: Arc::clone(&self.active_handles),
+            active: false,
+        };
+        handle.set_active(true);
+        handle
+    }
+}
+
+impl GpuBackend for SharedGpuBackend {
+    fn chip_name(&self) -> &str {
+        &self.chip_name
+    }
+
+    fn core_count(&self) -> usize {
+        self.core_count
+    }
+
+    fn set_active(&mut self, active: bool) {
+        if active == self.active {
+            return;
         }
+        self.active = active;
+        // Count under the lock so concurrent toggles reach the backend in order
+        let Ok(mut backend) = self.backend.lock() else {
+            return;
+        };
+        let handles = if active {
+            self.active_handles.fetch_add(1, Ordering::Relaxed) + 1
+        } else {
+            self.active_handles.fetch_sub(1, Ordering::Relaxed) - 1
+        };
+        backend.set_active(handles > 0);
     }
 
-    // Query powermetrics for GPU utilization (requires root)
-    fn get_gpu_utilization_from_powermetrics() -> Option<f32> {
-        use std::process::Command;
-
-        // Run powermetrics to get GPU stats
-        // -i 200 = 200ms sample (reduced from 500ms for faster response)
-        // -n 1 = one sample only
-        #[cfg(feature = "profile")]
-        let start = Instant::now();
-        let output = Command::new("powermetrics")
-            .args(["--sampler", "gpu_power", "-i", "200", "-n", "1"])
-            .output()
-            .ok()?;
-        #[cfg(feature = "profile")]
-        log_timing("powermetrics_command", start.elapsed().as_millis());
-
-        if !output.status.success() {
-            return None; // Probably not running as root
+    fn sample(&mut self) -> GpuInfo {
+        self.backend
+            .lock()
+            .map(|mut backend| backend.sample())
+            .unwrap_or_default()
+    }
+
+    fn process_gpu_source(&self) -> Box<dyn ProcessMetricSource> {
+        match self.backend.lock() {
+            Ok(backend) => backend.process_gpu_source(),
+            Err(_) => Box::new(crate::process_metrics::NoProcessMetric),
         }
+    }
+
+    fn process_energy_source(&self) -> Option<Box<dyn ProcessMetricSource>> {
+        self.backend.lock().ok()?.process_energy_source()
+    }
+}
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
+impl Drop for SharedGpuBackend {
+    fn drop(&mut self) {
+        self.set_active(false);
+    }
+}
 
-        // Parse "GPU HW active residency: XX.XX%" or similar patterns
-        for line in stdout.lines() {
-            let line_lower = line.to_lowercase();
+pub struct GpuMonitor {
+    backend: Option<Box<dyn GpuBackend>>,
+    current_info: GpuInfo,
+    core_histories: Vec<VecDeque<f32>>,
+}
 
-            // Look for GPU active residency
-            if line_lower.contains("gpu")
-                && line_lower.contains("active")
-                && line_lower.contains("residency")
-            {
-                // Extract percentage value
-                if let Some(pct) = Self::extract_percentage(line) {
-                    return Some(pct);
-                }
-            }
+impl GpuMonitor {
+    pub fn new(config: &Config) -> Self {
+        Self::with_backend(Self::detect_backend(config))
+    }
 
-            // Alternative: "GPU Power" percentage
-            // Note: Nested if required for MSRV compatibility (let chains are unstable)
-            #[allow(clippy::collapsible_if)]
-            if line_lower.contains("gpu") && line.contains("%") {
-                if let Some(pct) = Self::extract_percentage(line) {
-                    return Some(pct);
-                }
-            }
+    pub fn with_backend(backend: Option<Box<dyn GpuBackend>>) -> Self {
+        let (chip_name, core_count) = backend
+            .as_ref()
+            .map(|b| (b.chip_name().to_string(), b.core_count()))
+            .unwrap_or_else(|| ("Unknown".to_string(), 0));
+
+        let current_info = GpuInfo {
+            core_count,
+            chip_name,
+            ..Default::default()
+        };
+
+        GpuMonitor {
+            backend,
+            current_info,
+            core_histories: Vec::new(),
         }
+    }
 
+    /// Pick the backend for this machine: powermetrics on Apple Silicon, DRM
+    /// sysfs on Linux, nothing elsewhere.
+    pub fn detect_backend(config: &Config) -> Option<Box<dyn GpuBackend>> {
+        if PowermetricsBackend::is_apple_silicon() {
+            return Some(Box::new(PowermetricsBackend::new(config.gpu_cores)));
+        }
+        #[cfg(target_os = "linux")]
+        if let Some(backend) = crate::gpu_sysfs::SysfsGpuBackend::detect() {
+            return Some(Box::new(backend));
+        }
         None
     }
 
-    // Extract percentage value from a line like "GPU HW active residency:   5.23%"
-    fn extract_percentage(line: &str) -> Option<f32> {
-        // Find the percentage value (number followed by %)
-        let mut num_start = None;
-        let mut num_end = None;
+    pub fn is_available(&self) -> bool {
+        self.backend.is_some()
+    }
 
-        for (i, c) in line.chars().enumerate() {
-            if c.is_ascii_digit() || c == '.' {
-                if num_start.is_none() {
-                    num_start = Some(i);
-                }
-                num_end = Some(i + 1);
-            } else if c == '%' && num_end.is_some() {
-                // Found the percentage
-                break;
-            } else if num_start.is_some() && !c.is_ascii_digit() && c != '.' {
-                // Reset if we hit non-numeric before %
-                num_start = None;
-                num_end = None;
-            }
+    /// The GPU as (chip name, core count), for the UI to lay out its panel
+    /// without a sampler of its own.
+    pub fn describe(&self) -> Option<(String, usize)> {
+        let backend = self.backend.as_ref()?;
+        Some((backend.chip_name().to_string(), backend.core_count()))
+    }
+
+    /// Pause or resume backend sampling to match GPU panel visibility.
+    pub fn set_active(&mut self, active: bool) {
+        if let Some(backend) = self.backend.as_mut() {
+            backend.set_active(active);
         }
+    }
 
-        if let (Some(start), Some(end)) = (num_start, num_end) {
-            let num_str: String = line.chars().skip(start).take(end - start).collect();
-            num_str.parse::<f32>().ok()
-        } else {
-            None
+    /// Per-process GPU source sharing the backend's sampler, if there is a GPU.
+    pub fn process_gpu_source(&self) -> Option<Box<dyn ProcessMetricSource>> {
+        self.backend.as_ref().map(|b| b.process_gpu_source())
+    }
+
+    /// Per-process energy impact source, if the backend's sampler has one.
+    pub fn process_energy_source(&self) -> Option<Box<dyn ProcessMetricSource>> {
+        self.backend.as_ref()?.process_energy_source()
+    }
+
+    pub fn refresh(&mut self) {
+        let Some(backend) = self.backend.as_mut() else {
+            return;
+        };
+
+        self.current_info = backend.sample();
+
+        // Add each measured core's data to history
+        if self.core_histories.len() != self.current_info.cores.len() {
+            self.core_histories = (0..self.current_info.cores.len())
+                .map(|_| VecDeque::with_capacity(300))
+                .collect();
+        }
+        for (history, core) in self.core_histories.iter_mut().zip(&self.current_info.cores) {
+            history.push_back(core.utilization);
+
+            // Keep only the last 300 points (5 minutes)
+            if history.len() > 300 {
+                history.pop_front();
+            }
         }
     }
-}
 
-impl Default for GpuMonitor {
-    fn default() -> Self {
-        Self::new()
+    pub fn get_info(&self) -> &GpuInfo {
+        &self.current_info
     }
 }
 
 impl std::fmt::Debug for GpuMonitor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("GpuMonitor")
-            .field("available", &self.available)
-            .field("core_count", &self.core_count)
-            .field("chip_name", &self.chip_name)
+            .field("available", &self.is_available())
+            .field("core_count", &self.current_info.core_count)
+            .field("chip_name", &self.current_info.chip_name)
             .finish_non_exhaustive()
     }
 }
 
-impl Drop for GpuMonitor {
-    fn drop(&mut self) {
-        // Signal the background thread to stop
-        self.state.should_stop.store(true, Ordering::Relaxed);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_powermetrics_sample_has_no_synthesised_cores() {
+        let state = Arc::new(PowermetricsState::new());
+        state.publish(PowermetricsSample {
+            gpu: Some(GpuActivity {
+                active_residency: Some(10.53),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let mut monitor = GpuMonitor::with_backend(Some(Box::new(PowermetricsBackend {
+            core_count: 16,
+            chip_name: "M1 Pro".to_string(),
+            state,
+            _background_thread: thread::spawn(|| {}),
+        })));
+        monitor.refresh();
+
+        let info = monitor.get_info();
+        assert!(info.cores.is_empty());
+        assert_eq!(info.core_count, 16);
+        assert_eq!(info.overall_utilization, 10.53);
+    }
+
+    /// Backend recording its latest `set_active`.
+    struct Toggle(Arc<AtomicBool>);
+
+    impl GpuBackend for Toggle {
+        fn chip_name(&self) -> &str {
+            "Toggle"
+        }
+
+        fn core_count(&self) -> usize {
+            8
+        }
+
+        fn set_active(&mut self, active: bool) {
+            self.0.store(active, Ordering::Relaxed);
+        }
+
+        fn sample(&mut self) -> GpuInfo {
+            GpuInfo::default()
+        }
+
+        fn process_gpu_source(&self) -> Box<dyn ProcessMetricSource> {
+            Box::new(crate::process_metrics::NoProcessMetric)
+        }
+    }
+
+    #[test]
+    fn test_shared_backend_samples_while_any_handle_is_active() {
+        let active = Arc::new(AtomicBool::new(true));
+        let shared = SharedGpuBackend::new(Box::new(Toggle(Arc::clone(&active))));
+        assert!(!active.load(Ordering::Relaxed), "idle with no clients");
+
+        let mut first = shared.handle();
+        let second = shared.handle();
+        assert!(active.load(Ordering::Relaxed));
+        assert_eq!((first.chip_name(), first.core_count()), ("Toggle", 8));
+
+        first.set_active(false);
+        assert!(active.load(Ordering::Relaxed), "second still shows the GPU");
+        drop(second);
+        assert!(!active.load(Ordering::Relaxed));
+        first.set_active(true);
+        assert!(active.load(Ordering::Relaxed));
     }
 }
@@ -0,0 +1,178 @@
+//! Apple Silicon GPU identification.
+//!
+//! The GPU core count is read from the `system_profiler` hardware report,
+//! which lists the real figure for binned parts. The catalogue below is only
+//! consulted when the report omits it (older macOS releases), and a config
+//! override beats both.
+
+/// GPU core variants shipped for each chip, smallest first.
+const CATALOGUE: &[(&str, &[usize])] = &[
+    ("M1", &[7, 8]),
+    ("M1 Pro", &[14, 16]),
+    ("M1 Max", &[24, 32]),
+    ("M1 Ultra", &[48, 64]),
+    ("M2", &[8, 10]),
+    ("M2 Pro", &[16, 19]),
+    ("M2 Max", &[30, 38]),
+    ("M2 Ultra", &[60, 76]),
+    ("M3", &[8, 10]),
+    ("M3 Pro", &[14, 18]),
+    ("M3 Max", &[30, 40]),
+    ("M3 Ultra", &[60, 80]),
+    ("M4", &[8, 10]),
+    ("M4 Pro", &[16, 20]),
+    ("M4 Max", &[32, 40]),
+    ("M5", &[10]),
+];
+
+/// Fields of interest from `system_profiler SPHardwareDataType SPDisplaysDataType`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HardwareReport {
+    /// Chip name without the "Apple " prefix, e.g. "M2 Pro".
+    pub chip: Option<String>,
+    /// GPU core count from the displays section.
+    pub gpu_cores: Option<usize>,
+}
+
+/// Parse the text report. The hardware section's "Total Number of Cores" is
+/// the CPU count, so only the one following "Chipset Model" is taken as GPU.
+pub fn parse_hardware_report(report: &str) -> HardwareReport {
+    let mut parsed = HardwareReport::default();
+    let mut in_gpu = false;
+
+    for line in report.lines() {
+        let Some((key, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "Chip" => parsed.chip = Some(chip_name(value)),
+            "Chipset Model" => {
+                in_gpu = true;
+                parsed.chip.get_or_insert_with(|| chip_name(value));
+            }
+            "Total Number of Cores" if in_gpu => {
+                parsed.gpu_cores = value.split_whitespace().next().and_then(|n| n.parse().ok());
+            }
+            _ => {}
+        }
+    }
+
+    parsed
+}
+
+fn chip_name(value: &str) -> String {
+    value.strip_prefix("Apple ").unwrap_or(value).to_string()
+}
+
+/// Core variants for a chip, if it is in the catalogue.
+pub fn core_variants(chip: &str) -> Option<&'static [usize]> {
+    CATALOGUE
+        .iter()
+        .find(|(name, _)| *name == chip)
+        .map(|(_, variants)| *variants)
+}
+
+/// Settle on `(chip name, GPU cores)`. Precedence: config override, the
+/// report's own count, then the smallest variant from the catalogue, which
+/// never overstates a binned part. Unknown chips without a reported count get
+/// 0 so no core count is displayed.
+pub fn resolve(report: &HardwareReport, core_override: Option<usize>) -> (String, usize) {
+    let chip = report.chip.clone().unwrap_or_else(|| "Unknown".to_string());
+    let cores = core_override
+        .or(report.gpu_cores)
+        .or_else(|| core_variants(&chip).and_then(|v| v.first().copied()))
+        .unwrap_or(0);
+    (chip, cores)
+}
+
+/// Run `system_profiler` and resolve the GPU identity for this machine.
+pub fn detect(core_override: Option<usize>) -> (String, usize) {
+    let report = hardware_report()
+        .map(|text| parse_hardware_report(&text))
+        .unwrap_or_default();
+    resolve(&report, core_override)
+}
+
+#[cfg(target_os = "macos")]
+fn hardware_report() -> Option<String> {
+    let output = std::process::Command::new("system_profiler")
+        .args(["SPHardwareDataType", "SPDisplaysDataType"])
+        .output()
+        .ok()?;
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn hardware_report() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const M4_MAX_BINNED: &str = "\
+Hardware:
+
+    Hardware Overview:
+
+      Model Name: MacBook Pro
+      Model Identifier: Mac16,6
+      Chip: Apple M4 Max
+      Total Number of Cores: 14 (10 performance and 4 efficiency)
+      Memory: 36 GB
+
+Graphics/Displays:
+
+    Apple M4 Max:
+
+      Chipset Model: Apple M4 Max
+      Type: GPU
+      Bus: Built-In
+      Total Number of Cores: 32
+      Vendor: Apple (0x106b)
+      Metal Support: Metal 3
+      Displays:
+        Color LCD:
+          Display Type: Built-in Liquid Retina XDR Display
+";
+
+    #[test]
+    fn test_report_gpu_cores_beat_catalogue() {
+        let report = parse_hardware_report(M4_MAX_BINNED);
+        assert_eq!(report.chip.as_deref(), Some("M4 Max"));
+        // 14 is the CPU count from the hardware section
+        assert_eq!(report.gpu_cores, Some(32));
+        assert_eq!(resolve(&report, None), ("M4 Max".to_string(), 32));
+    }
+
+    #[test]
+    fn test_catalogue_fallback_and_override() {
+        let hardware_only = parse_hardware_report(
+            "Hardware Overview:\n  Chip: Apple M3 Ultra\n  Total Number of Cores: 32 (24 performance and 8 efficiency)\n",
+        );
+        assert_eq!(hardware_only.gpu_cores, None);
+        assert_eq!(resolve(&hardware_only, None), ("M3 Ultra".to_string(), 60));
+        assert_eq!(
+            resolve(&hardware_only, Some(80)),
+            ("M3 Ultra".to_string(), 80)
+        );
+
+        let unknown = parse_hardware_report("Chip: Apple M9 Hyper\n");
+        assert_eq!(resolve(&unknown, None), ("M9 Hyper".to_string(), 0));
+        assert_eq!(
+            resolve(&HardwareReport::default(), None),
+            ("Unknown".to_string(), 0)
+        );
+    }
+
+    #[test]
+    fn test_catalogue_variants_sorted() {
+        for (chip, variants) in CATALOGUE {
+            assert!(!variants.is_empty(), "{} has no variants", chip);
+            assert!(variants.windows(2).all(|w| w[0] < w[1]), "{}", chip);
+        }
+        assert_eq!(core_variants("M2 Pro"), Some(&[16, 19][..]));
+    }
+}
@@ -0,0 +1,247 @@
+//! Linux GPU backend reading DRM sysfs and per-client fdinfo counters.
+//!
+//! Device utilisation comes from `device/gpu_busy_percent` where the driver
+//! provides it (amdgpu). Drivers without it (i915, xe, msm, ...) still expose
+//! per-client engine time in `/proc/<pid>/fdinfo`, so utilisation falls back
+//! to the sum across clients. The same fdinfo sample feeds the per-process
+//! GPU column.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::gpu::{GpuBackend, GpuInfo};
+use crate::process_metrics::{FdinfoGpuSource, ProcessMetricSource, SharedProcessMetric};
+
+/// Drivers known to publish `drm-engine-*` usage in fdinfo.
+const FDINFO_DRIVERS: &[&str] = &[
+    "amdgpu", "i915", "xe", "msm", "panfrost", "panthor", "v3d", "nouveau", "etnaviv",
+];
+
+#[derive(Debug)]
+pub struct SysfsGpuBackend {
+    device_dir: PathBuf,
+    chip_name: String,
+    clients: FdinfoGpuSource,
+    process_gpu: SharedProcessMetric,
+    active: bool,
+}
+
+impl SysfsGpuBackend {
+    pub fn detect() -> Option<Self> {
+        Self::detect_in(Path::new("/sys/class/drm"), Path::new("/proc"))
+    }
+
+    /// Find the first usable `cardN` under `drm_root`. Cards are usable when
+    /// they report busy percent or are driven by an fdinfo-capable driver;
+    /// display-only devices such as simpledrm are skipped.
+    pub fn detect_in(drm_root: &Path, proc_root: &Path) -> Option<Self> {
+        let mut cards: Vec<(u32, PathBuf)> = std::fs::read_dir(drm_root)
+            .ok()?
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name();
+                let index = name.to_str()?.strip_prefix("card")?.parse::<u32>().ok()?;
+                Some((index, entry.path()))
+            })
+            .collect();
+        cards.sort_by_key(|(index, _)| *index);
+
+        for (index, card_dir) in cards {
+            let device_dir = card_dir.join("device");
+            let driver = driver_name(&device_dir);
+            let has_busy = device_dir.join("gpu_busy_percent").exists();
+            let fdinfo_capable = driver
+                .as_deref()
+                .is_some_and(|d| FDINFO_DRIVERS.contains(&d));
+            if !has_busy && !fdinfo_capable {
+                continue;
+            }
+
+            let chip_name = read_trimmed(&device_dir.join("product_name"))
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| format!("{} card{}", driver.as_deref().unwrap_or("drm"), index));
+
+            let pdev = pdev_name(&device_dir);
+            return Some(SysfsGpuBackend {
+                device_dir,
+                chip_name,
+                clients: FdinfoGpuSource::with_root(proc_root).for_pdev(pdev),
+                process_gpu: SharedProcessMetric::new(),
+                active: true,
+            });
+        }
+
+        None
+    }
+
+    fn busy_percent(&self) -> Option<f32> {
+        read_trimmed(&self.device_dir.join("gpu_busy_percent"))?
+            .parse::<f32>()
+            .ok()
+            .map(|v| v.clamp(0.0, 100.0))
+    }
+
+    fn vram(&self) -> Option<(u64, u64)> {
+        let used = read_trimmed(&self.device_dir.join("mem_info_vram_used"))?
+            .parse()
+            .ok()?;
+        let total = read_trimmed(&self.device_dir.join("mem_info_vram_total"))?
+            .parse()
+            .ok()?;
+        Some((used, total))
+    }
+}
+
+impl GpuBackend for SysfsGpuBackend {
+    fn chip_name(&self) -> &str {
+        &self.chip_name
+    }
+
+    /// DRM does not expose shader core counts; utilisation is aggregate only.
+    fn core_count(&self) -> usize {
+        0
+    }
+
+    fn set_active(&mut self, active: bool) {
+        self.active = active;
+        self.process_gpu.set_active(active);
+    }
+
+    fn sample(&mut self) -> GpuInfo {
+        // The fdinfo walk touches every process's fd table, so skip it while
+        // the GPU panel is hidden.
+        let per_process: Option<HashMap<u32, f32>> = if self.active {
+            self.clients.sample()
+        } else {
+            None
+        };
+        let summed = per_process
+            .as_ref()
+            .map(|usage| usage.values().sum::<f32>().min(100.0));
+        self.process_gpu.publish(per_process);
+
+        GpuInfo {
+            cores: Vec::new(),
+            overall_utilization: self.busy_percent().or(summed).unwrap_or(0.0),
+            core_count: 0,
+            chip_name: self.chip_name.clone(),
+            vram: self.vram(),
+            activity: None,
+            soc: None,
+        }
+    }
+
+    fn process_gpu_source(&self) -> Box<dyn ProcessMetricSource> {
+        Box::new(self.process_gpu.clone())
+    }
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+/// Bus address of a DRM device, from the `device` symlink; matches the
+/// `drm-pdev` field of its clients' fdinfo.
+fn pdev_name(device_dir: &Path) -> Option<String> {
+    let target = std::fs::read_link(device_dir).ok()?;
+    target.file_name()?.to_str().map(|s| s.to_string())
+}
+
+/// Kernel driver bound to a DRM device, from the `device/driver` symlink.
+fn driver_name(device_dir: &Path) -> Option<String> {
+    let target = std::fs::read_link(device_dir.join("driver")).ok()?;
+    target.file_name()?.to_str().map(|s| s.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::fixture::FixtureDir;
+
+    #[test]
+    fn test_amdgpu_busy_percent_and_vram() {
+        let fx = FixtureDir::new("sysfs-amdgpu");
+        fx.write("drm/card0-DP-1/status", "connected\n");
+        fx.write("drm/card1/device/gpu_busy_percent", "37\n");
+        fx.write("drm/card1/device/mem_info_vram_used", "1073741824\n");
+        fx.write("drm/card1/device/mem_info_vram_total", "8589934592\n");
+        fx.write("drm/card1/device/product_name", "Radeon RX 7600\n");
+        fx.link("drm/card1/device/driver", "../../../bus/pci/drivers/amdgpu");
+        std::fs::create_dir_all(fx.path().join("proc")).unwrap();
+
+        let mut backend =
+            SysfsGpuBackend::detect_in(&fx.path().join("drm"), &fx.path().join("proc"))
+                .expect("card1");
+        assert_eq!(backend.chip_name(), "Radeon RX 7600");
+        assert_eq!(backend.core_count(), 0);
+
+        let info = backend.sample();
+        assert_eq!(info.overall_utilization, 37.0);
+        assert_eq!(info.vram, Some((1 << 30, 8 << 30)));
+        assert!(info.cores.is_empty());
+    }
+
+    #[test]
+    fn test_i915_falls_back_to_fdinfo_clients() {
+        let fx = FixtureDir::new("sysfs-i915");
+        fx.link("devices/0000:00:02.0/driver", "../../bus/pci/drivers/i915");
+        fx.link("drm/card0/device", "../../devices/0000:00:02.0");
+        fx.link("proc/4242/fd/7", "/dev/dri/renderD128");
+        fx.write(
+            "proc/4242/fdinfo/7",
+            "drm-driver:\ti915\ndrm-client-id:\t9\ndrm-pdev:\t0000:00:02.0\n\
+             drm-engine-render:\t1000 ns\n",
+        );
+        // A client of a second card is not this card's load.
+        fx.link("proc/4242/fd/8", "/dev/dri/renderD129");
+        fx.write(
+            "proc/4242/fdinfo/8",
+            "drm-driver:\tamdgpu\ndrm-client-id:\t3\ndrm-pdev:\t0000:03:00.0\n\
+             drm-engine-gfx:\t1000 ns\n",
+        );
+        fx.link("proc/4243/fd/0", "/dev/null");
+
+        let mut backend =
+            SysfsGpuBackend::detect_in(&fx.path().join("drm"), &fx.path().join("proc"))
+                .expect("card0");
+        assert_eq!(backend.chip_name(), "i915 card0");
+
+        let info = backend.sample();
+        assert_eq!(info.overall_utilization, 0.0);
+        assert_eq!(info.vram, None);
+
+        // 4243 holds no DRM client, so it measures zero; 4242's client needs a
+        // second sample before it has a value.
+        let per_process = backend.process_gpu_source().sample().expect("sample");
+        assert_eq!(per_process.get(&4242), None);
+        assert_eq!(per_process.get(&4243), Some(&0.0));
+
+        // Only the other card's client was busy in between.
+        fx.write(
+            "proc/4242/fdinfo/8",
+            "drm-driver:\tamdgpu\ndrm-client-id:\t3\ndrm-pdev:\t0000:03:00.0\n\
+             drm-engine-gfx:\t100000000000 ns\n",
+        );
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        assert_eq!(backend.sample().overall_utilization, 0.0);
+        let per_process = backend.process_gpu_source().sample().expect("sample");
+        assert_eq!(per_process.get(&4242), Some(&0.0));
+
+        backend.set_active(false);
+        assert!(backend.process_gpu_source().sample().is_none());
+    }
+
+    #[test]
+    fn test_display_only_cards_are_skipped() {
+        let fx = FixtureDir::new("sysfs-simpledrm");
+        fx.link(
+            "drm/card0/device/driver",
+            "../../../bus/platform/drivers/simple-framebuffer",
+        );
+        assert!(
+            SysfsGpuBackend::detect_in(&fx.path().join("drm"), &fx.path().join("proc")).is_none()
+        );
+    }
+}
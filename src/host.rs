@@ -0,0 +1,25 @@
+//! Host identity and load for the header.
+//!
+//! Sampled by the collector like every other metric, so a TUI connected to
+//! a remote agent shows the agent's load and uptime rather than its own.
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HostInfo {
+    pub name: String,
+    pub uptime_secs: u64,
+    /// One-minute load average.
+    pub load_one: f64,
+}
+
+/// The machine oversee runs on.
+pub struct SystemHost;
+
+impl SystemHost {
+    pub fn host_info(&self) -> HostInfo {
+        HostInfo {
+            name: sysinfo::System::host_name().unwrap_or_else(|| "localhost".to_string()),
+            uptime_secs: sysinfo::System::uptime(),
+            load_one: sysinfo::System::load_average().one,
+        }
+    }
+}
@@ -0,0 +1,786 @@
+//! Deep views of a single process, opened from its breakout.
+//!
+//! Each view is requested through the `broker::Broker` only while it is on
+//! screen, since walking every mapping or descriptor of a large process is
+//! far more expensive than the fd and thread counts the breakout normally
+//! shows. A remote agent answers the same requests for its own processes.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InspectView {
+    /// Mapped regions aggregated by backing file.
+    Maps,
+    /// Open file descriptors.
+    Files,
+    /// Threads with their CPU use.
+    Threads,
+}
+
+impl InspectView {
+    pub fn next(self) -> Self {
+        match self {
+            InspectView::Maps => InspectView::Files,
+            InspectView::Files => InspectView::Threads,
+            InspectView::Threads => InspectView::Maps,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            InspectView::Maps => "memory map",
+            InspectView::Files => "open files",
+            InspectView::Threads => "threads",
+        }
+    }
+}
+
+/// All mappings of one backing file (or one kind of anonymous memory).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MappedFile {
+    /// Path, a kernel label such as `[heap]`, or `[anon]`.
+    pub name: String,
+    pub regions: usize,
+    /// Virtual size of the mappings.
+    pub size: u64,
+    /// `None` when only `/proc/<pid>/maps` was readable.
+    pub rss: Option<u64>,
+    pub pss: Option<u64>,
+    pub swap: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MapSort {
+    #[default]
+    Rss,
+    Pss,
+    Size,
+    Name,
+}
+
+impl MappedFile {
+    /// Case-insensitive substring match on the name.
+    pub fn matches(&self, filter: &str) -> bool {
+        contains_ignore_case(&self.name, filter)
+    }
+}
+
+impl MapSort {
+    pub fn next(self) -> Self {
+        match self {
+            MapSort::Rss => MapSort::Pss,
+            MapSort::Pss => MapSort::Size,
+            MapSort::Size => MapSort::Name,
+            MapSort::Name => MapSort::Rss,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MapSort::Rss => "rss",
+            MapSort::Pss => "pss",
+            MapSort::Size => "size",
+            MapSort::Name => "name",
+        }
+    }
+
+    /// Largest first for sizes, alphabetical for names.
+    pub fn sort(self, files: &mut [MappedFile]) {
+        match self {
+            MapSort::Rss => files.sort_by_key(|m| std::cmp::Reverse(m.rss)),
+            MapSort::Pss => files.sort_by_key(|m| std::cmp::Reverse(m.pss)),
+            MapSort::Size => files.sort_by_key(|m| std::cmp::Reverse(m.size)),
+            MapSort::Name => files.sort_by(|a, b| a.name.cmp(&b.name)),
+        }
+    }
+}
+
+/// Parse `/proc/<pid>/smaps` (or plain `maps`, which lacks the per-region
+/// `Key: N kB` lines) and aggregate the regions by backing file.
+pub fn parse_smaps(text: &str) -> Vec<MappedFile> {
+    let mut files: Vec<MappedFile> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    let mut current: Option<usize> = None;
+
+    for line in text.lines() {
+        if let Some((key, rest)) = line.split_once(':')
+            && !key.contains(' ')
+            && !key.contains('-')
+        {
+            let Some(file) = current.map(|i| &mut files[i]) else {
+                continue;
+            };
+            let Some(bytes) = rest
+                .trim()
+                .strip_suffix("kB")
+                .and_then(|kb| kb.trim().parse::<u64>().ok())
+                .map(|kb| kb * 1024)
+            else {
+                continue;
+            };
+            let field = match key {
+                "Rss" => &mut file.rss,
+                "Pss" => &mut file.pss,
+                "Swap" => &mut file.swap,
+                _ => continue,
+            };
+            *field.get_or_insert(0) += bytes;
+            continue;
+        }
+
+        // Region header: "start-end perms offset dev inode [pathname]"
+        let Some((start, end)) = line
+            .split_whitespace()
+            .next()
+            .and_then(|range| range.split_once('-'))
+        else {
+            current = None;
+            continue;
+        };
+        let (Ok(start), Ok(end)) = (u64::from_str_radix(start, 16), u64::from_str_radix(end, 16))
+        else {
+            current = None;
+            continue;
+        };
+        // Skip the five fixed fields; paths may contain spaces
+        let mut path = line;
+        for _ in 0..5 {
+            let trimmed = path.trim_start();
+            path = trimmed
+                .find(char::is_whitespace)
+                .map_or("", |i| &trimmed[i..]);
+        }
+        let path = path.trim().to_string();
+        let name = if path.is_empty() {
+            "[anon]".to_string()
+        } else {
+            path
+        };
+
+        let i = *index.entry(name.clone()).or_insert_with(|| {
+            files.push(MappedFile {
+                name,
+                regions: 0,
+                size: 0,
+                rss: None,
+                pss: None,
+                swap: None,
+            });
+            files.len() - 1
+        });
+        files[i].regions += 1;
+        files[i].size += end.saturating_sub(start);
+        current = Some(i);
+    }
+    files
+}
+
+/// Mapped files of `pid`, from smaps where readable and maps otherwise.
+/// `None` where the platform has neither.
+pub fn fetch_maps(pid: u32) -> Option<Vec<MappedFile>> {
+    proc_maps_text(pid).map(|text| parse_smaps(&text))
+}
+
+#[cfg(target_os = "linux")]
+fn proc_maps_text(pid: u32) -> Option<String> {
+    std::fs::read_to_string(format!("/proc/{}/smaps", pid))
+        .or_else(|_| std::fs::read_to_string(format!("/proc/{}/maps", pid)))
+        .ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn proc_maps_text(_pid: u32) -> Option<String> {
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FdKind {
+    File,
+    Directory,
+    Socket,
+    Pipe,
+    EventFd,
+    /// Other anonymous inodes: epoll, timerfd, signalfd, inotify...
+    AnonInode,
+    Device,
+    Other,
+}
+
+impl FdKind {
+    /// Classify a Linux `/proc/<pid>/fd/N` link target, e.g.
+    /// `socket:[81234]`, `pipe:[5521]`, `anon_inode:[eventfd]`.
+    pub fn from_link(target: &str) -> Self {
+        if target.starts_with("socket:") {
+            FdKind::Socket
+        } else if target.starts_with("pipe:") {
+            FdKind::Pipe
+        } else if target == "anon_inode:[eventfd]" {
+            FdKind::EventFd
+        } else if target.starts_with("anon_inode:") {
+            FdKind::AnonInode
+        } else if target.starts_with("/dev/") {
+            FdKind::Device
+        } else if target.starts_with('/') {
+            FdKind::File
+        } else {
+            FdKind::Other
+        }
+    }
+
+    /// Classify an lsof `TYPE` field (REG, DIR, IPv4, unix, PIPE, CHR...).
+    pub fn from_lsof_type(kind: &str) -> Self {
+        match kind {
+            "REG" => FdKind::File,
+            "DIR" => FdKind::Directory,
+            "IPv4" | "IPv6" | "unix" | "systm" | "sock" => FdKind::Socket,
+            "PIPE" | "FIFO" => FdKind::Pipe,
+            "CHR" | "BLK" => FdKind::Device,
+            "KQUEUE" | "PSXSHM" | "PSXSEM" => FdKind::AnonInode,
+            _ => FdKind::Other,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FdKind::File => "file",
+            FdKind::Directory => "dir",
+            FdKind::Socket => "socket",
+            FdKind::Pipe => "pipe",
+            FdKind::EventFd => "eventfd",
+            FdKind::AnonInode => "anon",
+            FdKind::Device => "device",
+            FdKind::Other => "other",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FdMode {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl FdMode {
+    /// From the octal `flags:` of `/proc/<pid>/fdinfo/N` (`O_ACCMODE` bits).
+    pub fn from_flags(flags: u32) -> Option<Self> {
+        match flags & 0o3 {
+            0 => Some(FdMode::Read),
+            1 => Some(FdMode::Write),
+            2 => Some(FdMode::ReadWrite),
+            _ => None,
+        }
+    }
+
+    /// From an lsof access field: `r`, `w` or `u`.
+    pub fn from_lsof(access: &str) -> Option<Self> {
+        match access {
+            "r" => Some(FdMode::Read),
+            "w" => Some(FdMode::Write),
+            "u" => Some(FdMode::ReadWrite),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FdMode::Read => "r",
+            FdMode::Write => "w",
+            FdMode::ReadWrite => "rw",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenFile {
+    pub fd: u32,
+    pub kind: FdKind,
+    /// Path, or the kernel's description for sockets, pipes and the like.
+    pub path: String,
+    pub mode: Option<FdMode>,
+    /// File position, where the OS reports one.
+    pub offset: Option<u64>,
+}
+
+impl OpenFile {
+    /// Case-insensitive substring match on the path or the kind.
+    pub fn matches(&self, filter: &str) -> bool {
+        contains_ignore_case(&self.path, filter) || contains_ignore_case(self.kind.label(), filter)
+    }
+}
+
+/// Parse `/proc/<pid>/fdinfo/N`: returns `(pos, flags)`.
+pub fn parse_fdinfo(text: &str) -> (Option<u64>, Option<u32>) {
+    let mut pos = None;
+    let mut flags = None;
+    for line in text.lines() {
+        if let Some(value) = line.strip_prefix("pos:") {
+            pos = value.trim().parse().ok();
+        } else if let Some(value) = line.strip_prefix("flags:") {
+            flags = u32::from_str_radix(value.trim(), 8).ok();
+        }
+    }
+    (pos, flags)
+}
+
+/// Parse `lsof -F fatno` field output: one field per line, each prefixed
+/// with its letter, a new `f` starting the next descriptor. Entries
+/// without a numeric fd (cwd, txt, mem) are not descriptors and are skipped.
+pub fn parse_lsof_fields(text: &str) -> Vec<OpenFile> {
+    let mut files = Vec::new();
+    let mut current: Option<OpenFile> = None;
+    for line in text.lines() {
+        let Some(tag) = line.chars().next() else {
+            continue;
+        };
+        let value = &line[tag.len_utf8()..];
+        match tag {
+            'f' => {
+                files.extend(current.take());
+                current = value.parse().ok().map(|fd| OpenFile {
+                    fd,
+                    kind: FdKind::Other,
+                    path: String::new(),
+                    mode: None,
+                    offset: None,
+                });
+            }
+            'a' => {
+                if let Some(file) = current.as_mut() {
+                    file.mode = FdMode::from_lsof(value);
+                }
+            }
+            't' => {
+                if let Some(file) = current.as_mut() {
+                    file.kind = FdKind::from_lsof_type(value);
+                }
+            }
+            'o' => {
+                if let Some(file) = current.as_mut() {
+                    // "0t123" is decimal, "0x7b" hex
+                    file.offset = match value.get(..2) {
+                        Some("0t") => value[2..].parse().ok(),
+                        Some("0x") => u64::from_str_radix(&value[2..], 16).ok(),
+                        _ => None,
+                    };
+                }
+            }
+            'n' => {
+                if let Some(file) = current.as_mut() {
+                    file.path = value.to_string();
+                }
+            }
+            _ => {}
+        }
+    }
+    files.extend(current);
+    files
+}
+
+/// Open descriptors of `pid`, ordered by fd. `None` when they can't be read.
+pub fn fetch_open_files(pid: u32) -> Option<Vec<OpenFile>> {
+    let mut files =
+        proc_open_files(pid).or_else(|| lsof_fields(pid).map(|t| parse_lsof_fields(&t)))?;
+    files.sort_by_key(|f| f.fd);
+    Some(files)
+}
+
+/// Walk `/proc/<pid>/fd`; there is no such directory outside Linux.
+fn proc_open_files(pid: u32) -> Option<Vec<OpenFile>> {
+    let dir = std::path::PathBuf::from(format!("/proc/{}", pid));
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir.join("fd")).ok()?.flatten() {
+        let Some(fd) = entry
+            .file_name()
+            .to_str()
+            .and_then(|n| n.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        // The fd may close between listing and reading; skip it
+        let Ok(target) = std::fs::read_link(entry.path()) else {
+            continue;
+        };
+        let path = target.to_string_lossy().into_owned();
+        let mut kind = FdKind::from_link(&path);
+        if kind == FdKind::File && std::fs::metadata(entry.path()).is_ok_and(|m| m.is_dir()) {
+            kind = FdKind::Directory;
+        }
+        let (offset, flags) = std::fs::read_to_string(dir.join("fdinfo").join(fd.to_string()))
+            .map(|text| parse_fdinfo(&text))
+            .unwrap_or_default();
+        files.push(OpenFile {
+            fd,
+            kind,
+            path,
+            mode: flags.and_then(FdMode::from_flags),
+            offset,
+        });
+    }
+    Some(files)
+}
+
+#[cfg(target_os = "macos")]
+fn lsof_fields(pid: u32) -> Option<String> {
+    let output = std::process::Command::new("lsof")
+        .args(["-p", &pid.to_string(), "-nP", "-F", "fatno"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn lsof_fields(_pid: u32) -> Option<String> {
+    None
+}
+
+/// One line of `/proc/<pid>/task/<tid>/stat`, times in clock ticks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskStat {
+    pub tid: u32,
+    pub name: String,
+    pub state: char,
+    pub utime: u64,
+    pub stime: u64,
+    /// When the task started, in ticks since boot.
+    pub start_ticks: u64,
+}
+
+/// Parse a task stat line. The name sits in parentheses and may itself
+/// contain spaces or parentheses, so the fields are split after the last `)`.
+pub fn parse_task_stat(text: &str) -> Option<TaskStat> {
+    let open = text.find('(')?;
+    let close = text.rfind(')')?;
+    let tid = text[..open].trim().parse().ok()?;
+    let name = text.get(open + 1..close)?.to_string();
+    // Fields after the name start at 3 (state); utime and stime are 14, 15
+    // and starttime 22
+    let fields: Vec<&str> = text[close + 1..].split_whitespace().collect();
+    Some(TaskStat {
+        tid,
+        name,
+        state: fields.first()?.chars().next()?,
+        utime: fields.get(11)?.parse().ok()?,
+        stime: fields.get(12)?.parse().ok()?,
+        start_ticks: fields.get(19)?.parse().ok()?,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreadInfo {
+    pub tid: u32,
+    pub name: String,
+    /// Kernel state letter: R running, S sleeping, D uninterruptible, ...
+    pub state: char,
+    /// Share of one core since the previous sample; `None` on the first.
+    pub cpu_percent: Option<f32>,
+    pub user_time: Duration,
+    pub system_time: Duration,
+}
+
+impl ThreadInfo {
+    /// Case-insensitive substring match on the name, or the tid.
+    pub fn matches(&self, filter: &str) -> bool {
+        contains_ignore_case(&self.name, filter) || self.tid.to_string().contains(filter)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ThreadSort {
+    #[default]
+    Cpu,
+    Time,
+    Tid,
+    Name,
+}
+
+impl ThreadSort {
+    pub fn next(self) -> Self {
+        match self {
+            ThreadSort::Cpu => ThreadSort::Time,
+            ThreadSort::Time => ThreadSort::Tid,
+            ThreadSort::Tid => ThreadSort::Name,
+            ThreadSort::Name => ThreadSort::Cpu,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ThreadSort::Cpu => "cpu",
+            ThreadSort::Time => "time",
+            ThreadSort::Tid => "tid",
+            ThreadSort::Name => "name",
+        }
+    }
+
+    /// Busiest first for CPU and time, ascending for tid and name.
+    pub fn sort(self, threads: &mut [ThreadInfo]) {
+        match self {
+            ThreadSort::Cpu => threads.sort_by(|a, b| {
+                b.cpu_percent
+                    .unwrap_or(0.0)
+                    .total_cmp(&a.cpu_percent.unwrap_or(0.0))
+                    .then(a.tid.cmp(&b.tid))
+            }),
+            ThreadSort::Time => {
+                threads.sort_by_key(|t| (std::cmp::Reverse(t.user_time + t.system_time), t.tid))
+            }
+            ThreadSort::Tid => threads.sort_by_key(|t| t.tid),
+            ThreadSort::Name => threads.sort_by(|a, b| a.name.cmp(&b.name).then(a.tid.cmp(&b.tid))),
+        }
+    }
+}
+
+/// Turns successive task stat samples of one process into per-thread CPU%.
+/// Lives in `broker::local_dispatch`, which samples every couple of seconds
+/// while the thread view is open.
+#[derive(Debug, Default)]
+pub struct ThreadSampler {
+    pid: u32,
+    /// Busy ticks per tid at `at`.
+    previous: HashMap<u32, u64>,
+    at: Option<Instant>,
+}
+
+impl ThreadSampler {
+    pub fn sample(
+        &mut self,
+        pid: u32,
+        stats: Vec<TaskStat>,
+        now: Instant,
+        ticks_per_second: u64,
+    ) -> Vec<ThreadInfo> {
+        if self.pid != pid {
+            self.pid = pid;
+            self.previous.clear();
+            self.at = None;
+        }
+        let elapsed = self.at.map(|at| now.duration_since(at).as_secs_f32());
+        let tick = |ticks: u64| Duration::from_secs_f64(ticks as f64 / ticks_per_second as f64);
+        let mut busy_now = HashMap::with_capacity(stats.len());
+        let threads = stats
+            .into_iter()
+            .map(|stat| {
+                let busy = stat.utime + stat.stime;
+                busy_now.insert(stat.tid, busy);
+                // Threads born since the last sample have no baseline yet
+                let cpu_percent = match (elapsed, self.previous.get(&stat.tid)) {
+                    (Some(secs), Some(&before)) if secs > 0.0 => Some(
+                        busy.saturating_sub(before) as f32 / ticks_per_second as f32 / secs * 100.0,
+                    ),
+                    _ => None,
+                };
+                ThreadInfo {
+                    tid: stat.tid,
+                    name: stat.name,
+                    state: stat.state,
+                    cpu_percent,
+                    user_time: tick(stat.utime),
+                    system_time: tick(stat.stime),
+                }
+            })
+            .collect();
+        self.previous = busy_now;
+        self.at = Some(now);
+        threads
+    }
+}
+
+/// Threads of `pid` with CPU% against the sampler's previous call. `None`
+/// where `/proc/<pid>/task` is unreadable, which includes every non-Linux
+/// platform.
+pub fn fetch_threads(pid: u32, sampler: &mut ThreadSampler) -> Option<Vec<ThreadInfo>> {
+    let task = std::path::PathBuf::from(format!("/proc/{}/task", pid));
+    let stats = std::fs::read_dir(&task)
+        .ok()?
+        .flatten()
+        // A thread may exit between listing and reading; skip it
+        .filter_map(|entry| std::fs::read_to_string(entry.path().join("stat")).ok())
+        .filter_map(|text| parse_task_stat(&text))
+        .collect();
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    let ticks = u64::try_from(ticks).ok().filter(|&t| t > 0).unwrap_or(100);
+    Some(sampler.sample(pid, stats, Instant::now(), ticks))
+}
+
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    needle.is_empty() || haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMAPS: &str = "\
+55d4c2a00000-55d4c2a21000 r--p 00000000 fd:01 1835 /usr/bin/postgres
+Size:                132 kB
+Rss:                 128 kB
+Pss:                  64 kB
+Swap:                  0 kB
+VmFlags: rd mr mw me dw sd
+55d4c2a21000-55d4c2b00000 r-xp 00021000 fd:01 1835 /usr/bin/postgres
+Size:                892 kB
+Rss:                 600 kB
+Pss:                 300 kB
+Swap:                  0 kB
+55d4c3000000-55d4c3400000 rw-p 00000000 00:00 0    [heap]
+Rss:                4096 kB
+Pss:                4096 kB
+Swap:                512 kB
+7f1a00000000-7f1a00100000 rw-p 00000000 00:00 0
+Rss:                1024 kB
+Pss:                1024 kB
+7f1a00100000-7f1a00200000 rw-s 00000000 00:01 42   /memfd:shared buffers (deleted)
+Rss:                 100 kB
+Pss:                  50 kB
+";
+
+    #[test]
+    fn test_parse_smaps_aggregates_by_file() {
+        let files = parse_smaps(SMAPS);
+        assert_eq!(files.len(), 4);
+
+        let binary = &files[0];
+        assert_eq!(binary.name, "/usr/bin/postgres");
+        assert_eq!(binary.regions, 2);
+        assert_eq!(binary.size, 0x100000);
+        assert_eq!(binary.rss, Some(728 * 1024));
+        assert_eq!(binary.pss, Some(364 * 1024));
+
+        assert_eq!(files[1].name, "[heap]");
+        assert_eq!(files[1].swap, Some(512 * 1024));
+        assert_eq!(files[2].name, "[anon]");
+        assert_eq!(files[2].swap, None);
+        assert_eq!(files[3].name, "/memfd:shared buffers (deleted)");
+    }
+
+    #[test]
+    fn test_plain_maps_and_sorting() {
+        let maps: String = SMAPS
+            .lines()
+            .filter(|l| !l.ends_with("kB") && !l.starts_with("VmFlags"))
+            .map(|l| format!("{}\n", l))
+            .collect();
+        let mut files = parse_smaps(&maps);
+        assert_eq!(files.len(), 4);
+        assert!(files.iter().all(|f| f.rss.is_none()));
+
+        MapSort::Size.sort(&mut files);
+        assert_eq!(files[0].name, "[heap]");
+        MapSort::Name.sort(&mut files);
+        assert_eq!(files[0].name, "/memfd:shared buffers (deleted)");
+
+        let mut files = parse_smaps(SMAPS);
+        MapSort::Rss.sort(&mut files);
+        let order: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            order,
+            [
+                "[heap]",
+                "[anon]",
+                "/usr/bin/postgres",
+                "/memfd:shared buffers (deleted)"
+            ]
+        );
+        assert_eq!(MapSort::Name.next(), MapSort::Rss);
+    }
+
+    #[test]
+    fn test_fd_classification_and_fdinfo() {
+        for (target, kind) in [
+            ("socket:[81234]", FdKind::Socket),
+            ("pipe:[5521]", FdKind::Pipe),
+            ("anon_inode:[eventfd]", FdKind::EventFd),
+            ("anon_inode:[eventpoll]", FdKind::AnonInode),
+            ("/dev/null", FdKind::Device),
+            ("/var/lib/postgresql/base/16384/2619", FdKind::File),
+            ("net:[4026531840]", FdKind::Other),
+        ] {
+            assert_eq!(FdKind::from_link(target), kind, "{}", target);
+        }
+
+        let (pos, flags) = parse_fdinfo("pos:\t8192\nflags:\t02100002\nmnt_id:\t29\nino:\t1234\n");
+        assert_eq!(pos, Some(8192));
+        assert_eq!(flags.and_then(FdMode::from_flags), Some(FdMode::ReadWrite));
+        assert_eq!(FdMode::from_flags(0o2000000), Some(FdMode::Read));
+        assert_eq!(FdMode::from_flags(0o1), Some(FdMode::Write));
+    }
+
+    #[test]
+    fn test_parse_lsof_fields() {
+        let text = "p4242\nfcwd\natr\ntDIR\nn/Users/dev\n\
+                    f0\nau\ntCHR\no0t0\nn/dev/ttys001\n\
+                    f5\nar\ntREG\no0t4096\nn/Users/dev/data.db\n\
+                    f7\nau\ntIPv4\nnlocalhost:5432 (LISTEN)\n\
+                    f9\nau\ntunix\no0x10\nn->0x1234\n";
+        let files = parse_lsof_fields(text);
+        let fds: Vec<u32> = files.iter().map(|f| f.fd).collect();
+        assert_eq!(fds, [0, 5, 7, 9], "cwd is not a descriptor");
+        assert_eq!(files[0].kind, FdKind::Device);
+        assert_eq!(files[1].mode, Some(FdMode::Read));
+        assert_eq!(files[1].offset, Some(4096));
+        assert_eq!(files[1].path, "/Users/dev/data.db");
+        assert_eq!(files[2].kind, FdKind::Socket);
+        assert_eq!(files[2].offset, None);
+        assert_eq!(files[3].offset, Some(16));
+
+        assert!(files[2].matches("SOCKET"));
+        assert!(files[1].matches("data"));
+        assert!(!files[1].matches("pipe"));
+        assert!(files[1].matches(""));
+    }
+
+    #[test]
+    fn test_thread_stats_and_cpu_sampling() {
+        let stat = parse_task_stat(
+            "4243 (tokio-runtime-w) R 1 4242 4242 0 -1 4194368 120 0 0 0 350 40 0 0 20 0 9 0 81234\n",
+        )
+        .unwrap();
+        assert_eq!(stat.tid, 4243);
+        assert_eq!(stat.name, "tokio-runtime-w");
+        assert_eq!(stat.state, 'R');
+        assert_eq!((stat.utime, stat.stime), (350, 40));
+        assert_eq!(stat.start_ticks, 81234);
+        let odd =
+            parse_task_stat("7 (a) b (c)) S 1 2 3 4 5 6 7 8 9 10 11 12 0 0 0 0 0 0 99").unwrap();
+        assert_eq!(odd.name, "a) b (c)");
+        assert_eq!((odd.utime, odd.stime), (11, 12));
+        assert!(parse_task_stat("garbage").is_none());
+
+        let task = |tid: u32, name: &str, utime: u64| TaskStat {
+            tid,
+            name: name.to_string(),
+            state: 'S',
+            utime,
+            stime: 0,
+            start_ticks: 0,
+        };
+        let mut sampler = ThreadSampler::default();
+        let start = Instant::now();
+        let first = sampler.sample(1, vec![task(1, "main", 100), task(2, "gc", 50)], start, 100);
+        assert!(first.iter().all(|t| t.cpu_percent.is_none()));
+        assert_eq!(first[0].user_time, Duration::from_secs(1));
+
+        let later = start + Duration::from_secs(2);
+        let mut second = sampler.sample(
+            1,
+            vec![task(1, "main", 110), task(2, "gc", 230), task(3, "new", 5)],
+            later,
+            100,
+        );
+        ThreadSort::Cpu.sort(&mut second);
+        let cpu: Vec<(u32, Option<f32>)> = second.iter().map(|t| (t.tid, t.cpu_percent)).collect();
+        assert_eq!(cpu, [(2, Some(90.0)), (1, Some(5.0)), (3, None)]);
+        ThreadSort::Name.sort(&mut second);
+        assert_eq!(second[0].name, "gc");
+        assert!(second[2].matches("NE"));
+        assert!(second[1].matches("1"));
+
+        let other = sampler.sample(9, vec![task(1, "main", 500)], later, 100);
+        assert_eq!(other[0].cpu_percent, None, "a new pid starts over");
+    }
+}
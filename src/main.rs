@@ -1,35 +1,74 @@
 mod app;
+mod broker;
+mod cgroup;
+mod config;
+mod context;
 mod cpu;
+#[cfg(any(target_os = "linux", test))]
+mod drm;
+mod events;
+mod fleet;
 mod gpu;
+mod gpu_catalog;
+#[cfg(target_os = "linux")]
+mod gpu_sysfs;
+mod host;
+mod inspect;
 mod memory;
+mod plist;
+mod power;
+mod powermetrics;
 mod process;
+mod process_metrics;
+mod psi;
+mod remote;
+mod services;
+mod source;
 mod theme;
+mod thermal;
 mod tui;
 mod ui;
+mod watch;
 
 use app::App;
+use config::Config;
+use source::Sources;
 
 /// Messages sent from the background data collector to the main thread
 /// Uses incremental updates to avoid cloning large history buffers every second
 pub enum DataUpdate {
     /// Incremental CPU update - just the new values for this tick
     Cpu {
-        core_values: Vec<f32>, // Current value for each core
-        average_value: f32,    // Current average across all cores
+        core_values: Vec<f32>,                // Current value for each core
+        average_value: f32,                   // Current average across all cores
+        frequencies_mhz: Vec<u64>,            // Current clock for each core
+        breakdown: Option<cpu::CpuBreakdown>, // Time in user/system/iowait/...
     },
+    /// Brand, core counts and clusters; sent once with the first sample
+    CpuTopology { topology: cpu::CpuTopology },
     /// Incremental GPU update - just the new values for this tick
     Gpu {
-        core_values: Vec<f32>, // Current value for each core
-        overall_value: f32,    // Current overall utilisation
+        core_values: Vec<f32>,               // Current value for each core
+        overall_value: f32,                  // Current overall utilisation
+        vram: Option<(u64, u64)>,            // Dedicated video memory (used, total) bytes
+        activity: Option<gpu::GpuActivity>,  // Frequency/power/residency breakdown
+        soc: Option<powermetrics::SocPower>, // CPU clusters and SoC power
     },
     /// Incremental memory update - just the new value for this tick
     Memory {
-        usage_value: f32, // Current memory usage percentage
-        info: memory::MemoryInfo,
+        usage_value: f32,                 // Current memory usage percentage
+        pressure: memory::MemoryPressure, // Pressure level this tick
+        info: Box<memory::MemoryInfo>,
     },
     Processes {
         processes: Vec<process::ProcessInfo>,
     },
+    /// Power draw and battery, with powermetrics SoC watts merged in
+    Power { info: power::PowerInfo },
+    /// Temperature sensors and throttling state
+    Thermal { info: thermal::ThermalInfo },
+    /// Host name, uptime and load average
+    Host { info: host::HostInfo },
 }
 
 /// Commands sent from the main thread to control the data collector
@@ -38,6 +77,7 @@ pub enum DataCommand {
     Resume,
     Stop,
     ChangeSortMode,
+    SetMemoryColumn(process::MemoryColumn),
     SetGpuActive(bool),
 }
 use std::error::Error;
@@ -45,6 +85,7 @@ use std::error::Error;
 use std::fs::OpenOptions;
 #[cfg(feature = "profile")]
 use std::io::Write;
+use std::sync::atomic::Ordering;
 use std::sync::mpsc;
 use std::thread;
 use std::time::{Duration, Instant};
@@ -77,27 +118,143 @@ macro_rules! profile {
     ($label:expr, $expr:expr) => {{ $expr }};
 }
 
+/// What the command line asks for.
+#[derive(Debug, PartialEq)]
+enum Mode {
+    /// Collect and display this machine.
+    Local,
+    /// `oversee agent [--listen addr]`: collect only, serve TUI clients.
+    Agent { listen: String },
+    /// `oversee --connect host[:port]`: display an agent's machine.
+    Connect { addr: String },
+    /// `oversee fleet [host[:port]...]`: a table of several agents; with no
+    /// hosts, those under `[fleet]` in the config.
+    Fleet { hosts: Vec<String> },
+}
+
+fn parse_args(args: &[String]) -> Result<Mode, String> {
+    let mut args = args.iter().map(String::as_str);
+    let value = |flag: &str, value: Option<&str>| {
+        value
+            .map(remote::with_default_port)
+            .ok_or_else(|| format!("{} needs an address", flag))
+    };
+    match args.next() {
+        None => Ok(Mode::Local),
+        Some("agent") => {
+            let mut listen = format!("127.0.0.1:{}", remote::DEFAULT_PORT);
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--listen" => listen = value(arg, args.next())?,
+                    _ => return Err(format!("unknown agent argument: {}", arg)),
+                }
+            }
+            Ok(Mode::Agent { listen })
+        }
+        Some("--connect") => {
+            let addr = value("--connect", args.next())?;
+            match args.next() {
+                None => Ok(Mode::Connect { addr }),
+                Some(arg) => Err(format!("unexpected argument: {}", arg)),
+            }
+        }
+        Some("fleet") => Ok(Mode::Fleet {
+            hosts: args.map(str::to_string).collect(),
+        }),
+        Some(arg) => Err(format!("unknown argument: {}", arg)),
+    }
+}
+
+const USAGE: &str = "usage: oversee [--connect host[:port]]
+       oversee agent [--listen addr[:port]]
+       oversee fleet [host[:port]...]";
+
 fn main() -> Result<(), Box<dyn Error>> {
     if std::env::args().any(|a| a == "--version" || a == "-V") {
         println!("oversee {}", env!("CARGO_PKG_VERSION"));
         return Ok(());
     }
 
-    // Create channels for communication with background thread
-    let (update_tx, update_rx) = mpsc::channel::<DataUpdate>();
-    let (command_tx, command_rx) = mpsc::channel::<DataCommand>();
-
-    // Spawn background data collection thread
-    let collector_handle = thread::spawn(move || {
-        run_data_collector(update_tx, command_rx);
-    });
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mode = match parse_args(&args) {
+        Ok(mode) => mode,
+        Err(err) => {
+            eprintln!("oversee: {}\n{}", err, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let config = Config::load();
+
+    // Set when the agent behind `--connect` hangs up
+    let (update_rx, command_tx, worker, mut app, agent_closed) = match mode {
+        Mode::Agent { listen } => {
+            let listener = std::net::TcpListener::bind(&listen)?;
+            eprintln!("oversee agent listening on {}", listener.local_addr()?);
+            // One GPU sampler for all clients, not one powermetrics each
+            let gpu = gpu::GpuMonitor::detect_backend(&config).map(gpu::SharedGpuBackend::new);
+            remote::serve(listener, move || {
+                let backend = gpu
+                    .as_ref()
+                    .map(|gpu| Box::new(gpu.handle()) as Box<dyn gpu::GpuBackend>);
+                Sources::system_with_gpu(gpu::GpuMonitor::with_backend(backend))
+            })?;
+            return Ok(());
+        }
+        Mode::Fleet { hosts } => {
+            let hosts = if hosts.is_empty() {
+                config.fleet.clone()
+            } else {
+                hosts
+            };
+            if hosts.is_empty() {
+                eprintln!(
+                    "oversee: fleet needs hosts, or [fleet] hosts in the config\n{}",
+                    USAGE
+                );
+                std::process::exit(2);
+            }
+            return fleet::run(&hosts, config);
+        }
+        Mode::Connect { addr } => {
+            let mut connection = match remote::connect(&addr) {
+                Ok(connection) => connection,
+                Err(err) => {
+                    eprintln!("oversee: cannot connect to {}: {}", addr, err);
+                    std::process::exit(1);
+                }
+            };
+            let app = connection.app(&addr, config);
+            let closed = connection.closed_flag();
+            (
+                connection.updates,
+                connection.commands,
+                connection.worker,
+                app,
+                Some(closed),
+            )
+        }
+        Mode::Local => {
+            // Create channels for communication with background thread
+            let (update_tx, update_rx) = mpsc::channel::<DataUpdate>();
+            let (command_tx, command_rx) = mpsc::channel::<DataCommand>();
+
+            // Spawn background data collection thread
+            let sources = Sources::system(&config);
+            let gpu = sources.gpu.describe();
+            let collector_handle = thread::spawn(move || {
+                run_data_collector(sources, update_tx, command_rx);
+            });
+
+            // Create app with command channel
+            let app = App::new(command_tx.clone(), config, gpu);
+            (update_rx, command_tx, collector_handle, app, None)
+        }
+    };
 
     // Initialize terminal
     let mut terminal = tui::TuiGuard::new()?;
 
-    // Create app with command channel
-    let mut app = App::new(command_tx.clone());
-
     // Wait briefly for initial data to arrive
     thread::sleep(Duration::from_millis(100));
     app.process_updates(&update_rx);
@@ -111,7 +268,17 @@ fn main() -> Result<(), Box<dyn Error>> {
         let event_occurred = profile!("event_poll", app.handle_event()?);
 
         // Process any data updates from background thread
-        let data_updated = profile!("process_updates", app.process_updates(&update_rx));
+        let mut data_updated = profile!("process_updates", app.process_updates(&update_rx));
+
+        // Keep the agent's last readings on screen, marked as stale
+        if !app.disconnected
+            && agent_closed
+                .as_ref()
+                .is_some_and(|closed| closed.load(Ordering::Relaxed))
+        {
+            app.disconnected = true;
+            data_updated = true;
+        }
 
         // Only render if something changed
         if data_updated || event_occurred {
@@ -119,127 +286,357 @@ fn main() -> Result<(), Box<dyn Error>> {
         }
     }
 
-    // Signal background thread to stop
+    // Signal background thread (or the agent's collector) to stop
     let _ = command_tx.send(DataCommand::Stop);
-    let _ = collector_handle.join();
+    let _ = worker.join();
 
     Ok(())
 }
 
-fn run_data_collector(tx: mpsc::Sender<DataUpdate>, rx: mpsc::Receiver<DataCommand>) {
-    use crate::cpu::CpuMonitor;
-    use crate::gpu::GpuMonitor;
-    use crate::memory::MemoryMonitor;
-    use crate::process::ProcessMonitor;
-
-    let mut cpu_monitor = CpuMonitor::new();
-    let mut gpu_monitor = GpuMonitor::new();
-    let mut memory_monitor = MemoryMonitor::new();
-    let mut process_monitor = ProcessMonitor::new();
-
-    let mut paused = false;
-    let mut last_update = Instant::now() - Duration::from_secs(10); // Force immediate update
-    let mut last_port_update = Instant::now() - Duration::from_secs(10);
-    let mut last_full_process_refresh = Instant::now() - Duration::from_secs(10); // Force immediate full refresh
-
+fn run_data_collector(
+    sources: Sources,
+    tx: mpsc::Sender<DataUpdate>,
+    rx: mpsc::Receiver<DataCommand>,
+) {
+    let mut collector = Collector::new(sources, tx);
     loop {
         // Check for commands (non-blocking)
         while let Ok(cmd) = rx.try_recv() {
-            match cmd {
-                DataCommand::Pause => paused = true,
-                DataCommand::Resume => paused = false,
-                DataCommand::Stop => return,
-                DataCommand::ChangeSortMode => {
-                    process_monitor.next_sort_mode();
-                    let _ = tx.send(DataUpdate::Processes {
-                        processes: process_monitor.get_processes().to_vec(),
-                    });
-                }
-                DataCommand::SetGpuActive(active) => gpu_monitor.set_active(active),
+            if !collector.handle_command(cmd) {
+                return;
             }
         }
 
-        if !paused {
-            let now = Instant::now();
-
-            // Update everything every 2 seconds. Each tick drives a sysinfo
-            // process refresh which on macOS dispatches work across libdispatch
-            // workers; halving the rate halves that idle cost.
-            if now.duration_since(last_update) >= Duration::from_secs(2) {
-                // CPU
-                profile!("cpu_refresh", cpu_monitor.refresh());
-                let usages = cpu_monitor.cpu_usages();
-
-                // GPU
-                profile!("gpu_refresh", gpu_monitor.refresh());
-                let gpu_info = gpu_monitor.get_info();
-
-                // Memory
-                profile!("memory_refresh", memory_monitor.refresh());
-                let mem_info = memory_monitor.get_memory_info();
-
-                // Processes: CPU-only refresh every 2 seconds, full refresh every 10 seconds.
-                // Port refresh every 15 seconds (lsof is expensive).
-                let include_ports = now.duration_since(last_port_update) >= Duration::from_secs(15);
-                let full_refresh =
-                    now.duration_since(last_full_process_refresh) >= Duration::from_secs(10);
-
-                if include_ports {
-                    profile!(
-                        "process_refresh_with_ports",
-                        process_monitor.refresh(true, true)
-                    );
-                    last_port_update = now;
-                    last_full_process_refresh = now;
-                } else if full_refresh {
-                    profile!("process_refresh_full", process_monitor.refresh(false, true));
-                    last_full_process_refresh = now;
-                } else {
-                    profile!(
-                        "process_refresh_cpu_only",
-                        process_monitor.refresh(false, false)
-                    );
-                }
+        collector.tick(Instant::now());
 
-                // Send incremental updates (only new values, not full histories)
-                #[cfg(feature = "profile")]
-                let send_start = Instant::now();
-
-                // CPU: send current values for each core
-                let cpu_core_values: Vec<f32> = usages.iter().map(|(_, u)| *u).collect();
-                let cpu_avg = if !cpu_core_values.is_empty() {
-                    cpu_core_values.iter().sum::<f32>() / cpu_core_values.len() as f32
-                } else {
-                    0.0
-                };
-                let _ = tx.send(DataUpdate::Cpu {
-                    core_values: cpu_core_values,
-                    average_value: cpu_avg,
-                });
+        // Sleep to avoid busy-waiting
+        thread::sleep(Duration::from_millis(100));
+    }
+}
 
-                // GPU: send current values
-                let _ = tx.send(DataUpdate::Gpu {
-                    core_values: gpu_info.cores.iter().map(|c| c.utilization).collect(),
-                    overall_value: gpu_info.overall_utilization,
-                });
+/// Collection state for the background thread. Split from the thread loop so
+/// tests can drive ticks with synthetic timestamps.
+struct Collector {
+    sources: Sources,
+    tx: mpsc::Sender<DataUpdate>,
+    paused: bool,
+    // `None` means never run, which forces an immediate refresh.
+    last_update: Option<Instant>,
+    last_port_update: Option<Instant>,
+    last_full_process_refresh: Option<Instant>,
+    sent_topology: bool,
+}
 
-                // Memory: send current usage percentage
-                let _ = tx.send(DataUpdate::Memory {
-                    usage_value: mem_info.memory_usage_percentage() as f32,
-                    info: mem_info,
-                });
+impl Collector {
+    fn new(sources: Sources, tx: mpsc::Sender<DataUpdate>) -> Self {
+        Collector {
+            sources,
+            tx,
+            paused: false,
+            last_update: None,
+            last_port_update: None,
+            last_full_process_refresh: None,
+            sent_topology: false,
+        }
+    }
 
-                let _ = tx.send(DataUpdate::Processes {
-                    processes: process_monitor.get_processes().to_vec(),
+    /// Apply a command from the UI thread. Returns `false` once stopped.
+    fn handle_command(&mut self, cmd: DataCommand) -> bool {
+        match cmd {
+            DataCommand::Pause => self.paused = true,
+            DataCommand::Resume => self.paused = false,
+            DataCommand::Stop => return false,
+            DataCommand::ChangeSortMode => {
+                self.sources.processes.next_sort_mode();
+                let _ = self.tx.send(DataUpdate::Processes {
+                    processes: self.sources.processes.processes().to_vec(),
                 });
-                #[cfg(feature = "profile")]
-                log_timing("channel_send_all", send_start.elapsed().as_millis());
+            }
+            DataCommand::SetMemoryColumn(column) => {
+                self.sources.processes.set_memory_column(column);
+                // PSS/USS are gathered on full refreshes; don't wait for one
+                self.last_full_process_refresh = None;
+            }
+            DataCommand::SetGpuActive(active) => self.sources.gpu.set_active(active),
+        }
+        true
+    }
 
-                last_update = now;
+    /// Sample every source and send updates if the update interval has passed.
+    fn tick(&mut self, now: Instant) {
+        if self.paused {
+            return;
+        }
+        let due = |last: Option<Instant>, interval: u64| {
+            last.is_none_or(|t| now.duration_since(t) >= Duration::from_secs(interval))
+        };
+
+        // Update everything every 2 seconds. Each tick drives a sysinfo
+        // process refresh which on macOS dispatches work across libdispatch
+        // workers; halving the rate halves that idle cost.
+        if !due(self.last_update, 2) {
+            return;
+        }
+        let sources = &mut self.sources;
+
+        // CPU
+        profile!("cpu_refresh", sources.cpu.refresh());
+        let cores = sources.cpu.cores();
+
+        // GPU
+        profile!("gpu_refresh", sources.gpu.refresh());
+        let gpu_info = sources.gpu.get_info();
+
+        // Memory
+        profile!("memory_refresh", sources.memory.refresh());
+        let mem_info = sources.memory.memory_info();
+
+        // Power
+        profile!("power_refresh", sources.power.refresh());
+        let mut power_info = sources.power.power_info();
+        if let Some(soc) = &gpu_info.soc {
+            power_info.merge_soc(soc);
+        }
+
+        // Thermal
+        profile!("thermal_refresh", sources.thermal.refresh());
+        let thermal_info = sources.thermal.thermal_info();
+
+        // Processes: CPU-only refresh every 2 seconds, full refresh every 10 seconds.
+        // Port refresh every 15 seconds (lsof is expensive).
+        let include_ports = due(self.last_port_update, 15);
+        let full_refresh = due(self.last_full_process_refresh, 10);
+
+        if include_ports {
+            profile!(
+                "process_refresh_with_ports",
+                sources.processes.refresh(true, true)
+            );
+            self.last_port_update = Some(now);
+            self.last_full_process_refresh = Some(now);
+        } else if full_refresh {
+            profile!(
+                "process_refresh_full",
+                sources.processes.refresh(false, true)
+            );
+            self.last_full_process_refresh = Some(now);
+        } else {
+            profile!(
+                "process_refresh_cpu_only",
+                sources.processes.refresh(false, false)
+            );
+        }
+
+        // Send incremental updates (only new values, not full histories)
+        #[cfg(feature = "profile")]
+        let send_start = Instant::now();
+
+        // CPU: topology once, then current values for each core
+        if !self.sent_topology {
+            let _ = self.tx.send(DataUpdate::CpuTopology {
+                topology: sources.cpu.topology(),
+            });
+            self.sent_topology = true;
+        }
+        let cpu_core_values: Vec<f32> = cores.iter().map(|c| c.usage).collect();
+        let cpu_avg = if !cpu_core_values.is_empty() {
+            cpu_core_values.iter().sum::<f32>() / cpu_core_values.len() as f32
+        } else {
+            0.0
+        };
+        let _ = self.tx.send(DataUpdate::Cpu {
+            core_values: cpu_core_values,
+            average_value: cpu_avg,
+            frequencies_mhz: cores.iter().map(|c| c.frequency_mhz).collect(),
+            breakdown: sources.cpu.breakdown(),
+        });
+
+        // GPU: send current values
+        let _ = self.tx.send(DataUpdate::Gpu {
+            core_values: gpu_info.cores.iter().map(|c| c.utilization).collect(),
+            overall_value: gpu_info.overall_utilization,
+            vram: gpu_info.vram,
+            activity: gpu_info.activity.clone(),
+            soc: gpu_info.soc.clone(),
+        });
+
+        // Memory: send current usage percentage
+        let _ = self.tx.send(DataUpdate::Memory {
+            usage_value: mem_info.memory_usage_percentage() as f32,
+            pressure: mem_info.pressure,
+            info: Box::new(mem_info),
+        });
+
+        let _ = self.tx.send(DataUpdate::Processes {
+            processes: sources.processes.processes().to_vec(),
+        });
+
+        let _ = self.tx.send(DataUpdate::Power { info: power_info });
+        let _ = self.tx.send(DataUpdate::Thermal { info: thermal_info });
+        let _ = self.tx.send(DataUpdate::Host {
+            info: sources.host.host_info(),
+        });
+        #[cfg(feature = "profile")]
+        log_timing("channel_send_all", send_start.elapsed().as_millis());
+
+        self.last_update = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::fixture::{self, FixtureProcesses};
+
+    fn drain(rx: &mpsc::Receiver<DataUpdate>) -> Vec<DataUpdate> {
+        rx.try_iter().collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parse =
+            |args: &[&str]| parse_args(&args.iter().map(|a| a.to_string()).collect::<Vec<_>>());
+        assert_eq!(parse(&[]), Ok(Mode::Local));
+        assert_eq!(
+            parse(&["agent"]),
+            Ok(Mode::Agent {
+                listen: "127.0.0.1:7373".to_string()
+            })
+        );
+        assert_eq!(
+            parse(&["agent", "--listen", "0.0.0.0"]),
+            Ok(Mode::Agent {
+                listen: "0.0.0.0:7373".to_string()
+            })
+        );
+        assert_eq!(
+            parse(&["--connect", "buildbox:9000"]),
+            Ok(Mode::Connect {
+                addr: "buildbox:9000".to_string()
+            })
+        );
+        assert!(parse(&["--connect"]).is_err());
+        assert_eq!(
+            parse(&["fleet", "buildbox-1", "buildbox-2:9000"]),
+            Ok(Mode::Fleet {
+                hosts: vec!["buildbox-1".to_string(), "buildbox-2:9000".to_string()]
+            })
+        );
+        assert_eq!(parse(&["fleet"]), Ok(Mode::Fleet { hosts: vec![] }));
+        assert!(parse(&["agent", "--verbose"]).is_err());
+        assert!(parse(&["top"]).is_err());
+    }
+
+    #[test]
+    fn test_collector_tick_sends_one_update_per_source() {
+        let (tx, rx) = mpsc::channel();
+        let mut collector = Collector::new(fixture::sources(vec![]), tx);
+        collector.tick(Instant::now());
+
+        let updates = drain(&rx);
+        assert_eq!(updates.len(), 8);
+        match &updates[0] {
+            DataUpdate::CpuTopology { topology } => assert_eq!(topology.clusters.len(), 2),
+            _ => panic!("expected CPU topology first"),
+        }
+        match &updates[1] {
+            DataUpdate::Cpu {
+                core_values,
+                average_value,
+                frequencies_mhz,
+                breakdown,
+            } => {
+                assert_eq!(core_values, &vec![10.0, 30.0]);
+                assert_eq!(*average_value, 20.0);
+                assert_eq!(frequencies_mhz, &vec![2400, 2500]);
+                assert_eq!(breakdown.map(|b| b.iowait), Some(3.0));
+            }
+            _ => panic!("expected CPU update second"),
+        }
+        match &updates[2] {
+            DataUpdate::Gpu {
+                core_values,
+                overall_value,
+                ..
+            } => {
+                assert_eq!(core_values.len(), 4);
+                assert_eq!(*overall_value, 40.0);
+            }
+            _ => panic!("expected GPU update third"),
+        }
+        match &updates[3] {
+            DataUpdate::Memory { usage_value, .. } => assert_eq!(*usage_value, 50.0),
+            _ => panic!("expected memory update fourth"),
+        }
+        match &updates[5] {
+            DataUpdate::Power { info } => assert_eq!(info.package_watts, Some(12.5)),
+            _ => panic!("expected power update sixth"),
+        }
+        match &updates[6] {
+            DataUpdate::Thermal { info } => {
+                assert_eq!(info.cpu().map(|s| s.celsius), Some(64.0))
             }
+            _ => panic!("expected thermal update seventh"),
+        }
+        match &updates[7] {
+            DataUpdate::Host { info } => assert_eq!(info.name, "fixture-host"),
+            _ => panic!("expected host update last"),
         }
+    }
 
-        // Sleep to avoid busy-waiting
-        thread::sleep(Duration::from_millis(100));
+    #[test]
+    fn test_collector_refresh_cadence() {
+        let processes = FixtureProcesses::new(vec![fixture::process(1, "init", 0.5, 10)]);
+        let log = processes.refreshes.clone();
+        let mut sources = fixture::sources(vec![]);
+        sources.processes = Box::new(processes);
+
+        let (tx, rx) = mpsc::channel();
+        let mut collector = Collector::new(sources, tx);
+        let start = Instant::now();
+        for secs in 0..=16 {
+            collector.tick(start + Duration::from_secs(secs));
+        }
+
+        // One-second ticks only refresh every 2s: ports at 0 and 16, full at 10.
+        let refreshes = log.lock().unwrap().clone();
+        assert_eq!(refreshes.len(), 9);
+        assert_eq!(refreshes[0], (true, true));
+        assert_eq!(refreshes[1], (false, false));
+        assert_eq!(refreshes[5], (false, true));
+        assert_eq!(refreshes[8], (true, true));
+        // Topology is only sent with the first sample
+        assert_eq!(drain(&rx).len(), 9 * 7 + 1);
+
+        // Switching the MEM column to PSS pulls the next full refresh forward
+        assert!(collector.handle_command(DataCommand::SetMemoryColumn(
+            process::MemoryColumn::Proportional
+        )));
+        collector.tick(start + Duration::from_secs(18));
+        assert_eq!(log.lock().unwrap().last(), Some(&(false, true)));
+    }
+
+    #[test]
+    fn test_collector_pause_and_commands() {
+        let (tx, rx) = mpsc::channel();
+        let mut collector = Collector::new(
+            fixture::sources(vec![
+                fixture::process(1, "a", 1.0, 1),
+                fixture::process(2, "b", 2.0, 2),
+            ]),
+            tx,
+        );
+        assert!(collector.handle_command(DataCommand::Pause));
+        collector.tick(Instant::now());
+        assert!(drain(&rx).is_empty());
+
+        assert!(collector.handle_command(DataCommand::Resume));
+        assert!(collector.handle_command(DataCommand::ChangeSortMode));
+        match drain(&rx).as_slice() {
+            [DataUpdate::Processes { processes }] => assert_eq!(processes[0].pid, 2),
+            _ => panic!("sort change should resend processes"),
+        }
+
+        assert!(!collector.handle_command(DataCommand::Stop));
     }
 }
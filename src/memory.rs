@@ -1,8 +1,11 @@
-use std::collections::VecDeque;
+use crate::psi::PressureStall;
+#[cfg(target_os = "macos")]
 use std::mem;
+use std::path::PathBuf;
 use sysinfo::System;
 
 // FFI declaration for sysctlbyname
+#[cfg(target_os = "macos")]
 unsafe extern "C" {
     fn sysctlbyname(
         name: *const libc::c_char,
@@ -15,6 +18,7 @@ unsafe extern "C" {
 
 /// Query macOS memory pressure level via sysctl
 /// Returns: Some(1) = Normal, Some(2) = Warning, Some(4) = Critical, None = Error
+#[cfg(target_os = "macos")]
 fn get_macos_memory_pressure_level() -> Option<u32> {
     let name = b"kern.memorystatus_vm_pressure_level\0";
     let mut pressure_level: u32 = 0;
@@ -37,6 +41,11 @@ fn get_macos_memory_pressure_level() -> Option<u32> {
     }
 }
 
+#[cfg(not(target_os = "macos"))]
+fn get_macos_memory_pressure_level() -> Option<u32> {
+    None
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MemoryPressure {
     Green,  // Normal - macOS reports level 1
@@ -54,6 +63,126 @@ impl MemoryPressure {
     }
 }
 
+/// Where the memory went, in bytes. Each field is `None` when the platform
+/// does not report it: Linux fills the `/proc/meminfo` fields, macOS the
+/// `vm_statistics64` ones, and both report available and cached.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MemoryBreakdown {
+    /// Memory that can be handed out without swapping.
+    pub available: Option<u64>,
+    /// Page cache (file-backed pages on macOS).
+    pub cached: Option<u64>,
+    pub buffers: Option<u64>,
+    pub shared: Option<u64>,
+    pub dirty: Option<u64>,
+    pub slab: Option<u64>,
+    pub wired: Option<u64>,
+    pub compressed: Option<u64>,
+    pub purgeable: Option<u64>,
+}
+
+impl MemoryBreakdown {
+    /// Reclaimable cache: page cache plus buffers.
+    pub fn cache(&self) -> Option<u64> {
+        match (self.cached, self.buffers) {
+            (None, None) => None,
+            (cached, buffers) => Some(cached.unwrap_or(0) + buffers.unwrap_or(0)),
+        }
+    }
+
+    /// Build from a macOS `vm_statistics64` sample, following Activity
+    /// Monitor: cached files are file-backed plus purgeable pages, and
+    /// memory in use is app (anonymous, non-purgeable) + wired + compressed.
+    pub fn from_vm_pages(pages: &VmPages, total_memory: u64) -> Self {
+        let bytes = |count: u64| count * pages.page_size;
+        let app = bytes(pages.internal.saturating_sub(pages.purgeable));
+        let wired = bytes(pages.wired);
+        let compressed = bytes(pages.compressed);
+        MemoryBreakdown {
+            available: Some(total_memory.saturating_sub(app + wired + compressed)),
+            cached: Some(bytes(pages.external + pages.purgeable)),
+            wired: Some(wired),
+            compressed: Some(compressed),
+            purgeable: Some(bytes(pages.purgeable)),
+            ..Default::default()
+        }
+    }
+}
+
+/// Page counts from `host_statistics64(HOST_VM_INFO64)`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VmPages {
+    pub page_size: u64,
+    pub wired: u64,
+    /// Pages occupied by the compressor, i.e. compressed memory.
+    pub compressed: u64,
+    pub purgeable: u64,
+    /// File-backed pages.
+    pub external: u64,
+    /// Anonymous pages.
+    pub internal: u64,
+}
+
+/// Parse `/proc/meminfo` ("MemAvailable:   8123456 kB" lines).
+pub fn parse_meminfo(text: &str) -> MemoryBreakdown {
+    let mut breakdown = MemoryBreakdown::default();
+    for line in text.lines() {
+        let Some((key, rest)) = line.split_once(':') else {
+            continue;
+        };
+        let Some(kb) = rest
+            .split_whitespace()
+            .next()
+            .and_then(|v| v.parse::<u64>().ok())
+        else {
+            continue;
+        };
+        let field = match key {
+            "MemAvailable" => &mut breakdown.available,
+            "Cached" => &mut breakdown.cached,
+            "Buffers" => &mut breakdown.buffers,
+            "Shmem" => &mut breakdown.shared,
+            "Dirty" => &mut breakdown.dirty,
+            "Slab" => &mut breakdown.slab,
+            _ => continue,
+        };
+        *field = Some(kb * 1024);
+    }
+    breakdown
+}
+
+#[cfg(target_os = "macos")]
+#[allow(deprecated)] // libc points the mach port helpers at the mach2 crate, which we don't depend on
+fn vm_pages() -> Option<VmPages> {
+    let mut stats: libc::vm_statistics64 = unsafe { mem::zeroed() };
+    let mut count = libc::HOST_VM_INFO64_COUNT;
+    let result = unsafe {
+        libc::host_statistics64(
+            libc::mach_host_self(),
+            libc::HOST_VM_INFO64,
+            &mut stats as *mut _ as libc::host_info64_t,
+            &mut count,
+        )
+    };
+    if result != libc::KERN_SUCCESS {
+        return None;
+    }
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    Some(VmPages {
+        page_size: u64::try_from(page_size).ok()?,
+        wired: stats.wire_count as u64,
+        compressed: stats.compressor_page_count as u64,
+        purgeable: stats.purgeable_count as u64,
+        external: stats.external_page_count as u64,
+        internal: stats.internal_page_count as u64,
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+fn vm_pages() -> Option<VmPages> {
+    None
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct MemoryInfo {
     pub total_memory: u64,
@@ -61,8 +190,11 @@ pub struct MemoryInfo {
     pub total_swap: u64,
     pub used_swap: u64,
     pub pressure: MemoryPressure,
-    #[allow(dead_code)] // May be used for future features
+    /// Memory PSI "some" avg10 on Linux, used percentage elsewhere.
     pub pressure_percentage: f64,
+    /// CPU, memory and IO pressure stall information (Linux only).
+    pub stall: PressureStall,
+    pub breakdown: MemoryBreakdown,
 }
 
 impl MemoryInfo {
@@ -78,6 +210,24 @@ impl MemoryInfo {
         }
     }
 
+    /// Used memory minus reclaimable cache: what applications and the kernel
+    /// actually hold. Equals `used_memory` when no breakdown is available.
+    pub fn used_excluding_cache(&self) -> u64 {
+        match (self.breakdown.available, self.breakdown.cache()) {
+            (Some(available), _) => self.total_memory.saturating_sub(available),
+            (None, Some(cache)) => self.used_memory.saturating_sub(cache),
+            (None, None) => self.used_memory,
+        }
+    }
+
+    pub fn used_excluding_cache_percentage(&self) -> f64 {
+        if self.total_memory == 0 {
+            0.0
+        } else {
+            (self.used_excluding_cache() as f64 / self.total_memory as f64) * 100.0
+        }
+    }
+
     pub fn swap_usage_percentage(&self) -> f64 {
         if self.total_swap == 0 {
             0.0
@@ -90,8 +240,10 @@ impl MemoryInfo {
 #[derive(Debug)]
 pub struct MemoryMonitor {
     system: System,
-    pressure_history: VecDeque<MemoryPressure>,
-    max_history: usize,
+    psi_root: PathBuf,
+    stall: PressureStall,
+    meminfo_path: PathBuf,
+    breakdown: MemoryBreakdown,
 }
 
 impl MemoryMonitor {
@@ -99,22 +251,22 @@ impl MemoryMonitor {
         let system = System::new();
         MemoryMonitor {
             system,
-            pressure_history: VecDeque::new(),
-            max_history: 300, // 5 minutes at 1 second intervals
+            psi_root: PathBuf::from("/proc/pressure"),
+            stall: PressureStall::default(),
+            meminfo_path: PathBuf::from("/proc/meminfo"),
+            breakdown: MemoryBreakdown::default(),
         }
     }
 
     pub fn refresh(&mut self) {
         self.system.refresh_memory();
-
-        // Calculate and store pressure
-        let info = self.get_memory_info();
-        self.pressure_history.push_back(info.pressure);
-
-        // Keep history within bounds
-        if self.pressure_history.len() > self.max_history {
-            self.pressure_history.pop_front();
-        }
+        self.stall = PressureStall::read(&self.psi_root);
+        self.breakdown = match vm_pages() {
+            Some(pages) => MemoryBreakdown::from_vm_pages(&pages, self.system.total_memory()),
+            None => std::fs::read_to_string(&self.meminfo_path)
+                .map(|text| parse_meminfo(&text))
+                .unwrap_or_default(),
+        };
     }
 
     pub fn get_memory_info(&self) -> MemoryInfo {
@@ -122,59 +274,28 @@ impl MemoryMonitor {
         let used_memory = self.system.used_memory();
         let total_swap = self.system.total_swap();
         let used_swap = self.system.used_swap();
+        let memory_stall = self.stall.memory;
 
         // Use native macOS memory pressure level from kern.memorystatus_vm_pressure_level
-        // This matches Activity Monitor's calculation exactly
-        let pressure = if let Some(level) = get_macos_memory_pressure_level() {
-            // macOS returns: 1 = Normal, 2 = Warning, 4 = Critical
-            match level {
-                1 => MemoryPressure::Green,
-                2 => MemoryPressure::Yellow,
-                4 => MemoryPressure::Red,
-                _ => {
-                    // Unknown level, fall back to simple heuristic
-                    // This should rarely happen
-                    let free_memory = total_memory.saturating_sub(used_memory);
-                    let free_percentage = if total_memory == 0 {
-                        100.0
-                    } else {
-                        (free_memory as f64 / total_memory as f64) * 100.0
-                    };
-
-                    if free_percentage >= 50.0 {
-                        MemoryPressure::Green
-                    } else if free_percentage >= 30.0 {
-                        MemoryPressure::Yellow
-                    } else {
-                        MemoryPressure::Red
-                    }
-                }
-            }
-        } else {
-            // Fallback if sysctl fails (non-macOS or permission issue)
-            let free_memory = total_memory.saturating_sub(used_memory);
-            let free_percentage = if total_memory == 0 {
-                100.0
-            } else {
-                (free_memory as f64 / total_memory as f64) * 100.0
-            };
-
-            if free_percentage >= 50.0 {
-                MemoryPressure::Green
-            } else if free_percentage >= 30.0 {
-                MemoryPressure::Yellow
-            } else {
-                MemoryPressure::Red
-            }
+        // This matches Activity Monitor's calculation exactly. macOS returns:
+        // 1 = Normal, 2 = Warning, 4 = Critical
+        let pressure = match get_macos_memory_pressure_level() {
+            Some(1) => MemoryPressure::Green,
+            Some(2) => MemoryPressure::Yellow,
+            Some(4) => MemoryPressure::Red,
+            // Linux: stall time is the only signal that ignores page cache
+            _ => match memory_stall {
+                Some(psi) => psi.memory_pressure(),
+                None => free_memory_pressure(total_memory, used_memory),
+            },
         };
 
-        // Calculate pressure percentage for display
-        // This is a visual indicator, not used for pressure level determination
-        let free_memory = total_memory.saturating_sub(used_memory);
-        let free_percentage = if total_memory == 0 {
-            100.0
-        } else {
-            (free_memory as f64 / total_memory as f64) * 100.0
+        // Share of time tasks stalled on memory where PSI exists, otherwise
+        // plain usage
+        let pressure_percentage = match memory_stall {
+            Some(psi) => psi.some.avg10 as f64,
+            None if total_memory == 0 => 0.0,
+            None => used_memory as f64 / total_memory as f64 * 100.0,
         };
 
         MemoryInfo {
@@ -183,15 +304,12 @@ impl MemoryMonitor {
             total_swap,
             used_swap,
             pressure,
-            pressure_percentage: 100.0 - free_percentage,
+            pressure_percentage,
+            stall: self.stall,
+            breakdown: self.breakdown,
         }
     }
 
-    #[allow(dead_code)] // May be used for future timeline features
-    pub fn get_pressure_history(&self) -> &VecDeque<MemoryPressure> {
-        &self.pressure_history
-    }
-
     #[allow(dead_code)] // May be used for future conditional features
     pub fn is_available(&self) -> bool {
         // Memory monitoring is always available
@@ -199,8 +317,102 @@ impl MemoryMonitor {
     }
 }
 
+/// Last-resort heuristic when neither macOS nor PSI report pressure.
+fn free_memory_pressure(total_memory: u64, used_memory: u64) -> MemoryPressure {
+    let free_memory = total_memory.saturating_sub(used_memory);
+    let free_percentage = if total_memory == 0 {
+        100.0
+    } else {
+        (free_memory as f64 / total_memory as f64) * 100.0
+    };
+
+    if free_percentage >= 50.0 {
+        MemoryPressure::Green
+    } else if free_percentage >= 30.0 {
+        MemoryPressure::Yellow
+    } else {
+        MemoryPressure::Red
+    }
+}
+
 impl Default for MemoryMonitor {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KB: u64 = 1024;
+
+    fn info(total: u64, used: u64, breakdown: MemoryBreakdown) -> MemoryInfo {
+        MemoryInfo {
+            total_memory: total,
+            used_memory: used,
+            total_swap: 0,
+            used_swap: 0,
+            pressure: MemoryPressure::Green,
+            pressure_percentage: 0.0,
+            stall: PressureStall::default(),
+            breakdown,
+        }
+    }
+
+    #[test]
+    fn test_parse_meminfo() {
+        let breakdown = parse_meminfo(
+            "MemTotal:       16000000 kB\n\
+             MemFree:         1000000 kB\n\
+             MemAvailable:    9000000 kB\n\
+             Buffers:          200000 kB\n\
+             Cached:          7000000 kB\n\
+             SwapCached:            0 kB\n\
+             Dirty:              1234 kB\n\
+             Shmem:            300000 kB\n\
+             Slab:             400000 kB\n\
+             HugePages_Total:       0\n",
+        );
+        assert_eq!(breakdown.available, Some(9_000_000 * KB));
+        assert_eq!(breakdown.cache(), Some(7_200_000 * KB));
+        assert_eq!(breakdown.shared, Some(300_000 * KB));
+        assert_eq!(breakdown.dirty, Some(1234 * KB));
+        assert_eq!(breakdown.slab, Some(400_000 * KB));
+        assert_eq!(breakdown.wired, None);
+
+        let memory = info(16_000_000 * KB, 15_000_000 * KB, breakdown);
+        assert_eq!(memory.used_excluding_cache(), 7_000_000 * KB);
+        // Without MemAvailable, subtract the cache from used
+        let older = MemoryBreakdown {
+            available: None,
+            ..breakdown
+        };
+        let memory = info(16_000_000 * KB, 15_000_000 * KB, older);
+        assert_eq!(memory.used_excluding_cache(), 7_800_000 * KB);
+        assert_eq!(
+            info(100, 40, MemoryBreakdown::default()).used_excluding_cache(),
+            40
+        );
+    }
+
+    #[test]
+    fn test_breakdown_from_vm_pages() {
+        let pages = VmPages {
+            page_size: 16 * KB,
+            wired: 100,
+            compressed: 50,
+            purgeable: 20,
+            external: 300,
+            internal: 520,
+        };
+        let breakdown = MemoryBreakdown::from_vm_pages(&pages, 2000 * 16 * KB);
+        assert_eq!(breakdown.wired, Some(100 * 16 * KB));
+        assert_eq!(breakdown.compressed, Some(50 * 16 * KB));
+        assert_eq!(breakdown.cached, Some(320 * 16 * KB));
+        // app 500 + wired 100 + compressed 50 pages in use
+        assert_eq!(breakdown.available, Some(1350 * 16 * KB));
+        assert_eq!(breakdown.purgeable, Some(20 * 16 * KB));
+        assert_eq!(breakdown.buffers, None);
+    }
+}
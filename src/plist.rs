@@ -0,0 +1,237 @@
+//! Minimal XML property list reader.
+//!
+//! Handles the subset powermetrics emits with `-f plist`: dict, array,
+//! string, integer, real, true/false, date and data. No DTD validation and
+//! no binary plists.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Dict(Vec<(String, Value)>),
+    Array(Vec<Value>),
+    String(String),
+    Integer(i64),
+    Real(f64),
+    Bool(bool),
+    /// ISO 8601 timestamp, kept as text.
+    Date(String),
+    /// Base64 payload, kept as text.
+    Data(String),
+}
+
+impl Value {
+    /// Look up a key in a dict. Returns `None` for other variants.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Dict(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Numeric value; integers are widened so callers need not care which
+    /// representation powermetrics picked for a field.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Real(v) => Some(*v),
+            Value::Integer(v) => Some(*v as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Integer(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// Parse one plist document. Returns `None` on malformed input.
+pub fn parse(xml: &str) -> Option<Value> {
+    let mut parser = Parser { rest: xml };
+    // Skip the prolog (<?xml ...?>, <!DOCTYPE ...>) up to <plist ...>
+    loop {
+        parser.skip_whitespace();
+        if parser.rest.starts_with("<?") || parser.rest.starts_with("<!") {
+            let end = parser.rest.find('>')?;
+            parser.rest = &parser.rest[end + 1..];
+        } else {
+            break;
+        }
+    }
+    match parser.next_tag()? {
+        Tag::Open("plist") => {
+            let value = parser.value()?;
+            match parser.next_tag()? {
+                Tag::Close("plist") => Some(value),
+                _ => None,
+            }
+        }
+        // Bare values without the <plist> wrapper are tolerated
+        tag => parser.value_from(tag),
+    }
+}
+
+enum Tag<'a> {
+    Open(&'a str),
+    Close(&'a str),
+    Empty(&'a str),
+}
+
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    /// Consume the next tag, ignoring its attributes.
+    fn next_tag(&mut self) -> Option<Tag<'a>> {
+        self.skip_whitespace();
+        let body = self.rest.strip_prefix('<')?;
+        let end = body.find('>')?;
+        let inner = &body[..end];
+        self.rest = &body[end + 1..];
+
+        if let Some(name) = inner.strip_prefix('/') {
+            return Some(Tag::Close(name.trim()));
+        }
+        let (inner, empty) = match inner.strip_suffix('/') {
+            Some(inner) => (inner, true),
+            None => (inner, false),
+        };
+        let name = inner.split_whitespace().next()?;
+        Some(if empty {
+            Tag::Empty(name)
+        } else {
+            Tag::Open(name)
+        })
+    }
+
+    /// Text up to the closing `</name>` tag.
+    fn text(&mut self, name: &str) -> Option<String> {
+        let close = format!("</{}>", name);
+        let end = self.rest.find(&close)?;
+        let text = unescape(&self.rest[..end]);
+        self.rest = &self.rest[end + close.len()..];
+        Some(text)
+    }
+
+    fn value(&mut self) -> Option<Value> {
+        let tag = self.next_tag()?;
+        self.value_from(tag)
+    }
+
+    fn value_from(&mut self, tag: Tag<'a>) -> Option<Value> {
+        match tag {
+            Tag::Empty("true") => Some(Value::Bool(true)),
+            Tag::Empty("false") => Some(Value::Bool(false)),
+            Tag::Empty("dict") => Some(Value::Dict(Vec::new())),
+            Tag::Empty("array") => Some(Value::Array(Vec::new())),
+            Tag::Empty("string") => Some(Value::String(String::new())),
+            Tag::Open("string") => self.text("string").map(Value::String),
+            Tag::Open("integer") => self
+                .text("integer")?
+                .trim()
+                .parse()
+                .ok()
+                .map(Value::Integer),
+            Tag::Open("real") => self.text("real")?.trim().parse().ok().map(Value::Real),
+            Tag::Open("date") => self.text("date").map(Value::Date),
+            Tag::Open("data") => self.text("data").map(|d| Value::Data(d.trim().to_string())),
+            Tag::Open("array") => {
+                let mut items = Vec::new();
+                loop {
+                    match self.next_tag()? {
+                        Tag::Close("array") => return Some(Value::Array(items)),
+                        tag => items.push(self.value_from(tag)?),
+                    }
+                }
+            }
+            Tag::Open("dict") => {
+                let mut entries = Vec::new();
+                loop {
+                    match self.next_tag()? {
+                        Tag::Close("dict") => return Some(Value::Dict(entries)),
+                        Tag::Open("key") => {
+                            let key = self.text("key")?;
+                            entries.push((key, self.value()?));
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+fn unescape(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nested_plist() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>name</key><string>Tom &amp; Jerry</string>
+	<key>count</key><integer>-2</integer>
+	<key>ratio</key><real>0.25</real>
+	<key>on</key><true/>
+	<key>empty</key><array/>
+	<key>items</key>
+	<array>
+		<dict><key>freq</key><integer>389</integer></dict>
+		<string/>
+	</array>
+</dict>
+</plist>"#;
+        let value = parse(xml).expect("valid plist");
+        assert_eq!(
+            value.get("name").and_then(Value::as_str),
+            Some("Tom & Jerry")
+        );
+        assert_eq!(value.get("count").and_then(Value::as_i64), Some(-2));
+        assert_eq!(value.get("ratio").and_then(Value::as_f64), Some(0.25));
+        assert_eq!(value.get("on"), Some(&Value::Bool(true)));
+        assert_eq!(value.get("empty").and_then(Value::as_array), Some(&[][..]));
+
+        let items = value.get("items").and_then(Value::as_array).unwrap();
+        assert_eq!(items[0].get("freq").and_then(Value::as_f64), Some(389.0));
+        assert_eq!(items[1], Value::String(String::new()));
+    }
+
+    #[test]
+    fn test_parse_rejects_truncated_document() {
+        assert!(parse("<plist><dict><key>a</key><integer>1</integer>").is_none());
+        assert!(parse("<plist><dict><integer>1</integer></dict></plist>").is_none());
+    }
+}
@@ -0,0 +1,450 @@
+//! Power draw and battery state.
+//!
+//! Linux reads RAPL energy counters from `/sys/class/powercap/intel-rapl*`
+//! and batteries from `/sys/class/power_supply`. On macOS the SoC watts come
+//! from the powermetrics stream (merged in by the collector, see
+//! [`PowerInfo::merge_soc`]) and the battery from `ioreg -rn AppleSmartBattery`.
+//! Every reader returns `None` where its files or tools are absent, so the
+//! monitor runs the same code on every platform.
+
+use crate::powermetrics::SocPower;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// `ioreg` is a subprocess; battery state changes slowly.
+const IOREG_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatteryState {
+    Charging,
+    Discharging,
+    Full,
+    /// On AC but not charging (charge limit reached, optimised charging).
+    NotCharging,
+    Unknown,
+}
+
+impl BatteryState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BatteryState::Charging => "charging",
+            BatteryState::Discharging => "on battery",
+            BatteryState::Full => "full",
+            BatteryState::NotCharging => "on ac",
+            BatteryState::Unknown => "",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatteryInfo {
+    pub charge_percent: f32,
+    pub state: BatteryState,
+    /// Watts into (+) or out of (-) the battery, where reported.
+    pub rate_watts: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PowerInfo {
+    /// Whole package (Linux RAPL) or CPU+GPU+ANE (Apple Silicon).
+    pub package_watts: Option<f32>,
+    pub cpu_watts: Option<f32>,
+    pub gpu_watts: Option<f32>,
+    pub dram_watts: Option<f32>,
+    pub battery: Option<BatteryInfo>,
+}
+
+impl PowerInfo {
+    /// Fill readings the platform sources lack from a powermetrics sample.
+    pub fn merge_soc(&mut self, soc: &SocPower) {
+        let watts = |mw: Option<f32>| mw.map(|mw| mw / 1000.0);
+        self.package_watts = self.package_watts.or(watts(soc.package_power_mw));
+        self.cpu_watts = self.cpu_watts.or(watts(soc.cpu_power_mw));
+        self.gpu_watts = self.gpu_watts.or(watts(soc.gpu_power_mw));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == PowerInfo::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RaplKind {
+    Package,
+    Core,
+    Uncore,
+    Dram,
+}
+
+#[derive(Debug)]
+struct RaplZone {
+    kind: RaplKind,
+    energy_path: PathBuf,
+    /// Counter wraps back to zero after this many microjoules.
+    max_energy_uj: u64,
+}
+
+/// RAPL energy counters, turned into watts by differencing successive reads.
+#[derive(Debug)]
+pub struct Rapl {
+    zones: Vec<RaplZone>,
+    previous: Option<(Instant, Vec<Option<u64>>)>,
+}
+
+impl Rapl {
+    /// Find readable zones under `root` (normally `/sys/class/powercap`).
+    /// Recent kernels restrict `energy_uj` to root, in which case there is
+    /// nothing to read and this returns `None`.
+    pub fn discover(root: &Path) -> Option<Self> {
+        let mut zones = Vec::new();
+        for entry in std::fs::read_dir(root).ok()?.flatten() {
+            let dir_name = entry.file_name();
+            let Some(dir_name) = dir_name.to_str() else {
+                continue;
+            };
+            // intel-rapl:0 is a package, intel-rapl:0:1 a subzone of it.
+            // The bare `intel-rapl` entry is the control type, not a zone.
+            let Some(index) = dir_name.strip_prefix("intel-rapl:") else {
+                continue;
+            };
+            let is_subzone = index.contains(':');
+            let dir = entry.path();
+            let Some(name) = read_trimmed(&dir.join("name")) else {
+                continue;
+            };
+            let kind = match (is_subzone, name.as_str()) {
+                (false, n) if n.starts_with("package") => RaplKind::Package,
+                (_, "dram") => RaplKind::Dram,
+                (true, "core") => RaplKind::Core,
+                (true, "uncore") => RaplKind::Uncore,
+                // psys overlaps package; unknown zones are skipped
+                _ => continue,
+            };
+            let energy_path = dir.join("energy_uj");
+            if read_u64(&energy_path).is_none() {
+                continue;
+            }
+            zones.push(RaplZone {
+                kind,
+                energy_path,
+                max_energy_uj: read_u64(&dir.join("max_energy_range_uj")).unwrap_or(u64::MAX),
+            });
+        }
+
+        if zones.is_empty() {
+            None
+        } else {
+            Some(Rapl {
+                zones,
+                previous: None,
+            })
+        }
+    }
+
+    /// Read every zone and return power since the previous call. The first
+    /// call only primes the counters.
+    pub fn sample(&mut self, now: Instant) -> PowerInfo {
+        let current: Vec<Option<u64>> = self
+            .zones
+            .iter()
+            .map(|zone| read_u64(&zone.energy_path))
+            .collect();
+
+        let mut info = PowerInfo::default();
+        if let Some((then, previous)) = &self.previous {
+            let elapsed = now.duration_since(*then).as_secs_f64();
+            if elapsed > 0.0 {
+                for ((zone, cur), prev) in self.zones.iter().zip(&current).zip(previous) {
+                    let (Some(cur), Some(prev)) = (*cur, *prev) else {
+                        continue;
+                    };
+                    let delta = if cur >= prev {
+                        cur - prev
+                    } else {
+                        // Counter wrapped
+                        zone.max_energy_uj.saturating_sub(prev) + cur
+                    };
+                    let watts = (delta as f64 / 1e6 / elapsed) as f32;
+                    let slot = match zone.kind {
+                        RaplKind::Package => &mut info.package_watts,
+                        RaplKind::Core => &mut info.cpu_watts,
+                        RaplKind::Uncore => &mut info.gpu_watts,
+                        RaplKind::Dram => &mut info.dram_watts,
+                    };
+                    // Multi-socket machines have one package zone per socket
+                    *slot = Some(slot.unwrap_or(0.0) + watts);
+                }
+            }
+        }
+
+        self.previous = Some((now, current));
+        info
+    }
+}
+
+/// First system battery under `root` (normally `/sys/class/power_supply`).
+/// Peripheral batteries (mice, headsets) report `scope = Device` and are skipped.
+pub fn read_sysfs_battery(root: &Path) -> Option<BatteryInfo> {
+    let mut supplies: Vec<PathBuf> = std::fs::read_dir(root)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .collect();
+    supplies.sort();
+
+    supplies.into_iter().find_map(|dir| {
+        if read_trimmed(&dir.join("type")).as_deref() != Some("Battery")
+            || read_trimmed(&dir.join("scope")).as_deref() == Some("Device")
+        {
+            return None;
+        }
+        let charge_percent = read_trimmed(&dir.join("capacity"))?.parse().ok()?;
+        let state = match read_trimmed(&dir.join("status")).as_deref() {
+            Some("Charging") => BatteryState::Charging,
+            Some("Discharging") => BatteryState::Discharging,
+            Some("Full") => BatteryState::Full,
+            Some("Not charging") => BatteryState::NotCharging,
+            _ => BatteryState::Unknown,
+        };
+        // power_now is in µW; some drivers only give current (µA) and voltage (µV)
+        let watts = read_u64(&dir.join("power_now"))
+            .map(|uw| uw as f64 / 1e6)
+            .or_else(|| {
+                let ua = read_u64(&dir.join("current_now"))?;
+                let uv = read_u64(&dir.join("voltage_now"))?;
+                Some(ua as f64 * uv as f64 / 1e12)
+            })
+            .map(|w| w as f32);
+        let rate_watts = watts.map(|w| match state {
+            BatteryState::Discharging => -w,
+            _ => w,
+        });
+        Some(BatteryInfo {
+            charge_percent,
+            state,
+            rate_watts,
+        })
+    })
+}
+
+/// Parse `ioreg -rn AppleSmartBattery` output.
+pub fn parse_ioreg_battery(output: &str) -> Option<BatteryInfo> {
+    let field = |key: &str| -> Option<&str> {
+        let prefix = format!("\"{}\" = ", key);
+        output
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(prefix.as_str()))
+            .map(str::trim)
+    };
+    let number = |key: &str| field(key)?.parse::<i64>().ok();
+    let flag = |key: &str| field(key) == Some("Yes");
+
+    // On Apple Silicon CurrentCapacity is already a percentage (MaxCapacity
+    // 100); Intel Macs report mAh for both.
+    let current = number("CurrentCapacity")?;
+    let max = number("MaxCapacity").filter(|&m| m > 0)?;
+    let charge_percent = (current as f32 / max as f32 * 100.0).clamp(0.0, 100.0);
+
+    let state = if flag("FullyCharged") {
+        BatteryState::Full
+    } else if flag("IsCharging") {
+        BatteryState::Charging
+    } else if flag("ExternalConnected") {
+        BatteryState::NotCharging
+    } else {
+        BatteryState::Discharging
+    };
+
+    // Amperage is signed mA but older releases print it as an unsigned
+    // 64-bit value, so negative currents show up as huge numbers.
+    let amperage = field("Amperage").and_then(|v| {
+        v.parse::<i64>()
+            .ok()
+            .or_else(|| v.parse::<u64>().ok().map(|u| u as i64))
+    });
+    let rate_watts = match (amperage, number("Voltage")) {
+        (Some(ma), Some(mv)) => Some((ma as f64 * mv as f64 / 1e6) as f32),
+        _ => None,
+    };
+
+    Some(BatteryInfo {
+        charge_percent,
+        state,
+        rate_watts,
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn ioreg_battery_report() -> Option<String> {
+    let output = std::process::Command::new("ioreg")
+        .args(["-rn", "AppleSmartBattery"])
+        .output()
+        .ok()?;
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn ioreg_battery_report() -> Option<String> {
+    None
+}
+
+#[derive(Debug)]
+pub struct PowerMonitor {
+    rapl: Option<Rapl>,
+    supply_root: PathBuf,
+    current: PowerInfo,
+    last_ioreg: Option<Instant>,
+    ioreg_battery: Option<BatteryInfo>,
+}
+
+impl PowerMonitor {
+    pub fn new() -> Self {
+        PowerMonitor {
+            rapl: Rapl::discover(Path::new("/sys/class/powercap")),
+            supply_root: PathBuf::from("/sys/class/power_supply"),
+            current: PowerInfo::default(),
+            last_ioreg: None,
+            ioreg_battery: None,
+        }
+    }
+
+    pub fn refresh(&mut self) {
+        let now = Instant::now();
+        let mut info = self
+            .rapl
+            .as_mut()
+            .map(|rapl| rapl.sample(now))
+            .unwrap_or_default();
+
+        info.battery = read_sysfs_battery(&self.supply_root);
+        if info.battery.is_none() {
+            if self
+                .last_ioreg
+                .is_none_or(|t| now.duration_since(t) >= IOREG_INTERVAL)
+            {
+                self.last_ioreg = Some(now);
+                self.ioreg_battery = ioreg_battery_report().and_then(|r| parse_ioreg_battery(&r));
+            }
+            info.battery = self.ioreg_battery;
+        }
+
+        self.current = info;
+    }
+
+    pub fn get_info(&self) -> PowerInfo {
+        self.current
+    }
+}
+
+impl Default for PowerMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    read_trimmed(path)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::fixture::FixtureDir;
+
+    #[test]
+    fn test_rapl_watts_and_wraparound() {
+        let fx = FixtureDir::new("rapl");
+        fx.write("intel-rapl/enabled", "1\n");
+        fx.write("intel-rapl:0/name", "package-0\n");
+        fx.write("intel-rapl:0/energy_uj", "1000000\n");
+        fx.write("intel-rapl:0/max_energy_range_uj", "262143328850\n");
+        fx.write("intel-rapl:0:0/name", "core\n");
+        fx.write("intel-rapl:0:0/energy_uj", "262142328850\n");
+        fx.write("intel-rapl:0:0/max_energy_range_uj", "262143328850\n");
+        fx.write("intel-rapl:1/name", "psys\n");
+        fx.write("intel-rapl:1/energy_uj", "5\n");
+
+        let mut rapl = Rapl::discover(fx.path()).expect("zones");
+        let t0 = Instant::now();
+        assert!(rapl.sample(t0).is_empty());
+
+        // 30 J over 2 s (15 W) on the package; the core counter wraps past its max
+        fx.write("intel-rapl:0/energy_uj", "31000000\n");
+        fx.write("intel-rapl:0:0/energy_uj", "9000000\n");
+        let info = rapl.sample(t0 + Duration::from_secs(2));
+        assert_eq!(info.package_watts, Some(15.0));
+        assert_eq!(info.cpu_watts, Some(5.0));
+        assert_eq!(info.gpu_watts, None);
+    }
+
+    #[test]
+    fn test_unreadable_rapl_is_none() {
+        let fx = FixtureDir::new("rapl-none");
+        fx.write("intel-rapl:0/name", "package-0\n");
+        assert!(Rapl::discover(fx.path()).is_none());
+    }
+
+    #[test]
+    fn test_sysfs_battery_skips_peripherals() {
+        let fx = FixtureDir::new("power-supply");
+        fx.write("AC/type", "Mains\n");
+        fx.write("BAT0/type", "Battery\n");
+        fx.write("BAT0/capacity", "76\n");
+        fx.write("BAT0/status", "Discharging\n");
+        fx.write("BAT0/current_now", "1500000\n");
+        fx.write("BAT0/voltage_now", "12000000\n");
+        fx.write("hidpp_battery_0/type", "Battery\n");
+        fx.write("hidpp_battery_0/scope", "Device\n");
+        fx.write("hidpp_battery_0/capacity", "10\n");
+
+        let battery = read_sysfs_battery(fx.path()).expect("BAT0");
+        assert_eq!(battery.charge_percent, 76.0);
+        assert_eq!(battery.state, BatteryState::Discharging);
+        assert_eq!(battery.rate_watts, Some(-18.0));
+    }
+
+    #[test]
+    fn test_parse_ioreg_battery() {
+        let output = r#"+-o AppleSmartBattery  <class AppleSmartBattery, id 0x100000254, registered, matched, active, busy 0 (0 ms), retain 7>
+    {
+      "BatteryData" = {"Voltage"=11000,"StateOfCharge"=82}
+      "ExternalConnected" = No
+      "Amperage" = 18446744073709550616
+      "CurrentCapacity" = 82
+      "MaxCapacity" = 100
+      "IsCharging" = No
+      "FullyCharged" = No
+      "Voltage" = 12500
+    }"#;
+        let battery = parse_ioreg_battery(output).expect("battery");
+        assert_eq!(battery.charge_percent, 82.0);
+        assert_eq!(battery.state, BatteryState::Discharging);
+        // -1000 mA at 12.5 V
+        assert_eq!(battery.rate_watts, Some(-12.5));
+
+        assert!(parse_ioreg_battery("").is_none());
+    }
+
+    #[test]
+    fn test_merge_soc_keeps_platform_readings() {
+        let mut info = PowerInfo {
+            cpu_watts: Some(3.0),
+            ..Default::default()
+        };
+        info.merge_soc(&SocPower {
+            cpu_power_mw: Some(600.0),
+            gpu_power_mw: Some(27.0),
+            package_power_mw: Some(1500.0),
+            ..Default::default()
+        });
+        assert_eq!(info.cpu_watts, Some(3.0));
+        assert_eq!(info.gpu_watts, Some(0.027));
+        assert_eq!(info.package_watts, Some(1.5));
+    }
+}
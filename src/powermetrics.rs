@@ -0,0 +1,350 @@
+//! Streaming reader for `powermetrics -f plist`.
+//!
+//! A single long-lived powermetrics process writes one plist document per
+//! sample, each terminated by a NUL byte. The GPU backend's background thread
+//! reads documents off the pipe as they arrive and parses them here, so the
+//! parser itself runs (and is tested) on any platform.
+
+use crate::gpu::GpuActivity;
+use crate::plist::{self, Value};
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+use std::process::{Child, Command, Stdio};
+
+/// Sample interval handed to powermetrics. Matches the collector tick.
+const SAMPLE_INTERVAL_MS: u32 = 2000;
+
+/// One CPU cluster (E or P) from the `cpu_power` sampler.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CpuCluster {
+    pub name: String,
+    pub frequency_mhz: f32,
+    /// Share of the interval the cluster was not idle, 0-100%.
+    pub active_residency: f32,
+}
+
+/// SoC-wide readings from the `cpu_power` sampler. Power is in milliwatts.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SocPower {
+    pub clusters: Vec<CpuCluster>,
+    pub cpu_power_mw: Option<f32>,
+    pub gpu_power_mw: Option<f32>,
+    pub ane_power_mw: Option<f32>,
+    /// CPU + GPU + ANE ("combined power").
+    pub package_power_mw: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PowermetricsSample {
+    pub gpu: Option<GpuActivity>,
+    pub soc: SocPower,
+    /// pid -> GPU% from the tasks sampler, when it ran.
+    pub tasks: Option<HashMap<u32, f32>>,
+    /// pid -> energy impact per second from the tasks sampler.
+    pub energy: Option<HashMap<u32, f32>>,
+}
+
+/// Start powermetrics streaming plist samples on stdout (requires root).
+pub fn spawn() -> io::Result<Child> {
+    Command::new("powermetrics")
+        .args([
+            "--samplers",
+            "cpu_power,gpu_power,tasks",
+            "--show-process-gpu",
+            "-f",
+            "plist",
+            "-i",
+            &SAMPLE_INTERVAL_MS.to_string(),
+        ])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+}
+
+/// Iterator over samples read from a NUL-separated plist stream. Malformed
+/// documents are skipped; iteration ends when the stream closes.
+pub struct SampleStream<R> {
+    reader: R,
+    buf: Vec<u8>,
+}
+
+impl<R: BufRead> SampleStream<R> {
+    pub fn new(reader: R) -> Self {
+        SampleStream {
+            reader,
+            buf: Vec::with_capacity(64 * 1024),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for SampleStream<R> {
+    type Item = PowermetricsSample;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.buf.clear();
+            match self.reader.read_until(b'\0', &mut self.buf) {
+                Ok(0) | Err(_) => return None,
+                Ok(_) => {}
+            }
+            let doc = String::from_utf8_lossy(&self.buf);
+            let doc = doc.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+            if doc.is_empty() {
+                continue;
+            }
+            if let Some(sample) = parse_sample(doc) {
+                return Some(sample);
+            }
+        }
+    }
+}
+
+/// Parse one powermetrics plist document.
+pub fn parse_sample(xml: &str) -> Option<PowermetricsSample> {
+    let root = plist::parse(xml)?;
+    // Energy counters are millijoules over the sample interval.
+    let elapsed_s = root
+        .get("elapsed_ns")
+        .and_then(Value::as_f64)
+        .map(|ns| ns / 1e9)
+        .filter(|s| *s > 0.0);
+    let processor = root.get("processor");
+
+    // Newer macOS reports power directly; older releases only give energy.
+    let power = |power_key: &str, energy_key: &str, section: Option<&Value>| -> Option<f32> {
+        processor
+            .and_then(|p| p.get(power_key))
+            .and_then(Value::as_f64)
+            .or_else(|| {
+                let energy = section?.get(energy_key)?.as_f64()?;
+                Some(energy / elapsed_s?)
+            })
+            .map(|mw| mw as f32)
+    };
+
+    let soc = SocPower {
+        clusters: processor
+            .and_then(|p| p.get("clusters"))
+            .and_then(Value::as_array)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|cluster| {
+                Some(CpuCluster {
+                    name: cluster.get("name")?.as_str()?.to_string(),
+                    frequency_mhz: (cluster.get("freq_hz")?.as_f64()? / 1e6) as f32,
+                    active_residency: idle_to_active(cluster.get("idle_ratio")?)?,
+                })
+            })
+            .collect(),
+        cpu_power_mw: power("cpu_power", "cpu_energy", processor),
+        gpu_power_mw: power("gpu_power", "gpu_energy", processor),
+        ane_power_mw: power("ane_power", "ane_energy", processor),
+        package_power_mw: power("combined_power", "combined_energy", processor),
+    };
+
+    let gpu = root.get("gpu").map(|gpu| GpuActivity {
+        // Unlike the CPU clusters, the GPU's `freq_hz` is already in MHz.
+        frequency_mhz: gpu.get("freq_hz").and_then(Value::as_f64).map(|f| f as f32),
+        power_mw: soc
+            .gpu_power_mw
+            .or_else(|| power("gpu_power", "gpu_energy", Some(gpu))),
+        active_residency: gpu.get("idle_ratio").and_then(idle_to_active),
+        idle_residency: gpu
+            .get("idle_ratio")
+            .and_then(Value::as_f64)
+            .map(|r| (r * 100.0) as f32),
+        frequency_residency: gpu
+            .get("dvfm_states")
+            .and_then(Value::as_array)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|state| {
+                Some((
+                    state.get("freq")?.as_f64()? as u32,
+                    (state.get("used_ratio")?.as_f64()? * 100.0) as f32,
+                ))
+            })
+            .collect(),
+    });
+
+    let tasks = root.get("tasks").and_then(Value::as_array).map(|tasks| {
+        tasks
+            .iter()
+            .filter_map(|task| {
+                // Aggregate rows carry negative pids
+                let pid = u32::try_from(task.get("pid")?.as_i64()?).ok()?;
+                let gpu_ms = task.get("gputime_ms_per_s")?.as_f64()?;
+                // 1000 ms of GPU time per second is one fully busy GPU
+                Some((pid, (gpu_ms as f32 / 10.0).clamp(0.0, 100.0)))
+            })
+            .collect()
+    });
+
+    let energy = root.get("tasks").and_then(Value::as_array).map(|tasks| {
+        tasks
+            .iter()
+            .filter_map(|task| {
+                let pid = u32::try_from(task.get("pid")?.as_i64()?).ok()?;
+                // Older releases only report the per-interval figure
+                let impact = task
+                    .get("energy_impact_per_s")
+                    .or_else(|| task.get("energy_impact"))?
+                    .as_f64()?;
+                Some((pid, impact as f32))
+            })
+            .collect()
+    });
+
+    Some(PowermetricsSample {
+        gpu,
+        soc,
+        tasks,
+        energy,
+    })
+}
+
+fn idle_to_active(idle_ratio: &Value) -> Option<f32> {
+    Some(((1.0 - idle_ratio.as_f64()?) * 100.0).clamp(0.0, 100.0) as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Trimmed `powermetrics --samplers cpu_power,gpu_power,tasks
+    /// --show-process-gpu -f plist` sample from an M1 Pro on macOS 14.
+    const SAMPLE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>is_delta</key><true/>
+	<key>elapsed_ns</key><integer>2004811000</integer>
+	<key>hw_model</key><string>MacBookPro18,3</string>
+	<key>timestamp</key><date>2026-10-18T00:00:00Z</date>
+	<key>tasks</key>
+	<array>
+		<dict>
+			<key>pid</key><integer>407</integer>
+			<key>name</key><string>WindowServer</string>
+			<key>cputime_ms_per_s</key><real>48.52</real>
+			<key>gputime_ms_per_s</key><real>213.7</real>
+			<key>energy_impact_per_s</key><real>35.2</real>
+		</dict>
+		<dict>
+			<key>pid</key><integer>0</integer>
+			<key>name</key><string>kernel_task</string>
+			<key>cputime_ms_per_s</key><real>37.97</real>
+			<key>gputime_ms_per_s</key><real>0</real>
+		</dict>
+		<dict>
+			<key>pid</key><integer>-2</integer>
+			<key>name</key><string>ALL_TASKS</string>
+			<key>gputime_ms_per_s</key><real>213.7</real>
+		</dict>
+	</array>
+	<key>processor</key>
+	<dict>
+		<key>clusters</key>
+		<array>
+			<dict>
+				<key>name</key><string>E-Cluster</string>
+				<key>freq_hz</key><real>1020000000</real>
+				<key>idle_ratio</key><real>0.75</real>
+				<key>cpus</key><array/>
+			</dict>
+			<dict>
+				<key>name</key><string>P0-Cluster</string>
+				<key>freq_hz</key><real>3228000000</real>
+				<key>idle_ratio</key><real>0.9</real>
+				<key>cpus</key><array/>
+			</dict>
+		</array>
+		<key>cpu_energy</key><integer>1200</integer>
+		<key>cpu_power</key><real>600.5</real>
+		<key>gpu_power</key><real>27</real>
+		<key>ane_power</key><real>0</real>
+		<key>combined_power</key><real>627.5</real>
+	</dict>
+	<key>gpu</key>
+	<dict>
+		<key>freq_hz</key><real>444</real>
+		<key>idle_ratio</key><real>0.8947</real>
+		<key>dvfm_states</key>
+		<array>
+			<dict><key>freq</key><integer>389</integer><key>used_ns</key><integer>118000000</integer><key>used_ratio</key><real>0.059</real></dict>
+			<dict><key>freq</key><integer>486</integer><key>used_ns</key><integer>24000000</integer><key>used_ratio</key><real>0.012</real></dict>
+		</array>
+		<key>gpu_energy</key><integer>54</integer>
+	</dict>
+</dict>
+</plist>
+"#;
+
+    /// Older releases report only energy (mJ per interval), not power.
+    const ENERGY_ONLY: &str = r#"<plist version="1.0"><dict>
+	<key>elapsed_ns</key><integer>2000000000</integer>
+	<key>processor</key><dict>
+		<key>cpu_energy</key><integer>1200</integer>
+		<key>ane_energy</key><integer>10</integer>
+	</dict>
+	<key>gpu</key><dict><key>gpu_energy</key><integer>54</integer></dict>
+</dict></plist>"#;
+
+    #[test]
+    fn test_parse_sample_gpu_and_soc() {
+        let sample = parse_sample(SAMPLE).expect("sample");
+
+        let gpu = sample.gpu.expect("gpu section");
+        assert_eq!(gpu.frequency_mhz, Some(444.0));
+        assert_eq!(gpu.power_mw, Some(27.0));
+        assert!((gpu.active_residency.unwrap() - 10.53).abs() < 0.01);
+        assert!((gpu.idle_residency.unwrap() - 89.47).abs() < 0.01);
+        assert_eq!(gpu.frequency_residency.len(), 2);
+        assert_eq!(gpu.frequency_residency[0].0, 389);
+        assert!((gpu.frequency_residency[0].1 - 5.9).abs() < 0.01);
+
+        let soc = sample.soc;
+        assert_eq!(soc.clusters.len(), 2);
+        assert_eq!(soc.clusters[0].name, "E-Cluster");
+        assert_eq!(soc.clusters[0].frequency_mhz, 1020.0);
+        assert_eq!(soc.clusters[0].active_residency, 25.0);
+        assert_eq!(soc.clusters[1].frequency_mhz, 3228.0);
+        assert_eq!(soc.cpu_power_mw, Some(600.5));
+        assert_eq!(soc.ane_power_mw, Some(0.0));
+        assert_eq!(soc.package_power_mw, Some(627.5));
+
+        let tasks = sample.tasks.expect("tasks");
+        assert_eq!(tasks.len(), 2);
+        assert!((tasks[&407] - 21.37).abs() < 0.01);
+        assert_eq!(tasks[&0], 0.0);
+
+        // kernel_task carries no energy figure
+        let energy = sample.energy.expect("energy");
+        assert_eq!(energy.len(), 1);
+        assert_eq!(energy[&407], 35.2);
+    }
+
+    #[test]
+    fn test_parse_sample_energy_fallback() {
+        let sample = parse_sample(ENERGY_ONLY).expect("sample");
+        assert_eq!(sample.soc.cpu_power_mw, Some(600.0));
+        assert_eq!(sample.soc.ane_power_mw, Some(5.0));
+        assert_eq!(sample.soc.package_power_mw, None);
+        assert_eq!(sample.gpu.unwrap().power_mw, Some(27.0));
+        assert!(sample.tasks.is_none());
+    }
+
+    #[test]
+    fn test_stream_splits_on_nul_and_skips_garbage() {
+        let stream = format!("{}\0garbage<plist>\0\n{}\0", SAMPLE, ENERGY_ONLY);
+        let samples: Vec<_> = SampleStream::new(stream.as_bytes()).collect();
+        assert_eq!(samples.len(), 2);
+        assert!(samples[0].tasks.is_some());
+        assert!(samples[1].tasks.is_none());
+
+        // A partial trailing document (process killed mid-write) is dropped
+        let truncated = &SAMPLE[..SAMPLE.len() / 2];
+        assert_eq!(SampleStream::new(truncated.as_bytes()).count(), 0);
+    }
+}
@@ -1,9 +1,13 @@
+use crate::cgroup::{CgroupResolver, ProcessCgroup, Workload};
+use crate::process_metrics::{NoProcessMetric, ProcessMetricSource};
 use std::collections::HashMap;
+#[cfg(target_os = "macos")]
 use std::ffi::CStr;
 #[cfg(feature = "profile")]
 use std::fs::OpenOptions;
 #[cfg(feature = "profile")]
 use std::io::Write as IoWrite;
+#[cfg(target_os = "macos")]
 use std::mem;
 use std::process::Command;
 #[cfg(feature = "profile")]
@@ -54,30 +58,185 @@ pub struct ProcessInfo {
     pub cmd: String,
     pub user: String,
     pub cpu_usage: f32,
-    pub gpu_usage: f32,
+    /// Measured GPU%, or `None` when no per-process GPU data is available.
+    pub gpu_usage: Option<f32>,
+    /// Energy impact per second (macOS powermetrics), where the OS reports it.
+    pub energy_impact: Option<f32>,
     pub memory: u64,
     pub ports: Vec<PortInfo>,
     pub cwd: Option<String>,
     pub exe: Option<String>,
     pub run_time: u64,
     pub thread_count: u32,
+    /// Proportional and unique set sizes, collected only while the MEM
+    /// column shows them.
+    pub pss: Option<u64>,
+    pub uss: Option<u64>,
+    /// Cgroup and the service or container it names (Linux only).
+    pub cgroup: Option<ProcessCgroup>,
 }
 
-#[derive(Debug, Clone)]
+impl ProcessInfo {
+    /// The figure the MEM column shows in `column` mode, if measured.
+    pub fn memory_for(&self, column: MemoryColumn) -> Option<u64> {
+        match column {
+            MemoryColumn::Resident => Some(self.memory),
+            MemoryColumn::Proportional => self.pss,
+            MemoryColumn::Unique => self.uss,
+        }
+    }
+
+    /// The service, container or pod the process belongs to, if any.
+    pub fn workload(&self) -> Option<&Workload> {
+        self.cgroup.as_ref()?.workload.as_ref()
+    }
+}
+
+/// What the MEM column shows. RSS counts a shared library in every process
+/// that maps it, so a browser's helpers add up to more than the machine has;
+/// PSS splits shared pages between their users and USS leaves them out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MemoryColumn {
+    #[default]
+    Resident,
+    Proportional,
+    Unique,
+}
+
+/// Whether this platform measures PSS and USS (Linux `smaps_rollup`).
+pub const HAS_SMAPS_ROLLUP: bool = cfg!(target_os = "linux");
+
+impl MemoryColumn {
+    /// The next mode, skipping PSS and USS when `smaps_rollup` is false
+    /// since they would never be measured.
+    pub fn next(self, smaps_rollup: bool) -> Self {
+        match self {
+            MemoryColumn::Resident if smaps_rollup => MemoryColumn::Proportional,
+            MemoryColumn::Proportional => MemoryColumn::Unique,
+            _ => MemoryColumn::Resident,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MemoryColumn::Resident => "MEM",
+            MemoryColumn::Proportional => "PSS",
+            MemoryColumn::Unique => "USS",
+        }
+    }
+}
+
+/// Memory breakdown for one process, in bytes. Linux fills every field it
+/// can read (PSS and shared/private need `smaps_rollup`, which is root-only
+/// for other users' processes); macOS reports resident and virtual size.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ProcessMemory {
+    pub resident: Option<u64>,
+    pub virtual_size: Option<u64>,
+    pub shared: Option<u64>,
+    /// Pages no other process maps, i.e. the unique set size (USS).
+    pub private: Option<u64>,
+    pub swap: Option<u64>,
+    pub pss: Option<u64>,
+}
+
+/// Parse `/proc/<pid>/status` and `/proc/<pid>/smaps_rollup`, which share
+/// the `Key:   1234 kB` format. Either or both may be passed in `text`.
+pub fn parse_proc_memory(text: &str) -> ProcessMemory {
+    let mut memory = ProcessMemory::default();
+    for line in text.lines() {
+        let Some((key, rest)) = line.split_once(':') else {
+            continue;
+        };
+        let Some(bytes) = rest
+            .trim()
+            .strip_suffix("kB")
+            .and_then(|kb| kb.trim().parse::<u64>().ok())
+            .map(|kb| kb * 1024)
+        else {
+            continue;
+        };
+        match key {
+            "VmSize" => memory.virtual_size = Some(bytes),
+            "Rss" | "VmRSS" => memory.resident = Some(bytes),
+            "Swap" | "VmSwap" => memory.swap = Some(bytes),
+            "Pss" => memory.pss = Some(bytes),
+            "Shared_Clean" | "Shared_Dirty" => {
+                *memory.shared.get_or_insert(0) += bytes;
+            }
+            "Private_Clean" | "Private_Dirty" => {
+                *memory.private.get_or_insert(0) += bytes;
+            }
+            _ => {}
+        }
+    }
+    memory
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct ProcessDetails {
-    pub pid: u32,
     pub fd_count: Option<u32>,
     pub thread_count_macos: Option<u32>,
+    pub memory: Option<ProcessMemory>,
 }
 
+/// Gather the breakout details of `pid`.
 pub fn fetch_process_details(pid: u32) -> ProcessDetails {
-    let fd_count = fetch_fd_count(pid);
-    let thread_count_macos = fetch_thread_count_macos(pid);
     ProcessDetails {
-        pid,
-        fd_count,
-        thread_count_macos,
+        fd_count: fetch_fd_count(pid),
+        thread_count_macos: fetch_thread_count_macos(pid),
+        memory: fetch_process_memory(pid),
+    }
+}
+
+fn fetch_process_memory(pid: u32) -> Option<ProcessMemory> {
+    proc_memory_text(pid)
+        .map(|text| parse_proc_memory(&text))
+        .or_else(|| fetch_task_memory_macos(pid))
+}
+
+#[cfg(target_os = "linux")]
+fn proc_memory_text(pid: u32) -> Option<String> {
+    let mut text = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    // smaps_rollup is unreadable for other users' processes without root;
+    // status alone still gives resident, virtual and swap.
+    if let Ok(smaps) = std::fs::read_to_string(format!("/proc/{}/smaps_rollup", pid)) {
+        text.push_str(&smaps);
+    }
+    Some(text)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn proc_memory_text(_pid: u32) -> Option<String> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn fetch_task_memory_macos(pid: u32) -> Option<ProcessMemory> {
+    let mut info: libc::proc_taskinfo = unsafe { mem::zeroed() };
+    let size = mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+    let written = unsafe {
+        libc::proc_pidinfo(
+            pid as libc::c_int,
+            libc::PROC_PIDTASKINFO,
+            0,
+            &mut info as *mut _ as *mut libc::c_void,
+            size,
+        )
+    };
+    if written != size {
+        return None;
     }
+    Some(ProcessMemory {
+        resident: Some(info.pti_resident_size),
+        virtual_size: Some(info.pti_virtual_size),
+        ..Default::default()
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+fn fetch_task_memory_macos(_pid: u32) -> Option<ProcessMemory> {
+    None
 }
 
 #[cfg(target_os = "linux")]
@@ -282,12 +441,22 @@ fn extract_port(addr: &str) -> Option<u16> {
 
 #[derive(Debug)]
 pub struct ProcessMonitor {
+    gpu_source: Box<dyn ProcessMetricSource>,
+    /// Latest per-process GPU sample, refreshed alongside full process refreshes.
+    gpu_usage: Option<HashMap<u32, f32>>,
+    energy_source: Box<dyn ProcessMetricSource>,
+    energy_impact: Option<HashMap<u32, f32>>,
     system: System,
     users: Users,
     processes: Vec<ProcessInfo>,
     sort_mode: SortMode,
+    memory_column: MemoryColumn,
     /// Cache UID -> username mappings to avoid repeated FFI calls
     uid_cache: HashMap<u32, String>,
+    /// Port map from the last lsof run. Full refreshes that skip lsof reuse it
+    /// so listening ports don't blink out between port refreshes.
+    port_cache: HashMap<u32, Vec<PortInfo>>,
+    cgroups: CgroupResolver,
 }
 
 impl ProcessMonitor {
@@ -309,26 +478,32 @@ impl ProcessMonitor {
         let users = Users::new_with_refreshed_list();
 
         ProcessMonitor {
+            gpu_source: Box::new(NoProcessMetric),
+            gpu_usage: None,
+            energy_source: Box::new(NoProcessMetric),
+            energy_impact: None,
             system,
             users,
             processes: Vec::new(),
             sort_mode: SortMode::Cpu,
+            memory_column: MemoryColumn::Resident,
             uid_cache: HashMap::new(),
+            port_cache: HashMap::new(),
+            cgroups: CgroupResolver::system(),
         }
     }
 
-    /// Heuristic GPU usage estimate from process name + CPU. Kept private so the
-    /// cheap cpu-only refresh path and the full rebuild path stay in sync.
-    fn estimate_gpu_usage(name: &str, cpu: f32) -> f32 {
-        if name.contains("Renderer") || name.contains("GPU") {
-            (cpu * 0.3).min(5.0)
-        } else if name.contains("WindowServer") || name.contains("loginwindow") {
-            2.0 + (cpu * 0.2).min(3.0)
-        } else if name.contains("VTDecoder") || name.contains("VideoToolbox") {
-            10.0 + (cpu * 0.5).min(20.0)
-        } else {
-            0.0
-        }
+    /// Replace the per-process GPU source, normally with the one published by
+    /// the active GPU backend so both share a single sampler.
+    pub fn set_gpu_source(&mut self, source: Box<dyn ProcessMetricSource>) {
+        self.gpu_source = source;
+        self.gpu_usage = None;
+    }
+
+    /// Replace the per-process energy source.
+    pub fn set_energy_source(&mut self, source: Box<dyn ProcessMetricSource>) {
+        self.energy_source = source;
+        self.energy_impact = None;
     }
 
     /// Refresh process information.
@@ -363,12 +538,23 @@ impl ProcessMonitor {
             sysinfo_start.elapsed().as_millis(),
         );
 
+        // Per-process GPU is sampled on full refreshes only; cheap ticks keep
+        // the last measured value.
+        if full_refresh {
+            self.gpu_usage = self.gpu_source.sample();
+            self.energy_impact = self.energy_source.sample();
+        }
+
         // Get port information for all processes (expensive operation - only when requested)
-        let port_map = if include_ports {
-            get_process_ports()
-        } else {
-            HashMap::new()
-        };
+        if include_ports {
+            self.port_cache = get_process_ports();
+        }
+
+        if full_refresh {
+            let system = &self.system;
+            self.cgroups
+                .begin_refresh(|pid| system.process(sysinfo::Pid::from_u32(pid)).is_some());
+        }
 
         // On cpu-only refreshes we can reuse the previously-built ProcessInfo
         // for each pid and just mutate its CPU/GPU fields. This skips the
@@ -395,8 +581,6 @@ impl ProcessMonitor {
                 // build below so they get a complete record.
                 if !full_refresh && let Some(mut existing) = prev.remove(&process_pid) {
                     existing.cpu_usage = process.cpu_usage();
-                    existing.gpu_usage =
-                        Self::estimate_gpu_usage(&existing.name, existing.cpu_usage);
                     return existing;
                 }
 
@@ -440,14 +624,31 @@ impl ProcessMonitor {
                     "unknown".to_string()
                 };
 
-                let gpu_usage = Self::estimate_gpu_usage(&name, process.cpu_usage());
-
-                let ports = port_map.get(&process_pid).cloned().unwrap_or_default();
+                let gpu_usage = self
+                    .gpu_usage
+                    .as_ref()
+                    .and_then(|usage| usage.get(&process_pid).copied());
+                let energy_impact = self
+                    .energy_impact
+                    .as_ref()
+                    .and_then(|impact| impact.get(&process_pid).copied());
+
+                let ports = self
+                    .port_cache
+                    .get(&process_pid)
+                    .cloned()
+                    .unwrap_or_default();
 
                 let cwd = process.cwd().map(|p| p.to_string_lossy().into_owned());
                 let exe = process.exe().map(|p| p.to_string_lossy().into_owned());
                 let run_time = process.run_time();
                 let thread_count = process.tasks().map(|t| t.len() as u32).unwrap_or(0);
+                // PSS/USS walk every mapping, so only pay for them on demand
+                let detail = if full_refresh && self.memory_column != MemoryColumn::Resident {
+                    fetch_process_memory(process_pid)
+                } else {
+                    None
+                };
 
                 ProcessInfo {
                     pid: process_pid,
@@ -456,12 +657,16 @@ impl ProcessMonitor {
                     user,
                     cpu_usage: process.cpu_usage(),
                     gpu_usage,
+                    energy_impact,
                     memory: process.memory(),
                     ports,
                     cwd,
                     exe,
                     run_time,
                     thread_count,
+                    pss: detail.and_then(|m| m.pss),
+                    uss: detail.and_then(|m| m.private),
+                    cgroup: self.cgroups.resolve(process_pid, process.start_time()),
                 }
             })
             .collect();
@@ -480,7 +685,9 @@ impl ProcessMonitor {
                     .sort_by(|a, b| b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap());
             }
             SortMode::Memory => {
-                self.processes.sort_by_key(|p| std::cmp::Reverse(p.memory));
+                let column = self.memory_column;
+                self.processes
+                    .sort_by_key(|p| std::cmp::Reverse(p.memory_for(column)));
             }
             SortMode::Name => {
                 self.processes.sort_by(|a, b| a.name.cmp(&b.name));
@@ -499,6 +706,12 @@ impl ProcessMonitor {
         self.sort_mode = self.sort_mode.next();
         self.sort_processes();
     }
+
+    /// Switch the MEM column. PSS/USS are filled in on the next full refresh.
+    pub fn set_memory_column(&mut self, column: MemoryColumn) {
+        self.memory_column = column;
+        self.sort_processes();
+    }
 }
 
 impl Default for ProcessMonitor {
@@ -538,3 +751,55 @@ fn get_username_from_uid(uid: u32) -> Option<String> {
 fn get_username_from_uid(_uid: u32) -> Option<String> {
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_memory() {
+        let status = "Name:\tchrome\nVmSize:\t 4200000 kB\nVmRSS:\t  300000 kB\nVmSwap:\t    2048 kB\nThreads:\t24\n";
+        let smaps = "55d0c0000000-7ffd00000000 ---p 00000000 00:00 0    [rollup]\n\
+                     Rss:              301000 kB\n\
+                     Pss:              120500 kB\n\
+                     Pss_Anon:          90000 kB\n\
+                     Shared_Clean:     150000 kB\n\
+                     Shared_Dirty:      30000 kB\n\
+                     Private_Clean:      1000 kB\n\
+                     Private_Dirty:    120000 kB\n\
+                     Swap:               2048 kB\n\
+                     SwapPss:            1024 kB\n";
+        let memory = parse_proc_memory(&format!("{}{}", status, smaps));
+        assert_eq!(memory.virtual_size, Some(4_200_000 * 1024));
+        assert_eq!(memory.resident, Some(301_000 * 1024), "smaps Rss wins");
+        assert_eq!(memory.pss, Some(120_500 * 1024));
+        assert_eq!(memory.shared, Some(180_000 * 1024));
+        assert_eq!(memory.private, Some(121_000 * 1024));
+        assert_eq!(memory.swap, Some(2048 * 1024));
+
+        // Without smaps_rollup (another user's process) only status fields
+        let memory = parse_proc_memory(status);
+        assert_eq!(memory.resident, Some(300_000 * 1024));
+        assert_eq!(memory.pss, None);
+        assert_eq!(memory.private, None);
+    }
+
+    #[test]
+    fn test_memory_column_selects_figure() {
+        let mut info = crate::source::fixture::process(1, "chrome", 0.0, 300);
+        info.pss = Some(120 * 1024 * 1024);
+        assert_eq!(info.memory_for(MemoryColumn::Resident), Some(info.memory));
+        assert_eq!(
+            info.memory_for(MemoryColumn::Proportional),
+            Some(120 * 1024 * 1024)
+        );
+        assert_eq!(info.memory_for(MemoryColumn::Unique), None);
+        assert_eq!(
+            MemoryColumn::Resident.next(true),
+            MemoryColumn::Proportional
+        );
+        assert_eq!(MemoryColumn::Unique.next(true), MemoryColumn::Resident);
+        assert_eq!(MemoryColumn::Resident.next(false), MemoryColumn::Resident);
+        assert_eq!(MemoryColumn::Proportional.label(), "PSS");
+    }
+}
@@ -0,0 +1,226 @@
+//! Per-process metric sources (GPU utilisation, energy impact).
+//!
+//! A source reports a measured value for the pids it can see. Pids it cannot
+//! measure are simply absent from the map and render as "n/a"; nothing here
+//! estimates usage from process names or CPU load.
+
+#[cfg(target_os = "linux")]
+use crate::drm::{DrmClient, busiest_engine_percent, parse_drm_fdinfo};
+use std::collections::HashMap;
+#[cfg(target_os = "linux")]
+use std::ffi::OsString;
+#[cfg(target_os = "linux")]
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+#[cfg(target_os = "linux")]
+use std::time::{Duration, Instant};
+
+pub trait ProcessMetricSource: Send + std::fmt::Debug {
+    /// Sample the metric for every visible pid. Returns `None` when the
+    /// source has no data at all (no GPU, no permission, sampler paused).
+    fn sample(&mut self) -> Option<HashMap<u32, f32>>;
+}
+
+/// Per-process sample published by a backend that already runs its own
+/// sampler (the powermetrics stream, DRM fdinfo), so processes don't sample twice.
+#[derive(Debug, Clone)]
+pub struct SharedProcessMetric {
+    sample: Arc<Mutex<Option<HashMap<u32, f32>>>>,
+    active: Arc<AtomicBool>,
+}
+
+impl SharedProcessMetric {
+    pub fn new() -> Self {
+        SharedProcessMetric {
+            sample: Arc::new(Mutex::new(None)),
+            active: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    pub fn publish(&self, sample: Option<HashMap<u32, f32>>) {
+        if let Ok(mut current) = self.sample.lock() {
+            *current = sample;
+        }
+    }
+
+    pub fn set_active(&self, active: bool) {
+        self.active.store(active, Ordering::Relaxed);
+    }
+}
+
+impl Default for SharedProcessMetric {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcessMetricSource for SharedProcessMetric {
+    fn sample(&mut self) -> Option<HashMap<u32, f32>> {
+        // A paused sampler has no current data; report that rather than stale values.
+        if !self.active.load(Ordering::Relaxed) {
+            return None;
+        }
+        self.sample.lock().ok()?.clone()
+    }
+}
+
+/// Source for machines without any per-process accounting of a metric.
+#[derive(Debug, Default)]
+pub struct NoProcessMetric;
+
+impl ProcessMetricSource for NoProcessMetric {
+    fn sample(&mut self) -> Option<HashMap<u32, f32>> {
+        None
+    }
+}
+
+/// How often the full `/proc/*/fd` walk looks for new DRM descriptors.
+/// Between walks only the fdinfo files already found are re-read.
+#[cfg(target_os = "linux")]
+const FD_RESCAN_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Linux source reading DRM engine counters from `/proc/<pid>/fdinfo`.
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+pub struct FdinfoGpuSource {
+    proc_root: PathBuf,
+    /// PCI address of the monitored card; clients of other devices are ignored.
+    pdev: Option<String>,
+    /// DRM descriptors per readable pid, from the last fd walk.
+    drm_fds: HashMap<u32, Vec<OsString>>,
+    last_scan: Option<Instant>,
+    previous: HashMap<u32, Vec<DrmClient>>,
+    last_sample: Option<Instant>,
+}
+
+#[cfg(target_os = "linux")]
+impl FdinfoGpuSource {
+    /// Source over an alternate procfs root; fixture trees in tests.
+    pub fn with_root(proc_root: impl Into<PathBuf>) -> Self {
+        FdinfoGpuSource {
+            proc_root: proc_root.into(),
+            pdev: None,
+            drm_fds: HashMap::new(),
+            last_scan: None,
+            previous: HashMap::new(),
+            last_sample: None,
+        }
+    }
+
+    /// Only count clients whose `drm-pdev` matches `pdev`.
+    pub fn for_pdev(mut self, pdev: Option<String>) -> Self {
+        self.pdev = pdev;
+        self
+    }
+
+    /// The descriptors of `pid` pointing into `/dev/dri`. Returns `None`
+    /// when the fd table is unreadable (another user's process without root).
+    fn scan_drm_fds(proc_root: &Path, pid: u32) -> Option<Vec<OsString>> {
+        let entries = std::fs::read_dir(proc_root.join(pid.to_string()).join("fd")).ok()?;
+        Some(
+            entries
+                .flatten()
+                .filter(|entry| {
+                    std::fs::read_link(entry.path()).is_ok_and(|t| t.starts_with("/dev/dri"))
+                })
+                .map(|entry| entry.file_name())
+                .collect(),
+        )
+    }
+
+    /// Walk every pid's fd table, refreshing the descriptor cache.
+    fn rescan(&mut self) -> Option<()> {
+        let mut drm_fds = HashMap::new();
+        for entry in std::fs::read_dir(&self.proc_root).ok()?.flatten() {
+            let Some(pid) = entry
+                .file_name()
+                .to_str()
+                .and_then(|s| s.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            if let Some(fds) = Self::scan_drm_fds(&self.proc_root, pid) {
+                drm_fds.insert(pid, fds);
+            }
+        }
+        self.drm_fds = drm_fds;
+        Some(())
+    }
+
+    /// Collect the distinct DRM clients of the monitored card behind `fds`.
+    fn read_clients(&self, pid: u32, fds: &[OsString]) -> Vec<DrmClient> {
+        let fdinfo_dir = self.proc_root.join(pid.to_string()).join("fdinfo");
+        let mut clients: Vec<DrmClient> = Vec::new();
+        for fd in fds {
+            let Ok(contents) = std::fs::read_to_string(fdinfo_dir.join(fd)) else {
+                continue;
+            };
+            let Some(client) = parse_drm_fdinfo(&contents) else {
+                continue;
+            };
+            if let (Some(want), Some(pdev)) = (&self.pdev, &client.pdev)
+                && want != pdev
+            {
+                continue;
+            }
+            // Duplicated descriptors share a client id; count each client once.
+            if !clients.iter().any(|c| c.client_id == client.client_id) {
+                clients.push(client);
+            }
+        }
+        clients
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl ProcessMetricSource for FdinfoGpuSource {
+    fn sample(&mut self) -> Option<HashMap<u32, f32>> {
+        let now = Instant::now();
+        if self
+            .last_scan
+            .is_none_or(|t| now.duration_since(t) >= FD_RESCAN_INTERVAL)
+        {
+            self.rescan()?;
+            self.last_scan = Some(now);
+        }
+        let elapsed_ns = self
+            .last_sample
+            .map(|t| now.duration_since(t).as_nanos() as u64)
+            .unwrap_or(0);
+
+        let mut current: HashMap<u32, Vec<DrmClient>> = HashMap::new();
+        let mut usage: HashMap<u32, f32> = HashMap::new();
+
+        for (&pid, fds) in &self.drm_fds {
+            let clients = self.read_clients(pid, fds);
+            // A client's counters need two samples; until then the pid is n/a.
+            let percent = match self.previous.get(&pid) {
+                _ if clients.is_empty() => Some(0.0),
+                Some(prev_clients) => Some(
+                    clients
+                        .iter()
+                        .filter_map(|cur| {
+                            prev_clients
+                                .iter()
+                                .find(|p| p.client_id == cur.client_id)
+                                .map(|prev| busiest_engine_percent(prev, cur, elapsed_ns))
+                        })
+                        .sum::<f32>()
+                        .min(100.0),
+                ),
+                None => None,
+            };
+            if let Some(percent) = percent {
+                usage.insert(pid, percent);
+            }
+            if !clients.is_empty() {
+                current.insert(pid, clients);
+            }
+        }
+
+        self.previous = current;
+        self.last_sample = Some(now);
+        Some(usage)
+    }
+}
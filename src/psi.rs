@@ -0,0 +1,159 @@
+//! Linux pressure stall information (PSI).
+//!
+//! `/proc/pressure/{cpu,memory,io}` report the share of wall time in which
+//! some (or all) runnable tasks were stalled waiting on that resource,
+//! averaged over 10, 60 and 300 seconds. Unlike free-memory heuristics this
+//! ignores reclaimable page cache and measures actual contention. Kernels
+//! before 4.20, or built without `CONFIG_PSI`, have no such files.
+
+use crate::memory::MemoryPressure;
+use std::path::Path;
+
+/// Memory is contended when some task stalls this often (avg10, %).
+const MEMORY_SOME_WARN: f32 = 10.0;
+/// Critical once every task stalls together this often (avg10, %).
+const MEMORY_FULL_CRIT: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PsiLine {
+    pub avg10: f32,
+    pub avg60: f32,
+    pub avg300: f32,
+    /// Cumulative stall time in microseconds.
+    pub total_us: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Psi {
+    /// At least one task stalled.
+    pub some: PsiLine,
+    /// All non-idle tasks stalled at once. System-wide CPU reports zeros or
+    /// omits it.
+    pub full: Option<PsiLine>,
+}
+
+impl Psi {
+    /// Map memory stalls onto the traffic-light pressure levels.
+    pub fn memory_pressure(&self) -> MemoryPressure {
+        let full = self.full.map_or(0.0, |f| f.avg10);
+        if full >= MEMORY_FULL_CRIT {
+            MemoryPressure::Red
+        } else if self.some.avg10 >= MEMORY_SOME_WARN {
+            MemoryPressure::Yellow
+        } else {
+            MemoryPressure::Green
+        }
+    }
+}
+
+/// CPU, memory and IO stalls at the latest sample.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PressureStall {
+    pub cpu: Option<Psi>,
+    pub memory: Option<Psi>,
+    pub io: Option<Psi>,
+}
+
+impl PressureStall {
+    /// Read all three resources under `root` (normally `/proc/pressure`).
+    pub fn read(root: &Path) -> Self {
+        let read = |name: &str| {
+            std::fs::read_to_string(root.join(name))
+                .ok()
+                .and_then(|text| parse(&text))
+        };
+        PressureStall {
+            cpu: read("cpu"),
+            memory: read("memory"),
+            io: read("io"),
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.cpu.is_some() || self.memory.is_some() || self.io.is_some()
+    }
+}
+
+/// Parse a pressure file:
+///
+/// ```text
+/// some avg10=0.00 avg60=0.12 avg300=0.08 total=123456
+/// full avg10=0.00 avg60=0.00 avg300=0.00 total=4567
+/// ```
+pub fn parse(text: &str) -> Option<Psi> {
+    let mut some = None;
+    let mut full = None;
+    for line in text.lines() {
+        let mut fields = line.split_whitespace();
+        let kind = fields.next();
+        let mut parsed = PsiLine::default();
+        for field in fields {
+            let Some((key, value)) = field.split_once('=') else {
+                continue;
+            };
+            match key {
+                "avg10" => parsed.avg10 = value.parse().ok()?,
+                "avg60" => parsed.avg60 = value.parse().ok()?,
+                "avg300" => parsed.avg300 = value.parse().ok()?,
+                "total" => parsed.total_us = value.parse().ok()?,
+                _ => {}
+            }
+        }
+        match kind {
+            Some("some") => some = Some(parsed),
+            Some("full") => full = Some(parsed),
+            _ => {}
+        }
+    }
+    Some(Psi { some: some?, full })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::fixture::FixtureDir;
+
+    #[test]
+    fn test_parse_and_map_memory_pressure() {
+        let psi = parse(
+            "some avg10=12.50 avg60=4.00 avg300=1.10 total=987654\n\
+             full avg10=0.40 avg60=0.10 avg300=0.00 total=1234\n",
+        )
+        .unwrap();
+        assert_eq!(psi.some.avg10, 12.5);
+        assert_eq!(psi.some.avg300, 1.1);
+        assert_eq!(psi.some.total_us, 987654);
+        assert_eq!(psi.full.map(|f| f.avg10), Some(0.4));
+        assert_eq!(psi.memory_pressure(), MemoryPressure::Yellow);
+
+        let thrashing = Psi {
+            full: Some(PsiLine {
+                avg10: 25.0,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(thrashing.memory_pressure(), MemoryPressure::Red);
+        assert_eq!(Psi::default().memory_pressure(), MemoryPressure::Green);
+
+        assert!(parse("").is_none());
+        assert!(parse("some avg10=x\n").is_none());
+    }
+
+    #[test]
+    fn test_read_pressure_dir() {
+        let fx = FixtureDir::new("psi");
+        fx.write("cpu", "some avg10=3.00 avg60=2.00 avg300=1.00 total=10\n");
+        fx.write(
+            "io",
+            "some avg10=20.00 avg60=5.00 avg300=1.00 total=10\nfull avg10=15.00 avg60=4.00 avg300=1.00 total=8\n",
+        );
+        let stall = PressureStall::read(fx.path());
+        assert_eq!(stall.cpu.map(|p| p.some.avg10), Some(3.0));
+        assert!(stall.cpu.unwrap().full.is_none());
+        assert!(stall.memory.is_none());
+        assert_eq!(stall.io.and_then(|p| p.full).map(|f| f.avg10), Some(15.0));
+        assert!(stall.is_available());
+        assert!(!PressureStall::read(&fx.path().join("missing")).is_available());
+    }
+}
@@ -0,0 +1,1168 @@
+//! Remote monitoring: a headless agent and a TUI client over TCP.
+//!
+//! `oversee agent` runs only the collector and streams its `DataUpdate`s to
+//! each connected client, which sends `DataCommand`s back; the TUI on the
+//! other end is the same one that runs locally. Each client gets its own
+//! collector, so pausing or re-sorting on one laptop leaves the others alone.
+//!
+//! Messages are length-prefixed frames of a small hand-rolled binary encoding
+//! (`Wire`), little-endian throughout. A connection opens with the agent's
+//! `Hello`; clients refuse agents of another protocol version. Broker
+//! requests (breakout details, inspector views, context, signals) travel
+//! alongside the commands and are answered by the agent's machine, since a
+//! pid from the agent means nothing, or another process, on the client.
+//! There is no authentication or encryption: bind to localhost and tunnel
+//! over SSH, or keep the agent on a trusted network.
+
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::app::App;
+use crate::broker::{self, Broker, Request, Response, SignalOutcome};
+use crate::cgroup::{CgroupLimits, ProcessCgroup, Runtime, Workload};
+use crate::config::Config;
+use crate::context::{CgroupEntry, EnvVar, Namespace, ProcessContext, ResourceLimit};
+use crate::cpu::{CoreCluster, CpuBreakdown, CpuTopology};
+use crate::gpu::GpuActivity;
+use crate::host::HostInfo;
+use crate::inspect::{FdKind, FdMode, MappedFile, OpenFile, ThreadInfo};
+use crate::memory::{MemoryBreakdown, MemoryInfo, MemoryPressure};
+use crate::power::{BatteryInfo, BatteryState, PowerInfo};
+use crate::powermetrics::{CpuCluster, SocPower};
+use crate::process::{
+    ConnectionState, MemoryColumn, PortInfo, ProcessDetails, ProcessInfo, ProcessMemory, Protocol,
+};
+use crate::psi::{PressureStall, Psi, PsiLine};
+use crate::source::Sources;
+use crate::thermal::{Fan, Sensor, SensorKind, ThermalInfo};
+use crate::{DataCommand, DataUpdate};
+
+/// Port used when `--connect` or `--listen` gives only a host.
+pub const DEFAULT_PORT: u16 = 7373;
+/// Bumped whenever any encoding below changes.
+const PROTOCOL_VERSION: u16 = 3;
+const MAGIC: &[u8; 7] = b"oversee";
+/// A process list of tens of thousands of entries fits comfortably.
+const MAX_FRAME: usize = 64 << 20;
+/// How long to wait for an agent to accept before giving up on it.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long a freshly connected peer has to introduce itself.
+const HELLO_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+/// Cursor over a received frame.
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes }
+    }
+
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        if self.bytes.len() < len {
+            return Err(invalid("truncated message"));
+        }
+        let (head, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        Ok(self.take(N)?.try_into().expect("take returns N bytes"))
+    }
+}
+
+/// A value that can cross the wire.
+pub trait Wire: Sized {
+    fn encode(&self, out: &mut Vec<u8>);
+    fn decode(input: &mut Reader) -> io::Result<Self>;
+}
+
+macro_rules! wire_number {
+    ($($ty:ty),*) => {$(
+        impl Wire for $ty {
+            fn encode(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn decode(input: &mut Reader) -> io::Result<Self> {
+                Ok(<$ty>::from_le_bytes(input.array()?))
+            }
+        }
+    )*};
+}
+
+wire_number!(u8, u16, u32, u64, i32, f32, f64);
+
+impl Wire for usize {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as u64).encode(out);
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        usize::try_from(u64::decode(input)?).map_err(|_| invalid("length out of range"))
+    }
+}
+
+impl Wire for bool {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as u8).encode(out);
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        match u8::decode(input)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(invalid(format!("bad bool {}", tag))),
+        }
+    }
+}
+
+impl Wire for char {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as u32).encode(out);
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        char::from_u32(u32::decode(input)?).ok_or_else(|| invalid("bad char"))
+    }
+}
+
+impl Wire for Duration {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.as_secs().encode(out);
+        self.subsec_nanos().encode(out);
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        let secs = u64::decode(input)?;
+        let nanos = u32::decode(input)?;
+        if nanos >= 1_000_000_000 {
+            return Err(invalid("bad duration"));
+        }
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
+/// As the time since the Unix epoch; earlier times clamp to it.
+impl Wire for SystemTime {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .encode(out);
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        UNIX_EPOCH
+            .checked_add(Duration::decode(input)?)
+            .ok_or_else(|| invalid("time out of range"))
+    }
+}
+
+impl Wire for String {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.len().encode(out);
+        out.extend_from_slice(self.as_bytes());
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        let len = usize::decode(input)?;
+        String::from_utf8(input.take(len)?.to_vec()).map_err(|_| invalid("string is not UTF-8"))
+    }
+}
+
+impl<T: Wire> Wire for Vec<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.len().encode(out);
+        for item in self {
+            item.encode(out);
+        }
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        let len = usize::decode(input)?;
+        // Every element takes at least a byte; don't trust `len` further
+        if len > input.bytes.len() {
+            return Err(invalid("truncated list"));
+        }
+        (0..len).map(|_| T::decode(input)).collect()
+    }
+}
+
+impl<T: Wire> Wire for Option<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            None => out.push(0),
+            Some(value) => {
+                out.push(1);
+                value.encode(out);
+            }
+        }
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        Ok(if bool::decode(input)? {
+            Some(T::decode(input)?)
+        } else {
+            None
+        })
+    }
+}
+
+impl<T: Wire> Wire for Box<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (**self).encode(out);
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        Ok(Box::new(T::decode(input)?))
+    }
+}
+
+impl<A: Wire, B: Wire> Wire for (A, B) {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+        self.1.encode(out);
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        Ok((A::decode(input)?, B::decode(input)?))
+    }
+}
+
+/// Field by field, in declaration order. Listing every field makes a new
+/// one a compile error here rather than a silently dropped value.
+macro_rules! wire_struct {
+    ($($ty:ident { $($field:ident),* $(,)? })*) => {$(
+        impl Wire for $ty {
+            fn encode(&self, out: &mut Vec<u8>) {
+                $(self.$field.encode(out);)*
+            }
+
+            fn decode(input: &mut Reader) -> io::Result<Self> {
+                Ok($ty {
+                    $($field: Wire::decode(input)?,)*
+                })
+            }
+        }
+    )*};
+}
+
+/// Fieldless enums as their variant index.
+macro_rules! wire_enum {
+    ($($ty:ident { $($variant:ident),* $(,)? })*) => {$(
+        impl Wire for $ty {
+            fn encode(&self, out: &mut Vec<u8>) {
+                out.push(*self as u8);
+            }
+
+            fn decode(input: &mut Reader) -> io::Result<Self> {
+                let tag = u8::decode(input)?;
+                [$($ty::$variant),*]
+                    .into_iter()
+                    .nth(tag as usize)
+                    .ok_or_else(|| invalid(format!("bad {} {}", stringify!($ty), tag)))
+            }
+        }
+    )*};
+}
+
+wire_struct! {
+    CoreCluster { name, cpus }
+    CpuTopology { brand, physical_cores, logical_cores, clusters }
+    CpuBreakdown { user, nice, system, idle, iowait, irq, softirq, steal }
+    GpuActivity { frequency_mhz, power_mw, active_residency, idle_residency, frequency_residency }
+    CpuCluster { name, frequency_mhz, active_residency }
+    SocPower { clusters, cpu_power_mw, gpu_power_mw, ane_power_mw, package_power_mw }
+    MemoryBreakdown {
+        available, cached, buffers, shared, dirty, slab, wired, compressed, purgeable,
+    }
+    PsiLine { avg10, avg60, avg300, total_us }
+    Psi { some, full }
+    PressureStall { cpu, memory, io }
+    MemoryInfo {
+        total_memory, used_memory, total_swap, used_swap, pressure, pressure_percentage,
+        stall, breakdown,
+    }
+    PortInfo { port, protocol, state, local_address, remote_address }
+    CgroupLimits { memory_max, memory_current, cpu_max }
+    ProcessCgroup { path, workload, limits }
+    ProcessInfo {
+        pid, name, cmd, user, cpu_usage, gpu_usage, energy_impact, memory, ports, cwd, exe,
+        run_time, thread_count, pss, uss, cgroup,
+    }
+    BatteryInfo { charge_percent, state, rate_watts }
+    PowerInfo { package_watts, cpu_watts, gpu_watts, dram_watts, battery }
+    Sensor { label, kind, celsius, critical }
+    Fan { label, rpm }
+    ThermalInfo { sensors, fans, throttling }
+    HostInfo { name, uptime_secs, load_one }
+    ProcessMemory { resident, virtual_size, shared, private, swap, pss }
+    ProcessDetails { fd_count, thread_count_macos, memory }
+    MappedFile { name, regions, size, rss, pss, swap }
+    OpenFile { fd, kind, path, mode, offset }
+    ThreadInfo { tid, name, state, cpu_percent, user_time, system_time }
+    EnvVar { key, value, masked }
+    ResourceLimit { name, soft, hard, units }
+    CgroupEntry { hierarchy, controllers, path }
+    Namespace { kind, inode, isolated }
+    ProcessContext { environment, limits, cgroups, namespaces, start_time }
+}
+
+wire_enum! {
+    MemoryPressure { Green, Yellow, Red }
+    BatteryState { Charging, Discharging, Full, NotCharging, Unknown }
+    SensorKind { Package, Cpu, Gpu, Other }
+    Protocol { Tcp, Udp }
+    ConnectionState { Listen, Established, Other }
+    MemoryColumn { Resident, Proportional, Unique }
+    Runtime { Docker, Podman, Containerd, CriO }
+    FdKind { File, Directory, Socket, Pipe, EventFd, AnonInode, Device, Other }
+    FdMode { Read, Write, ReadWrite }
+}
+
+impl Wire for Workload {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Workload::Pod { uid } => {
+                out.push(0);
+                uid.encode(out);
+            }
+            Workload::Container { runtime, id } => {
+                out.push(1);
+                runtime.encode(out);
+                id.encode(out);
+            }
+            Workload::Unit(name) => {
+                out.push(2);
+                name.encode(out);
+            }
+        }
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        Ok(match u8::decode(input)? {
+            0 => Workload::Pod {
+                uid: Wire::decode(input)?,
+            },
+            1 => Workload::Container {
+                runtime: Wire::decode(input)?,
+                id: Wire::decode(input)?,
+            },
+            2 => Workload::Unit(Wire::decode(input)?),
+            tag => return Err(invalid(format!("bad workload {}", tag))),
+        })
+    }
+}
+
+impl Wire for DataUpdate {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            DataUpdate::Cpu {
+                core_values,
+                average_value,
+                frequencies_mhz,
+                breakdown,
+            } => {
+                out.push(0);
+                core_values.encode(out);
+                average_value.encode(out);
+                frequencies_mhz.encode(out);
+                breakdown.encode(out);
+            }
+            DataUpdate::CpuTopology { topology } => {
+                out.push(1);
+                topology.encode(out);
+            }
+            DataUpdate::Gpu {
+                core_values,
+                overall_value,
+                vram,
+                activity,
+                soc,
+            } => {
+                out.push(2);
+                core_values.encode(out);
+                overall_value.encode(out);
+                vram.encode(out);
+                activity.encode(out);
+                soc.encode(out);
+            }
+            DataUpdate::Memory {
+                usage_value,
+                pressure,
+                info,
+            } => {
+                out.push(3);
+                usage_value.encode(out);
+                pressure.encode(out);
+                info.encode(out);
+            }
+            DataUpdate::Processes { processes } => {
+                out.push(4);
+                processes.encode(out);
+            }
+            DataUpdate::Power { info } => {
+                out.push(5);
+                info.encode(out);
+            }
+            DataUpdate::Thermal { info } => {
+                out.push(6);
+                info.encode(out);
+            }
+            DataUpdate::Host { info } => {
+                out.push(7);
+                info.encode(out);
+            }
+        }
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        Ok(match u8::decode(input)? {
+            0 => DataUpdate::Cpu {
+                core_values: Wire::decode(input)?,
+                average_value: Wire::decode(input)?,
+                frequencies_mhz: Wire::decode(input)?,
+                breakdown: Wire::decode(input)?,
+            },
+            1 => DataUpdate::CpuTopology {
+                topology: Wire::decode(input)?,
+            },
+            2 => DataUpdate::Gpu {
+                core_values: Wire::decode(input)?,
+                overall_value: Wire::decode(input)?,
+                vram: Wire::decode(input)?,
+                activity: Wire::decode(input)?,
+                soc: Wire::decode(input)?,
+            },
+            3 => DataUpdate::Memory {
+                usage_value: Wire::decode(input)?,
+                pressure: Wire::decode(input)?,
+                info: Wire::decode(input)?,
+            },
+            4 => DataUpdate::Processes {
+                processes: Wire::decode(input)?,
+            },
+            5 => DataUpdate::Power {
+                info: Wire::decode(input)?,
+            },
+            6 => DataUpdate::Thermal {
+                info: Wire::decode(input)?,
+            },
+            7 => DataUpdate::Host {
+                info: Wire::decode(input)?,
+            },
+            tag => return Err(invalid(format!("bad update {}", tag))),
+        })
+    }
+}
+
+impl Wire for DataCommand {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            DataCommand::Pause => out.push(0),
+            DataCommand::Resume => out.push(1),
+            DataCommand::Stop => out.push(2),
+            DataCommand::ChangeSortMode => out.push(3),
+            DataCommand::SetMemoryColumn(column) => {
+                out.push(4);
+                column.encode(out);
+            }
+            DataCommand::SetGpuActive(active) => {
+                out.push(5);
+                active.encode(out);
+            }
+        }
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        Ok(match u8::decode(input)? {
+            0 => DataCommand::Pause,
+            1 => DataCommand::Resume,
+            2 => DataCommand::Stop,
+            3 => DataCommand::ChangeSortMode,
+            4 => DataCommand::SetMemoryColumn(Wire::decode(input)?),
+            5 => DataCommand::SetGpuActive(Wire::decode(input)?),
+            tag => return Err(invalid(format!("bad command {}", tag))),
+        })
+    }
+}
+
+impl Wire for Request {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(match self {
+            Request::Details(_) => 0,
+            Request::Maps(_) => 1,
+            Request::Files(_) => 2,
+            Request::Threads(_) => 3,
+            Request::Context(_) => 4,
+            Request::Terminate(_) => 5,
+        });
+        self.pid().encode(out);
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        let tag = u8::decode(input)?;
+        let pid = u32::decode(input)?;
+        Ok(match tag {
+            0 => Request::Details(pid),
+            1 => Request::Maps(pid),
+            2 => Request::Files(pid),
+            3 => Request::Threads(pid),
+            4 => Request::Context(pid),
+            5 => Request::Terminate(pid),
+            tag => return Err(invalid(format!("bad request {}", tag))),
+        })
+    }
+}
+
+impl Wire for SignalOutcome {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            SignalOutcome::Terminated => out.push(0),
+            SignalOutcome::Killed => out.push(1),
+            SignalOutcome::Failed(errno) => {
+                out.push(2);
+                errno.encode(out);
+            }
+        }
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        Ok(match u8::decode(input)? {
+            0 => SignalOutcome::Terminated,
+            1 => SignalOutcome::Killed,
+            2 => SignalOutcome::Failed(Wire::decode(input)?),
+            tag => return Err(invalid(format!("bad signal outcome {}", tag))),
+        })
+    }
+}
+
+impl Wire for Response {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Response::Details(details) => {
+                out.push(0);
+                details.encode(out);
+            }
+            Response::Maps(maps) => {
+                out.push(1);
+                maps.encode(out);
+            }
+            Response::Files(files) => {
+                out.push(2);
+                files.encode(out);
+            }
+            Response::Threads(threads) => {
+                out.push(3);
+                threads.encode(out);
+            }
+            Response::Context(context) => {
+                out.push(4);
+                context.encode(out);
+            }
+            Response::Terminate(outcome) => {
+                out.push(5);
+                outcome.encode(out);
+            }
+        }
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        Ok(match u8::decode(input)? {
+            0 => Response::Details(Wire::decode(input)?),
+            1 => Response::Maps(Wire::decode(input)?),
+            2 => Response::Files(Wire::decode(input)?),
+            3 => Response::Threads(Wire::decode(input)?),
+            4 => Response::Context(Wire::decode(input)?),
+            5 => Response::Terminate(Wire::decode(input)?),
+            tag => return Err(invalid(format!("bad response {}", tag))),
+        })
+    }
+}
+
+/// Every frame a client sends after connecting.
+pub enum ClientMessage {
+    Command(DataCommand),
+    /// A broker job for the agent to answer, with its id.
+    Request(u64, Request),
+}
+
+impl Wire for ClientMessage {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            ClientMessage::Command(command) => {
+                out.push(0);
+                command.encode(out);
+            }
+            ClientMessage::Request(id, request) => {
+                out.push(1);
+                id.encode(out);
+                request.encode(out);
+            }
+        }
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        Ok(match u8::decode(input)? {
+            0 => ClientMessage::Command(Wire::decode(input)?),
+            1 => ClientMessage::Request(Wire::decode(input)?, Wire::decode(input)?),
+            tag => return Err(invalid(format!("bad client message {}", tag))),
+        })
+    }
+}
+
+/// Every frame the agent sends after its `Hello`.
+pub enum AgentMessage {
+    Update(DataUpdate),
+    /// The answer to a client's `ClientMessage::Request`.
+    Answer(u64, Request, Response),
+}
+
+impl Wire for AgentMessage {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            AgentMessage::Update(update) => {
+                out.push(0);
+                update.encode(out);
+            }
+            AgentMessage::Answer(id, request, response) => {
+                out.push(1);
+                id.encode(out);
+                request.encode(out);
+                response.encode(out);
+            }
+        }
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        Ok(match u8::decode(input)? {
+            0 => AgentMessage::Update(Wire::decode(input)?),
+            1 => AgentMessage::Answer(
+                Wire::decode(input)?,
+                Wire::decode(input)?,
+                Wire::decode(input)?,
+            ),
+            tag => return Err(invalid(format!("bad agent message {}", tag))),
+        })
+    }
+}
+
+/// First frame from the agent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hello {
+    pub host: String,
+    /// The agent's GPU as (chip name, core count), when it has one.
+    pub gpu: Option<(String, usize)>,
+    /// The agent measures PSS and USS.
+    pub smaps_rollup: bool,
+}
+
+impl Wire for Hello {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(MAGIC);
+        PROTOCOL_VERSION.encode(out);
+        self.host.encode(out);
+        self.gpu.encode(out);
+        self.smaps_rollup.encode(out);
+    }
+
+    fn decode(input: &mut Reader) -> io::Result<Self> {
+        if input.take(MAGIC.len())? != MAGIC {
+            return Err(invalid("not an oversee agent"));
+        }
+        let version = u16::decode(input)?;
+        if version != PROTOCOL_VERSION {
+            return Err(invalid(format!(
+                "agent speaks protocol {}, this oversee speaks {}",
+                version, PROTOCOL_VERSION
+            )));
+        }
+        Ok(Hello {
+            host: Wire::decode(input)?,
+            gpu: Wire::decode(input)?,
+            smaps_rollup: Wire::decode(input)?,
+        })
+    }
+}
+
+/// A writer several threads send frames through.
+type SharedWriter = Arc<Mutex<BufWriter<TcpStream>>>;
+
+fn send(writer: &SharedWriter, message: &impl Wire) -> io::Result<()> {
+    let mut writer = writer
+        .lock()
+        .map_err(|_| io::Error::other("writer poisoned"))?;
+    write_frame(&mut *writer, message)
+}
+
+pub fn write_frame(writer: &mut impl Write, message: &impl Wire) -> io::Result<()> {
+    let mut payload = Vec::new();
+    message.encode(&mut payload);
+    writer.write_all(&(payload.len() as u32).to_le_bytes())?;
+    writer.write_all(&payload)?;
+    writer.flush()
+}
+
+pub fn read_frame<T: Wire>(reader: &mut impl Read) -> io::Result<T> {
+    let mut len = [0; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_FRAME {
+        return Err(invalid(format!("{} byte frame", len)));
+    }
+    let mut payload = vec![0; len];
+    reader.read_exact(&mut payload)?;
+    let mut input = Reader::new(&payload);
+    let message = T::decode(&mut input)?;
+    if !input.bytes.is_empty() {
+        return Err(invalid("trailing bytes in frame"));
+    }
+    Ok(message)
+}
+
+/// `host` or `host:port`, with [`DEFAULT_PORT`] filled in.
+pub fn with_default_port(addr: &str) -> String {
+    // A bare IPv6 address has colons but no brackets
+    if addr.starts_with('[') && addr.ends_with(']') || !addr.contains(':') {
+        format!("{}:{}", addr, DEFAULT_PORT)
+    } else if addr.matches(':').count() > 1 && !addr.starts_with('[') {
+        format!("[{}]:{}", addr, DEFAULT_PORT)
+    } else {
+        addr.to_string()
+    }
+}
+
+/// Accept clients forever, each with its own collector over `sources()`.
+pub fn serve<F>(listener: TcpListener, sources: F) -> io::Result<()>
+where
+    F: Fn() -> Sources + Send + Sync + 'static,
+{
+    let sources = Arc::new(sources);
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                eprintln!("oversee agent: accept failed: {}", err);
+                continue;
+            }
+        };
+        let sources = Arc::clone(&sources);
+        thread::spawn(move || {
+            let peer = stream
+                .peer_addr()
+                .map_or_else(|_| "client".to_string(), |a| a.to_string());
+            if let Err(err) = serve_client(stream, sources()) {
+                eprintln!("oversee agent: {}: {}", peer, err);
+            }
+        });
+    }
+    Ok(())
+}
+
+fn serve_client(stream: TcpStream, sources: Sources) -> io::Result<()> {
+    stream.set_nodelay(true)?;
+    let hello = Hello {
+        host: sources.host.host_info().name,
+        gpu: sources.gpu.describe(),
+        smaps_rollup: crate::process::HAS_SMAPS_ROLLUP,
+    };
+    let writer: SharedWriter = Arc::new(Mutex::new(BufWriter::new(stream.try_clone()?)));
+    send(&writer, &hello)?;
+
+    let (update_tx, update_rx) = mpsc::channel();
+    let (command_tx, command_rx) = mpsc::channel();
+    let collector =
+        thread::spawn(move || crate::run_data_collector(sources, update_tx, command_rx));
+
+    // Broker requests, answered about this machine's processes
+    let (request_tx, request_rx) = mpsc::channel::<(u64, Request)>();
+    let (answer_tx, answer_rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut dispatch = broker::local_dispatch(answer_tx);
+        for (id, request) in request_rx {
+            dispatch(id, request);
+        }
+    });
+    let answer_writer = Arc::clone(&writer);
+    thread::spawn(move || {
+        for (id, request, response) in answer_rx {
+            if send(&answer_writer, &AgentMessage::Answer(id, request, response)).is_err() {
+                break;
+            }
+        }
+    });
+
+    // Commands from the client; hanging up stops the collector
+    let stop_tx = command_tx.clone();
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let commands = thread::spawn(move || {
+        while let Ok(message) = read_frame::<ClientMessage>(&mut reader) {
+            let sent = match message {
+                ClientMessage::Command(command) => command_tx.send(command).is_ok(),
+                ClientMessage::Request(id, request) => request_tx.send((id, request)).is_ok(),
+            };
+            if !sent {
+                break;
+            }
+        }
+        let _ = command_tx.send(DataCommand::Stop);
+    });
+
+    // Updates until the collector stops or the client stops reading
+    let mut result = Ok(());
+    for update in update_rx {
+        if let Err(err) = send(&writer, &AgentMessage::Update(update)) {
+            result = Err(err);
+            break;
+        }
+    }
+    let _ = stop_tx.send(DataCommand::Stop);
+    let _ = stream.shutdown(Shutdown::Both);
+    let _ = collector.join();
+    let _ = commands.join();
+    match result {
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    }
+}
+
+/// A client's end of an agent connection: the same channels the TUI uses
+/// with a local collector.
+pub struct Connection {
+    pub hello: Hello,
+    pub updates: Receiver<DataUpdate>,
+    pub commands: Sender<DataCommand>,
+    /// Forwards commands; finishes once `DataCommand::Stop` is sent.
+    pub worker: JoinHandle<()>,
+    /// Set once the agent hangs up or sends something unreadable.
+    closed: Arc<AtomicBool>,
+    writer: SharedWriter,
+    /// Broker answers from the agent, until an app takes them.
+    answers: Option<Receiver<(u64, Request, Response)>>,
+}
+
+impl Connection {
+    /// The agent is gone; `updates` holds whatever it sent before.
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Relaxed)
+    }
+
+    /// The flag behind `is_closed`, for callers that give the rest of the
+    /// connection away.
+    pub fn closed_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.closed)
+    }
+
+    /// A TUI over this connection, labelled with `addr`. Its broker asks
+    /// the agent, so there is one app per connection.
+    pub fn app(&mut self, addr: &str, config: Config) -> App {
+        let mut app = App::new(self.commands.clone(), config, self.hello.gpu.clone());
+        app.remote = Some(addr.to_string());
+        app.smaps_rollup = self.hello.smaps_rollup;
+        let answers = self.answers.take().expect("one app per connection");
+        let writer = Arc::clone(&self.writer);
+        app.broker = Broker::forwarding(
+            move |id, request| {
+                let _ = send(&writer, &ClientMessage::Request(id, request));
+            },
+            answers,
+        );
+        app
+    }
+}
+
+pub fn connect(addr: impl ToSocketAddrs) -> io::Result<Connection> {
+    // A blackholed host would otherwise hold us for the OS's SYN timeout
+    let mut last_err = io::Error::new(io::ErrorKind::InvalidInput, "no addresses");
+    let mut connected = None;
+    for addr in addr.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) {
+            Ok(stream) => {
+                connected = Some(stream);
+                break;
+            }
+            Err(err) => last_err = err,
+        }
+    }
+    let stream = connected.ok_or(last_err)?;
+    stream.set_nodelay(true)?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    stream.set_read_timeout(Some(HELLO_TIMEOUT))?;
+    let hello: Hello = read_frame(&mut reader)?;
+    stream.set_read_timeout(None)?;
+
+    let (update_tx, updates) = mpsc::channel();
+    let (answer_tx, answers) = mpsc::channel();
+    let closed = Arc::new(AtomicBool::new(false));
+    let reader_closed = Arc::clone(&closed);
+    thread::spawn(move || {
+        while let Ok(message) = read_frame::<AgentMessage>(&mut reader) {
+            match message {
+                AgentMessage::Update(update) => {
+                    if update_tx.send(update).is_err() {
+                        break;
+                    }
+                }
+                // Nobody asking any more is no reason to hang up
+                AgentMessage::Answer(id, request, response) => {
+                    let _ = answer_tx.send((id, request, response));
+                }
+            }
+        }
+        reader_closed.store(true, Ordering::Relaxed);
+    });
+
+    let writer: SharedWriter = Arc::new(Mutex::new(BufWriter::new(stream.try_clone()?)));
+    let command_writer = Arc::clone(&writer);
+    let (commands, command_rx) = mpsc::channel::<DataCommand>();
+    let worker = thread::spawn(move || {
+        for command in command_rx {
+            let stop = matches!(command, DataCommand::Stop);
+            if send(&command_writer, &ClientMessage::Command(command)).is_err() || stop {
+                break;
+            }
+        }
+        let _ = stream.shutdown(Shutdown::Both);
+    });
+
+    Ok(Connection {
+        hello,
+        updates,
+        commands,
+        worker,
+        closed,
+        writer,
+        answers: Some(answers),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::fixture;
+
+    fn round_trip<T: Wire>(value: &T) -> T {
+        let mut frame = Vec::new();
+        write_frame(&mut frame, value).unwrap();
+        read_frame(&mut frame.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_wire_round_trip() {
+        let process = ProcessInfo {
+            gpu_usage: Some(12.5),
+            ports: vec![PortInfo {
+                port: 5432,
+                protocol: Protocol::Tcp,
+                state: ConnectionState::Listen,
+                local_address: Some("127.0.0.1".to_string()),
+                remote_address: None,
+            }],
+            cgroup: Some(ProcessCgroup {
+                path: "/system.slice/docker-0123456789abcdef.scope".to_string(),
+                workload: Some(Workload::Container {
+                    runtime: Runtime::Docker,
+                    id: "0123456789abcdef".to_string(),
+                }),
+                limits: CgroupLimits {
+                    memory_max: Some(1 << 30),
+                    memory_current: None,
+                    cpu_max: Some(1.5),
+                },
+            }),
+            ..fixture::process(4242, "postgres", 3.5, 512)
+        };
+        match round_trip(&DataUpdate::Processes {
+            processes: vec![process.clone()],
+        }) {
+            DataUpdate::Processes { processes } => {
+                let decoded = &processes[0];
+                assert_eq!(
+                    (decoded.pid, decoded.name.as_str(), decoded.memory),
+                    (4242, "postgres", process.memory)
+                );
+                assert_eq!(decoded.gpu_usage, Some(12.5));
+                assert_eq!(decoded.ports[0].port, 5432);
+                assert!(matches!(decoded.ports[0].state, ConnectionState::Listen));
+                assert_eq!(decoded.cgroup, process.cgroup);
+            }
+            _ => panic!("expected processes"),
+        }
+
+        let memory = fixture::memory_info(6, 16, MemoryPressure::Yellow);
+        match round_trip(&DataUpdate::Memory {
+            usage_value: 37.5,
+            pressure: MemoryPressure::Yellow,
+            info: Box::new(memory),
+        }) {
+            DataUpdate::Memory { info, pressure, .. } => {
+                assert_eq!(pressure, MemoryPressure::Yellow);
+                assert_eq!(info.used_memory, memory.used_memory);
+                assert_eq!(info.stall, memory.stall);
+                assert_eq!(info.breakdown, memory.breakdown);
+            }
+            _ => panic!("expected memory"),
+        }
+
+        assert!(matches!(
+            round_trip(&DataCommand::SetMemoryColumn(MemoryColumn::Unique)),
+            DataCommand::SetMemoryColumn(MemoryColumn::Unique)
+        ));
+
+        let context = ProcessContext {
+            environment: Some(vec![EnvVar {
+                key: "DATABASE_URL".to_string(),
+                value: String::new(),
+                masked: true,
+            }]),
+            namespaces: Some(vec![Namespace {
+                kind: "net".to_string(),
+                inode: 4026531992,
+                isolated: Some(true),
+            }]),
+            start_time: Some(UNIX_EPOCH + Duration::new(1_700_000_000, 250)),
+            ..Default::default()
+        };
+        match round_trip(&AgentMessage::Answer(
+            7,
+            Request::Context(4242),
+            Response::Context(context.clone()),
+        )) {
+            AgentMessage::Answer(7, Request::Context(4242), Response::Context(decoded)) => {
+                assert_eq!(decoded, context)
+            }
+            _ => panic!("expected a context answer"),
+        }
+        let thread = ThreadInfo {
+            tid: 4243,
+            name: "worker".to_string(),
+            state: 'S',
+            cpu_percent: None,
+            user_time: Duration::from_millis(1500),
+            system_time: Duration::ZERO,
+        };
+        match round_trip(&Response::Threads(Some(vec![thread.clone()]))) {
+            Response::Threads(Some(threads)) => assert_eq!(threads, [thread]),
+            _ => panic!("expected threads"),
+        }
+        assert_eq!(
+            round_trip(&SignalOutcome::Failed(libc::EPERM)),
+            SignalOutcome::Failed(libc::EPERM)
+        );
+        assert!(matches!(
+            round_trip(&ClientMessage::Request(3, Request::Terminate(4242))),
+            ClientMessage::Request(3, Request::Terminate(4242))
+        ));
+
+        // Truncated and foreign frames are refused rather than misread
+        let mut frame = Vec::new();
+        write_frame(&mut frame, &DataCommand::SetGpuActive(true)).unwrap();
+        frame[0] = 1;
+        frame.truncate(5);
+        assert!(read_frame::<DataCommand>(&mut frame.as_slice()).is_err());
+        let mut frame = Vec::new();
+        write_frame(&mut frame, &"HTTP/1.1".to_string()).unwrap();
+        assert!(read_frame::<Hello>(&mut frame.as_slice()).is_err());
+
+        assert_eq!(with_default_port("buildbox"), "buildbox:7373");
+        assert_eq!(with_default_port("buildbox:9000"), "buildbox:9000");
+        assert_eq!(with_default_port("::1"), "[::1]:7373");
+        assert_eq!(with_default_port("[::1]:9000"), "[::1]:9000");
+    }
+
+    #[test]
+    fn test_agent_loopback() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            serve(listener, || {
+                fixture::sources(vec![
+                    fixture::process(1, "init", 0.5, 10),
+                    fixture::process(4242, "postgres", 12.5, 512),
+                ])
+            })
+        });
+
+        let mut connection = connect(addr).unwrap();
+        assert_eq!(connection.hello.host, "fixture-host");
+        assert_eq!(connection.hello.gpu, Some(("Fixture GPU".to_string(), 4)));
+        assert_eq!(
+            connection.hello.smaps_rollup,
+            crate::process::HAS_SMAPS_ROLLUP
+        );
+
+        // Broker requests are answered by the agent's machine, which here
+        // is this one
+        let mut remote_app = connection.app("127.0.0.1", Config::default());
+        let request = Request::Threads(std::process::id());
+        remote_app.broker.request(request);
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while !remote_app.broker.poll().contains(&request) {
+            assert!(std::time::Instant::now() < deadline, "agent never answered");
+            thread::sleep(Duration::from_millis(5));
+        }
+        if cfg!(target_os = "linux") {
+            assert!(matches!(
+                remote_app.broker.get(request),
+                Some(Response::Threads(Some(threads))) if !threads.is_empty()
+            ));
+        }
+
+        let (mut app, _rx) = fixture::app();
+        let next = |connection: &Connection| {
+            connection
+                .updates
+                .recv_timeout(Duration::from_secs(5))
+                .expect("agent sends updates")
+        };
+        // A full tick arrives as it does from a local collector
+        let mut tick = Vec::new();
+        while !matches!(tick.last(), Some(DataUpdate::Host { .. })) {
+            tick.push(next(&connection));
+        }
+        assert_eq!(tick.len(), 8);
+        let (tx, rx) = mpsc::channel();
+        for update in tick {
+            tx.send(update).unwrap();
+        }
+        app.process_updates(&rx);
+        assert_eq!(app.get_all_processes()[1].name, "postgres");
+        assert_eq!(app.cpu_topology.as_ref().unwrap().brand, "Fixture CPU");
+        assert_eq!(app.host_info.as_ref().unwrap().load_one, 1.5);
+
+        // Commands reach the agent's collector
+        connection
+            .commands
+            .send(DataCommand::ChangeSortMode)
+            .unwrap();
+        loop {
+            if let DataUpdate::Processes { processes } = next(&connection) {
+                // The fixture source reverses its list on every re-sort
+                assert_eq!(processes[0].name, "postgres");
+                break;
+            }
+        }
+
+        assert!(!connection.is_closed());
+        connection.commands.send(DataCommand::Stop).unwrap();
+        // The agent hangs up once the collector stops
+        while connection
+            .updates
+            .recv_timeout(Duration::from_secs(5))
+            .is_ok()
+        {}
+        assert!(connection.is_closed());
+        connection.worker.join().unwrap();
+    }
+}
@@ -0,0 +1,301 @@
+//! systemd services reconstructed from cgroup paths.
+//!
+//! systemd runs every service in its own cgroup, so grouping processes by the
+//! unit their cgroup names gives the service list without a D-Bus connection.
+//! The main pid is taken to be the unit's longest-running process; a restart
+//! is counted whenever that pid is replaced and the old one is gone, which
+//! also catches a service that crashed and came back between two refreshes.
+//! A unit that went inactive in between simply started again, as timer and
+//! oneshot units do on every run.
+
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime};
+
+/// How long a unit stays listed after its last process exits, so transient
+/// `run-*.service` units do not pile up.
+const INACTIVE_TTL: Duration = Duration::from_secs(10 * 60);
+
+use crate::cgroup::Workload;
+use crate::process::ProcessInfo;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitState {
+    /// Has processes.
+    Active,
+    /// Seen earlier, no processes now.
+    Inactive,
+}
+
+impl UnitState {
+    pub fn label(self) -> &'static str {
+        match self {
+            UnitState::Active => "active",
+            UnitState::Inactive => "inactive",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceProcess {
+    pub pid: u32,
+    pub name: String,
+    pub cpu_usage: f32,
+    pub memory: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Service {
+    /// Unit name, e.g. `postgresql.service`.
+    pub name: String,
+    pub state: UnitState,
+    /// Current main pid; `None` while inactive.
+    pub main_pid: Option<u32>,
+    /// Main pid first, then by pid.
+    pub processes: Vec<ServiceProcess>,
+    pub cpu_usage: f32,
+    pub memory: u64,
+    /// Main pid replacements seen since oversee started.
+    pub restarts: u32,
+    pub last_restart: Option<SystemTime>,
+    /// When the unit's last process exited.
+    pub inactive_since: Option<SystemTime>,
+}
+
+#[derive(Debug, Default)]
+pub struct ServiceMonitor {
+    services: HashMap<String, Service>,
+    primed: bool,
+}
+
+impl ServiceMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Active services busiest first, then inactive ones by name.
+    pub fn services(&self) -> Vec<&Service> {
+        let mut services: Vec<&Service> = self.services.values().collect();
+        services.sort_by(|a, b| {
+            (a.state == UnitState::Inactive)
+                .cmp(&(b.state == UnitState::Inactive))
+                .then(b.cpu_usage.total_cmp(&a.cpu_usage))
+                .then(a.name.cmp(&b.name))
+        });
+        services
+    }
+
+    pub fn len(&self) -> usize {
+        self.services.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.services.is_empty()
+    }
+
+    /// Rows a scrolling list of the services needs: one per unit and one
+    /// per process beneath it.
+    pub fn line_count(&self) -> usize {
+        self.services.values().map(|s| 1 + s.processes.len()).sum()
+    }
+
+    /// Fold in a new process snapshot. The first one sets the baseline, so
+    /// services already running when oversee starts show no restarts.
+    pub fn observe(&mut self, processes: &[ProcessInfo], now: SystemTime) {
+        let mut units: HashMap<&str, Vec<&ProcessInfo>> = HashMap::new();
+        for process in processes {
+            if let Some(Workload::Unit(name)) = process.workload() {
+                units.entry(name.as_str()).or_default().push(process);
+            }
+        }
+
+        let active: HashSet<String> = units.keys().map(|name| name.to_string()).collect();
+        for (name, mut members) in units {
+            // Longest-running first; systemd forks the main process before
+            // anything else in the unit
+            members.sort_by(|a, b| b.run_time.cmp(&a.run_time).then(a.pid.cmp(&b.pid)));
+            let main_pid = members[0].pid;
+            let pids: HashSet<u32> = members.iter().map(|p| p.pid).collect();
+
+            let service = self
+                .services
+                .entry(name.to_string())
+                .or_insert_with(|| Service {
+                    name: name.to_string(),
+                    state: UnitState::Active,
+                    main_pid: None,
+                    processes: Vec::new(),
+                    cpu_usage: 0.0,
+                    memory: 0,
+                    restarts: 0,
+                    last_restart: None,
+                    inactive_since: None,
+                });
+            if self.primed
+                && let Some(previous) = service.main_pid
+                && previous != main_pid
+                && !pids.contains(&previous)
+            {
+                service.restarts += 1;
+                service.last_restart = Some(now);
+            }
+            service.state = UnitState::Active;
+            service.inactive_since = None;
+            service.main_pid = Some(main_pid);
+            service.cpu_usage = members.iter().map(|p| p.cpu_usage).sum();
+            service.memory = members.iter().map(|p| p.memory).sum();
+            let mut children: Vec<&ProcessInfo> = members[1..].to_vec();
+            children.sort_by_key(|p| p.pid);
+            service.processes = std::iter::once(members[0])
+                .chain(children)
+                .map(|p| ServiceProcess {
+                    pid: p.pid,
+                    name: p.name.clone(),
+                    cpu_usage: p.cpu_usage,
+                    memory: p.memory,
+                })
+                .collect();
+        }
+
+        // Units whose processes all exited stay listed as inactive for a while
+        for service in self.services.values_mut() {
+            if !active.contains(&service.name) && service.state == UnitState::Active {
+                service.state = UnitState::Inactive;
+                service.inactive_since = Some(now);
+                service.main_pid = None;
+                service.processes.clear();
+                service.cpu_usage = 0.0;
+                service.memory = 0;
+            }
+        }
+        self.services.retain(|_, service| {
+            service
+                .inactive_since
+                .is_none_or(|since| now.duration_since(since).unwrap_or_default() < INACTIVE_TTL)
+        });
+        self.primed = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cgroup::CgroupResolver;
+    use crate::source::fixture::{self, FixtureDir};
+
+    #[test]
+    fn test_services_from_cgroup_tree() {
+        let fx = FixtureDir::new("services");
+        let unit = |pid: u32, path: &str| {
+            fx.write(&format!("proc/{}/cgroup", pid), &format!("0::{}\n", path));
+        };
+        unit(100, "/system.slice/postgresql.service");
+        unit(101, "/system.slice/postgresql.service");
+        unit(102, "/system.slice/postgresql.service");
+        unit(200, "/system.slice/worker.service");
+        unit(201, "/system.slice/worker.service");
+        unit(300, "/user.slice/user-1000.slice/session-2.scope");
+
+        let mut resolver = CgroupResolver::new(fx.path().join("proc"), fx.path().join("sys"));
+        let mut snapshot = |procs: &[(u32, &str, f32, u64, u64)]| -> Vec<ProcessInfo> {
+            resolver.begin_refresh(|_| true);
+            procs
+                .iter()
+                .map(|&(pid, name, cpu, mem_mb, run_time)| ProcessInfo {
+                    run_time,
+                    cgroup: resolver.resolve(pid, 0),
+                    ..fixture::process(pid, name, cpu, mem_mb)
+                })
+                .collect()
+        };
+        let at = |secs| SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+
+        let mut monitor = ServiceMonitor::new();
+        monitor.observe(
+            &snapshot(&[
+                (100, "postgres", 2.0, 100, 500),
+                (102, "postgres", 1.0, 20, 400),
+                (101, "postgres", 3.0, 30, 400),
+                (200, "worker", 1.0, 50, 50),
+                (300, "bash", 9.0, 5, 900),
+            ]),
+            at(0),
+        );
+        let services = monitor.services();
+        assert_eq!(services.len(), 2, "session scopes are not services");
+        let postgres = services[0];
+        assert_eq!(postgres.name, "postgresql.service");
+        assert_eq!(postgres.main_pid, Some(100));
+        assert_eq!(
+            postgres.processes.iter().map(|p| p.pid).collect::<Vec<_>>(),
+            [100, 101, 102]
+        );
+        assert_eq!(postgres.cpu_usage, 6.0);
+        assert_eq!(postgres.memory, 150 * 1024 * 1024);
+        assert_eq!(monitor.line_count(), 2 + 4);
+
+        // The worker crashes and comes back as pid 201 between refreshes
+        monitor.observe(
+            &snapshot(&[
+                (100, "postgres", 2.0, 100, 502),
+                (201, "worker", 4.0, 50, 1),
+            ]),
+            at(2),
+        );
+        let worker = monitor.services()[0].clone();
+        assert_eq!(worker.name, "worker.service");
+        assert_eq!(worker.state, UnitState::Active);
+        assert_eq!((worker.main_pid, worker.restarts), (Some(201), 1));
+        assert_eq!(worker.last_restart, Some(at(2)));
+
+        // A child exiting is not a restart
+        monitor.observe(&snapshot(&[(201, "worker", 4.0, 50, 3)]), at(4));
+        assert_eq!(monitor.services()[0].restarts, 1);
+        assert_eq!(monitor.services()[1].restarts, 0);
+    }
+
+    #[test]
+    fn test_inactive_units_start_afresh_and_expire() {
+        let fx = FixtureDir::new("services-timer");
+        fx.write(
+            "proc/500/cgroup",
+            "0::/system.slice/logrotate.service
+",
+        );
+        fx.write(
+            "proc/501/cgroup",
+            "0::/system.slice/logrotate.service
+",
+        );
+        let mut resolver = CgroupResolver::new(fx.path().join("proc"), fx.path().join("sys"));
+        let mut snapshot = |pids: &[u32]| -> Vec<ProcessInfo> {
+            resolver.begin_refresh(|_| true);
+            pids.iter()
+                .map(|&pid| ProcessInfo {
+                    cgroup: resolver.resolve(pid, 0),
+                    ..fixture::process(pid, "logrotate", 1.0, 4)
+                })
+                .collect()
+        };
+        let at = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+
+        let mut monitor = ServiceMonitor::new();
+        monitor.observe(&snapshot(&[500]), at(0));
+        monitor.observe(&snapshot(&[]), at(2));
+        let logrotate = monitor.services()[0].clone();
+        assert_eq!(logrotate.state, UnitState::Inactive);
+        assert_eq!(logrotate.main_pid, None);
+        assert_eq!(logrotate.inactive_since, Some(at(2)));
+
+        // The next timer run is a fresh start, not a restart
+        monitor.observe(&snapshot(&[501]), at(3600));
+        let logrotate = monitor.services()[0].clone();
+        assert_eq!((logrotate.main_pid, logrotate.restarts), (Some(501), 0));
+
+        // Still listed shortly after exiting, gone once the TTL passes
+        monitor.observe(&snapshot(&[]), at(3602));
+        monitor.observe(&snapshot(&[]), at(3602 + 60));
+        assert_eq!(monitor.len(), 1);
+        monitor.observe(&snapshot(&[]), at(3602 + INACTIVE_TTL.as_secs()));
+        assert!(monitor.is_empty());
+    }
+}
@@ -0,0 +1,565 @@
+//! Metric sources driven by the background collector.
+//!
+//! The real monitors (sysinfo, sysctl, lsof, powermetrics) implement these
+//! traits; tests swap in the fixture sources at the bottom of this file so the
+//! collector loop, App state and UI rendering can run without a real machine.
+
+use crate::config::Config;
+use crate::cpu::{CoreSample, CpuBreakdown, CpuMonitor, CpuTopology};
+use crate::gpu::GpuMonitor;
+use crate::host::{HostInfo, SystemHost};
+use crate::memory::{MemoryInfo, MemoryMonitor};
+use crate::power::{PowerInfo, PowerMonitor};
+use crate::process::{MemoryColumn, ProcessInfo, ProcessMonitor};
+use crate::process_metrics::ProcessMetricSource;
+use crate::thermal::{ThermalInfo, ThermalMonitor};
+
+pub trait CpuSource: Send {
+    fn refresh(&mut self);
+    /// Usage and clock for every logical core.
+    fn cores(&self) -> Vec<CoreSample>;
+    fn topology(&self) -> CpuTopology;
+    /// Time-in-state split since the previous refresh, where the OS reports it.
+    fn breakdown(&self) -> Option<CpuBreakdown>;
+}
+
+pub trait MemorySource: Send {
+    fn refresh(&mut self);
+    fn memory_info(&self) -> MemoryInfo;
+}
+
+pub trait ProcessSource: Send {
+    /// See [`ProcessMonitor::refresh`] for the meaning of the flags.
+    fn refresh(&mut self, include_ports: bool, full_refresh: bool);
+    fn processes(&self) -> &[ProcessInfo];
+    fn next_sort_mode(&mut self);
+    fn set_memory_column(&mut self, column: MemoryColumn);
+    fn set_gpu_source(&mut self, source: Box<dyn ProcessMetricSource>);
+    fn set_energy_source(&mut self, source: Box<dyn ProcessMetricSource>);
+}
+
+pub trait PowerSource: Send {
+    fn refresh(&mut self);
+    fn power_info(&self) -> PowerInfo;
+}
+
+pub trait ThermalSource: Send {
+    fn refresh(&mut self);
+    fn thermal_info(&self) -> ThermalInfo;
+}
+
+pub trait HostSource: Send {
+    fn host_info(&self) -> HostInfo;
+}
+
+impl CpuSource for CpuMonitor {
+    fn refresh(&mut self) {
+        CpuMonitor::refresh(self)
+    }
+
+    fn cores(&self) -> Vec<CoreSample> {
+        CpuMonitor::cores(self)
+    }
+
+    fn topology(&self) -> CpuTopology {
+        CpuMonitor::topology(self).clone()
+    }
+
+    fn breakdown(&self) -> Option<CpuBreakdown> {
+        CpuMonitor::breakdown(self)
+    }
+}
+
+impl MemorySource for MemoryMonitor {
+    fn refresh(&mut self) {
+        MemoryMonitor::refresh(self)
+    }
+
+    fn memory_info(&self) -> MemoryInfo {
+        self.get_memory_info()
+    }
+}
+
+impl ProcessSource for ProcessMonitor {
+    fn refresh(&mut self, include_ports: bool, full_refresh: bool) {
+        ProcessMonitor::refresh(self, include_ports, full_refresh)
+    }
+
+    fn processes(&self) -> &[ProcessInfo] {
+        self.get_processes()
+    }
+
+    fn next_sort_mode(&mut self) {
+        ProcessMonitor::next_sort_mode(self)
+    }
+
+    fn set_memory_column(&mut self, column: MemoryColumn) {
+        ProcessMonitor::set_memory_column(self, column)
+    }
+
+    fn set_gpu_source(&mut self, source: Box<dyn ProcessMetricSource>) {
+        ProcessMonitor::set_gpu_source(self, source)
+    }
+
+    fn set_energy_source(&mut self, source: Box<dyn ProcessMetricSource>) {
+        ProcessMonitor::set_energy_source(self, source)
+    }
+}
+
+impl PowerSource for PowerMonitor {
+    fn refresh(&mut self) {
+        PowerMonitor::refresh(self)
+    }
+
+    fn power_info(&self) -> PowerInfo {
+        self.get_info()
+    }
+}
+
+impl ThermalSource for ThermalMonitor {
+    fn refresh(&mut self) {
+        ThermalMonitor::refresh(self)
+    }
+
+    fn thermal_info(&self) -> ThermalInfo {
+        self.get_info()
+    }
+}
+
+impl HostSource for SystemHost {
+    fn host_info(&self) -> HostInfo {
+        SystemHost::host_info(self)
+    }
+}
+
+/// Everything the collector samples. GPU uses the `GpuBackend` seam inside
+/// `GpuMonitor` rather than a separate trait.
+pub struct Sources {
+    pub cpu: Box<dyn CpuSource>,
+    pub gpu: GpuMonitor,
+    pub memory: Box<dyn MemorySource>,
+    pub processes: Box<dyn ProcessSource>,
+    pub power: Box<dyn PowerSource>,
+    pub thermal: Box<dyn ThermalSource>,
+    pub host: Box<dyn HostSource>,
+}
+
+impl Sources {
+    /// Sources backed by the host machine.
+    pub fn system(config: &Config) -> Self {
+        Self::system_with_gpu(GpuMonitor::new(config))
+    }
+
+    /// Host sources around a GPU monitor built elsewhere, e.g. over a
+    /// backend the agent shares between clients.
+    pub fn system_with_gpu(gpu: GpuMonitor) -> Self {
+        Self::new(
+            Box::new(CpuMonitor::new()),
+            gpu,
+            Box::new(MemoryMonitor::new()),
+            Box::new(ProcessMonitor::new()),
+            Box::new(PowerMonitor::new()),
+            Box::new(ThermalMonitor::new()),
+            Box::new(SystemHost),
+        )
+    }
+
+    /// Assemble sources, handing the GPU backend's per-process samplers to the
+    /// process source so both share one sampler.
+    pub fn new(
+        cpu: Box<dyn CpuSource>,
+        gpu: GpuMonitor,
+        memory: Box<dyn MemorySource>,
+        mut processes: Box<dyn ProcessSource>,
+        power: Box<dyn PowerSource>,
+        thermal: Box<dyn ThermalSource>,
+        host: Box<dyn HostSource>,
+    ) -> Self {
+        if let Some(source) = gpu.process_gpu_source() {
+            processes.set_gpu_source(source);
+        }
+        if let Some(source) = gpu.process_energy_source() {
+            processes.set_energy_source(source);
+        }
+        Sources {
+            cpu,
+            gpu,
+            memory,
+            processes,
+            power,
+            thermal,
+            host,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod fixture {
+    //! Deterministic sources returning canned values.
+
+    use super::*;
+    use crate::app::App;
+    use crate::broker::{Broker, Request, Response, SignalOutcome};
+    use crate::context::ProcessContext;
+    use crate::cpu::CoreCluster;
+    use crate::gpu::{GpuBackend, GpuCoreInfo, GpuInfo};
+    use crate::memory::{MemoryBreakdown, MemoryPressure};
+    use crate::power::{BatteryInfo, BatteryState};
+    use crate::process::ProcessDetails;
+    use crate::process_metrics::NoProcessMetric;
+    use crate::psi::{PressureStall, Psi, PsiLine};
+    use crate::thermal::Fan;
+    use crate::{Collector, DataCommand};
+    use ratatui::{Terminal, backend::TestBackend};
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc::{self, Receiver};
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    /// Scratch directory unique to a test and process, removed on drop. Used
+    /// by every test that builds fake sysfs/procfs trees.
+    pub struct FixtureDir(PathBuf);
+
+    impl FixtureDir {
+        pub fn new(name: &str) -> Self {
+            // Tests run in parallel; a shared name must not share a tree
+            static NEXT: AtomicUsize = AtomicUsize::new(0);
+            let dir = std::env::temp_dir().join(format!(
+                "oversee-{}-{}-{}",
+                name,
+                std::process::id(),
+                NEXT.fetch_add(1, Ordering::Relaxed)
+            ));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            FixtureDir(dir)
+        }
+
+        pub fn path(&self) -> &Path {
+            &self.0
+        }
+
+        pub fn write(&self, rel: &str, contents: &str) {
+            let path = self.0.join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+
+        #[cfg(target_os = "linux")]
+        pub fn link(&self, rel: &str, target: &str) {
+            let path = self.0.join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::os::unix::fs::symlink(target, path).unwrap();
+        }
+    }
+
+    impl Drop for FixtureDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    pub struct FixtureCpu(pub Vec<f32>);
+
+    impl CpuSource for FixtureCpu {
+        fn refresh(&mut self) {}
+
+        fn cores(&self) -> Vec<CoreSample> {
+            self.0
+                .iter()
+                .enumerate()
+                .map(|(i, u)| CoreSample {
+                    label: format!("CPU {}", i),
+                    usage: *u,
+                    frequency_mhz: 2400 + i as u64 * 100,
+                })
+                .collect()
+        }
+
+        /// One E core followed by one P core.
+        fn topology(&self) -> CpuTopology {
+            CpuTopology {
+                brand: "Fixture CPU".to_string(),
+                physical_cores: Some(self.0.len()),
+                logical_cores: self.0.len(),
+                clusters: vec![
+                    CoreCluster {
+                        name: "E".to_string(),
+                        cpus: vec![0],
+                    },
+                    CoreCluster {
+                        name: "P".to_string(),
+                        cpus: (1..self.0.len()).collect(),
+                    },
+                ],
+            }
+        }
+
+        fn breakdown(&self) -> Option<CpuBreakdown> {
+            Some(CpuBreakdown {
+                user: 12.0,
+                system: 5.0,
+                idle: 80.0,
+                iowait: 3.0,
+                ..Default::default()
+            })
+        }
+    }
+
+    pub struct FixtureMemory(pub MemoryInfo);
+
+    impl MemorySource for FixtureMemory {
+        fn refresh(&mut self) {}
+
+        fn memory_info(&self) -> MemoryInfo {
+            self.0
+        }
+    }
+
+    pub fn memory_info(used_gb: u64, total_gb: u64, pressure: MemoryPressure) -> MemoryInfo {
+        const GB: u64 = 1024 * 1024 * 1024;
+        MemoryInfo {
+            total_memory: total_gb * GB,
+            used_memory: used_gb * GB,
+            total_swap: 0,
+            used_swap: 0,
+            pressure,
+            pressure_percentage: 1.5,
+            stall: stall(),
+            breakdown: MemoryBreakdown {
+                available: Some((total_gb - used_gb) * GB + 2 * GB),
+                cached: Some(3 * GB / 2),
+                buffers: Some(GB / 2),
+                shared: Some(GB / 4),
+                dirty: Some(12 * 1024 * 1024),
+                slab: Some(GB / 2),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Light CPU and IO contention with a little memory stall.
+    pub fn stall() -> PressureStall {
+        let psi = |some: f32, full: Option<f32>| Psi {
+            some: PsiLine {
+                avg10: some,
+                ..Default::default()
+            },
+            full: full.map(|avg10| PsiLine {
+                avg10,
+                ..Default::default()
+            }),
+        };
+        PressureStall {
+            cpu: Some(psi(4.0, None)),
+            memory: Some(psi(1.5, Some(0.2))),
+            io: Some(psi(2.5, Some(1.0))),
+        }
+    }
+
+    /// Process source that records every refresh as `(include_ports, full_refresh)`.
+    pub struct FixtureProcesses {
+        pub processes: Vec<ProcessInfo>,
+        pub refreshes: Arc<Mutex<Vec<(bool, bool)>>>,
+    }
+
+    impl FixtureProcesses {
+        pub fn new(processes: Vec<ProcessInfo>) -> Self {
+            FixtureProcesses {
+                processes,
+                refreshes: Arc::new(Mutex::new(Vec::new())),
+            }
+        }
+    }
+
+    impl ProcessSource for FixtureProcesses {
+        fn refresh(&mut self, include_ports: bool, full_refresh: bool) {
+            self.refreshes
+                .lock()
+                .unwrap()
+                .push((include_ports, full_refresh));
+        }
+
+        fn processes(&self) -> &[ProcessInfo] {
+            &self.processes
+        }
+
+        fn next_sort_mode(&mut self) {
+            self.processes.reverse();
+        }
+
+        fn set_memory_column(&mut self, _column: MemoryColumn) {}
+
+        fn set_gpu_source(&mut self, _source: Box<dyn ProcessMetricSource>) {}
+
+        fn set_energy_source(&mut self, _source: Box<dyn ProcessMetricSource>) {}
+    }
+
+    pub struct FixturePower(pub PowerInfo);
+
+    impl PowerSource for FixturePower {
+        fn refresh(&mut self) {}
+
+        fn power_info(&self) -> PowerInfo {
+            self.0
+        }
+    }
+
+    pub struct FixtureThermal(pub ThermalInfo);
+
+    impl ThermalSource for FixtureThermal {
+        fn refresh(&mut self) {}
+
+        fn thermal_info(&self) -> ThermalInfo {
+            self.0.clone()
+        }
+    }
+
+    pub struct FixtureHost(pub HostInfo);
+
+    impl HostSource for FixtureHost {
+        fn host_info(&self) -> HostInfo {
+            self.0.clone()
+        }
+    }
+
+    /// GPU backend reporting a fixed utilisation across `cores` cores.
+    pub struct FixtureGpu {
+        pub utilization: f32,
+        pub cores: usize,
+    }
+
+    impl GpuBackend for FixtureGpu {
+        fn chip_name(&self) -> &str {
+            "Fixture GPU"
+        }
+
+        fn core_count(&self) -> usize {
+            self.cores
+        }
+
+        fn set_active(&mut self, _active: bool) {}
+
+        fn sample(&mut self) -> GpuInfo {
+            GpuInfo {
+                cores: (0..self.cores)
+                    .map(|_| GpuCoreInfo {
+                        utilization: self.utilization,
+                    })
+                    .collect(),
+                overall_utilization: self.utilization,
+                core_count: self.cores,
+                chip_name: self.chip_name().to_string(),
+                vram: None,
+                activity: None,
+                soc: None,
+            }
+        }
+
+        fn process_gpu_source(&self) -> Box<dyn ProcessMetricSource> {
+            Box::new(NoProcessMetric)
+        }
+    }
+
+    pub fn process(pid: u32, name: &str, cpu_usage: f32, memory_mb: u64) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            name: name.to_string(),
+            cmd: format!("/usr/bin/{}", name),
+            user: "dev".to_string(),
+            cpu_usage,
+            gpu_usage: None,
+            energy_impact: None,
+            memory: memory_mb * 1024 * 1024,
+            ports: Vec::new(),
+            cwd: None,
+            exe: None,
+            run_time: 60,
+            thread_count: 4,
+            pss: None,
+            uss: None,
+            cgroup: None,
+        }
+    }
+
+    /// A complete set of fixture sources.
+    pub fn sources(processes: Vec<ProcessInfo>) -> Sources {
+        Sources::new(
+            Box::new(FixtureCpu(vec![10.0, 30.0])),
+            GpuMonitor::with_backend(Some(Box::new(FixtureGpu {
+                utilization: 40.0,
+                cores: 4,
+            }))),
+            Box::new(FixtureMemory(memory_info(8, 16, MemoryPressure::Green))),
+            Box::new(FixtureProcesses::new(processes)),
+            Box::new(FixturePower(PowerInfo {
+                package_watts: Some(12.5),
+                battery: Some(BatteryInfo {
+                    charge_percent: 80.0,
+                    state: BatteryState::Discharging,
+                    rate_watts: Some(-12.5),
+                }),
+                ..Default::default()
+            })),
+            Box::new(FixtureThermal(ThermalInfo {
+                fans: vec![Fan {
+                    label: "CPU Fan".to_string(),
+                    rpm: 1800,
+                }],
+                ..ThermalInfo::from_readings(
+                    [
+                        ("coretemp Package id 0".to_string(), 64.0, Some(100.0)),
+                        ("amdgpu edge".to_string(), 51.0, None),
+                    ],
+                    false,
+                )
+            })),
+            Box::new(FixtureHost(HostInfo {
+                name: "fixture-host".to_string(),
+                uptime_secs: 3 * 3600 + 5 * 60,
+                load_one: 1.5,
+            })),
+        )
+    }
+
+    /// App that never touches the host GPU, plus the receiving end of its
+    /// command channel.
+    pub fn app() -> (App, Receiver<DataCommand>) {
+        let (command_tx, command_rx) = mpsc::channel();
+        let mut app = App::new(command_tx, Config::default(), None);
+        // Never read or signal a host process that happens to share a pid
+        app.broker = Broker::with_handler(unreadable);
+        (app, command_rx)
+    }
+
+    /// Broker answers as for a process oversee may not look at.
+    pub fn unreadable(request: Request) -> Response {
+        match request {
+            Request::Details(_) => Response::Details(ProcessDetails::default()),
+            Request::Maps(_) => Response::Maps(None),
+            Request::Files(_) => Response::Files(None),
+            Request::Threads(_) => Response::Threads(None),
+            Request::Context(_) => Response::Context(ProcessContext::default()),
+            Request::Terminate(_) => Response::Terminate(SignalOutcome::Failed(libc::EPERM)),
+        }
+    }
+
+    /// The drawn screen as text, one line per terminal row.
+    pub fn screen_text(terminal: &Terminal<TestBackend>) -> String {
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Run one collector tick over `sources` and deliver the result to `app`.
+    pub fn collect(app: &mut App, sources: Sources) {
+        let (tx, rx) = mpsc::channel();
+        Collector::new(sources, tx).tick(Instant::now());
+        app.process_updates(&rx);
+    }
+}
@@ -9,13 +9,28 @@ pub const TRAIL_TIERS: usize = 4;
 
 pub struct Theme {
     pub cpu: Color,
+    /// CPU time-in-state bands.
+    pub cpu_user: Color,
+    pub cpu_system: Color,
+    pub cpu_iowait: Color,
     pub gpu: Color,
     pub mem: Color,
+    /// Memory bar segments besides app memory (`mem`).
+    pub mem_wired: Color,
+    pub mem_compressed: Color,
+    pub mem_cache: Color,
+    pub power: Color,
+    pub thermal: Color,
 
     /// Index 0 = newest (brightest), TRAIL_TIERS - 1 = oldest (faintest).
     pub cpu_trail: [Color; TRAIL_TIERS],
+    pub cpu_user_trail: [Color; TRAIL_TIERS],
+    pub cpu_system_trail: [Color; TRAIL_TIERS],
+    pub cpu_iowait_trail: [Color; TRAIL_TIERS],
     pub gpu_trail: [Color; TRAIL_TIERS],
     pub mem_trail: [Color; TRAIL_TIERS],
+    pub power_trail: [Color; TRAIL_TIERS],
+    pub thermal_trail: [Color; TRAIL_TIERS],
 
     pub accent_warn: Color,
     pub accent_crit: Color,
@@ -31,8 +46,16 @@ pub struct Theme {
 
 pub const THEME: Theme = Theme {
     cpu: Color::Cyan,
+    cpu_user: Color::Rgb(110, 160, 255),
+    cpu_system: Color::Rgb(255, 120, 80),
+    cpu_iowait: Color::Rgb(230, 210, 70),
     gpu: Color::Magenta,
     mem: Color::Green,
+    mem_wired: Color::Rgb(230, 150, 60),
+    mem_compressed: Color::Rgb(190, 120, 230),
+    mem_cache: Color::Rgb(45, 110, 60),
+    power: Color::Rgb(255, 170, 60),
+    thermal: Color::Rgb(255, 110, 110),
 
     cpu_trail: [
         Color::Cyan,
@@ -40,6 +63,24 @@ pub const THEME: Theme = Theme {
         Color::Rgb(50, 110, 130),
         Color::Rgb(35, 70, 85),
     ],
+    cpu_user_trail: [
+        Color::Rgb(110, 160, 255),
+        Color::Rgb(85, 125, 200),
+        Color::Rgb(55, 80, 130),
+        Color::Rgb(35, 50, 85),
+    ],
+    cpu_system_trail: [
+        Color::Rgb(255, 120, 80),
+        Color::Rgb(200, 95, 65),
+        Color::Rgb(130, 60, 40),
+        Color::Rgb(85, 40, 25),
+    ],
+    cpu_iowait_trail: [
+        Color::Rgb(230, 210, 70),
+        Color::Rgb(180, 165, 55),
+        Color::Rgb(115, 105, 35),
+        Color::Rgb(75, 70, 25),
+    ],
     gpu_trail: [
         Color::Magenta,
         Color::Rgb(180, 80, 180),
@@ -52,6 +93,18 @@ pub const THEME: Theme = Theme {
         Color::Rgb(45, 110, 60),
         Color::Rgb(30, 70, 40),
     ],
+    power_trail: [
+        Color::Rgb(255, 170, 60),
+        Color::Rgb(200, 130, 50),
+        Color::Rgb(130, 85, 35),
+        Color::Rgb(85, 55, 25),
+    ],
+    thermal_trail: [
+        Color::Rgb(255, 110, 110),
+        Color::Rgb(200, 85, 85),
+        Color::Rgb(130, 55, 55),
+        Color::Rgb(85, 35, 35),
+    ],
 
     accent_warn: Color::Yellow,
     accent_crit: Color::Red,
@@ -0,0 +1,338 @@
+//! Temperature sensors, fan speeds and throttling hints.
+//!
+//! Readings come from sysinfo `Components` (hwmon on Linux, SMC/IOKit on
+//! macOS). Sensor labels differ wildly between drivers and machines, so they
+//! are sorted into CPU, package and GPU by name. On Linux the kernel's
+//! thermal throttle counters confirm throttling; otherwise it is inferred
+//! from how close a sensor is to the critical threshold it reports. Sensors
+//! without one (all of them on macOS) say nothing about throttling. Fan speeds come
+//! from hwmon and are Linux-only; sysinfo does not expose the SMC fans.
+
+use std::path::{Path, PathBuf};
+use sysinfo::Components;
+
+/// Warn when a sensor is this close to its critical threshold.
+const CRITICAL_MARGIN: f32 = 5.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensorKind {
+    Package,
+    Cpu,
+    Gpu,
+    Other,
+}
+
+impl SensorKind {
+    /// Classify a sensor label, e.g. "coretemp Package id 0", "k10temp Tctl",
+    /// "amdgpu edge", "PMU tdie1", "GPU MTR Temp Sensor1".
+    pub fn from_label(label: &str) -> Self {
+        let label = label.to_ascii_lowercase();
+        let has = |needle: &str| label.contains(needle);
+        if has("gpu") || has("nouveau") || has("radeon") {
+            SensorKind::Gpu
+        } else if has("package") || has("tctl") || has("x86_pkg") || has("soc mtr") {
+            SensorKind::Package
+        } else if has("core") || has("cpu") || has("tdie") || has("tccd") {
+            SensorKind::Cpu
+        } else {
+            SensorKind::Other
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sensor {
+    pub label: String,
+    pub kind: SensorKind,
+    pub celsius: f32,
+    pub critical: Option<f32>,
+}
+
+impl Sensor {
+    /// Degrees left before the critical threshold; unknown when unreported.
+    pub fn headroom(&self) -> Option<f32> {
+        self.critical.map(|critical| critical - self.celsius)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fan {
+    pub label: String,
+    pub rpm: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ThermalInfo {
+    pub sensors: Vec<Sensor>,
+    pub fans: Vec<Fan>,
+    /// The kernel recorded throttle events since the previous sample, or a
+    /// sensor is within a few degrees of its critical threshold.
+    pub throttling: bool,
+}
+
+impl ThermalInfo {
+    /// Build from raw `(label, celsius, critical)` readings. Unreadable
+    /// sensors (NaN, zero) are dropped.
+    pub fn from_readings(
+        readings: impl IntoIterator<Item = (String, f32, Option<f32>)>,
+        throttle_events: bool,
+    ) -> Self {
+        let sensors: Vec<Sensor> = readings
+            .into_iter()
+            .filter(|(_, celsius, _)| celsius.is_finite() && *celsius > 0.0)
+            .map(|(label, celsius, critical)| Sensor {
+                kind: SensorKind::from_label(&label),
+                label,
+                celsius,
+                critical: critical.filter(|c| c.is_finite() && *c > 0.0),
+            })
+            .collect();
+        let near_critical = sensors
+            .iter()
+            .filter(|s| s.kind != SensorKind::Other)
+            .any(|s| s.headroom().is_some_and(|h| h <= CRITICAL_MARGIN));
+        ThermalInfo {
+            sensors,
+            fans: Vec::new(),
+            throttling: throttle_events || near_critical,
+        }
+    }
+
+    fn hottest(&self, kinds: &[SensorKind]) -> Option<&Sensor> {
+        self.sensors
+            .iter()
+            .filter(|s| kinds.contains(&s.kind))
+            .max_by(|a, b| a.celsius.total_cmp(&b.celsius))
+    }
+
+    /// Package sensor when present, otherwise the hottest CPU core.
+    pub fn cpu(&self) -> Option<&Sensor> {
+        self.hottest(&[SensorKind::Package])
+            .or_else(|| self.hottest(&[SensorKind::Cpu]))
+    }
+
+    pub fn gpu(&self) -> Option<&Sensor> {
+        self.hottest(&[SensorKind::Gpu])
+    }
+
+    pub fn fastest_fan(&self) -> Option<&Fan> {
+        self.fans.iter().max_by_key(|f| f.rpm)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sensors.is_empty() && self.fans.is_empty()
+    }
+}
+
+/// Fan tachometers under `root` (normally `/sys/class/hwmon`), labelled by
+/// `fanN_label` or else the chip name, e.g. "nct6798 fan2".
+pub fn read_fans(root: &Path) -> Vec<Fan> {
+    let Ok(chips) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let read = |path: PathBuf| std::fs::read_to_string(path).ok();
+    let mut fans = Vec::new();
+    for chip in chips.flatten() {
+        let dir = chip.path();
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let chip_name = read(dir.join("name")).map(|s| s.trim().to_string());
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let Some(fan) = name.to_str().and_then(|n| n.strip_suffix("_input")) else {
+                continue;
+            };
+            if !fan.starts_with("fan") {
+                continue;
+            }
+            let Some(rpm) = read(entry.path()).and_then(|s| s.trim().parse::<u32>().ok()) else {
+                continue;
+            };
+            let label = read(dir.join(format!("{}_label", fan)))
+                .map(|s| s.trim().to_string())
+                .unwrap_or_else(|| match &chip_name {
+                    Some(chip) => format!("{} {}", chip, fan),
+                    None => fan.to_string(),
+                });
+            fans.push(Fan { label, rpm });
+        }
+    }
+    fans.sort_by(|a, b| a.label.cmp(&b.label));
+    fans
+}
+
+/// Sum of the per-CPU thermal throttle counters under `root` (normally
+/// `/sys/devices/system/cpu`). `None` where the kernel does not expose them.
+pub fn read_throttle_count(root: &Path) -> Option<u64> {
+    let mut total = None;
+    for entry in std::fs::read_dir(root).ok()?.flatten() {
+        let name = entry.file_name();
+        let is_cpu = name
+            .to_str()
+            .and_then(|n| n.strip_prefix("cpu"))
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()));
+        if !is_cpu {
+            continue;
+        }
+        let dir = entry.path().join("thermal_throttle");
+        for counter in ["core_throttle_count", "package_throttle_count"] {
+            if let Some(count) = std::fs::read_to_string(dir.join(counter))
+                .ok()
+                .and_then(|s| s.trim().parse::<u64>().ok())
+            {
+                *total.get_or_insert(0) += count;
+            }
+        }
+    }
+    total
+}
+
+#[derive(Debug)]
+pub struct ThermalMonitor {
+    components: Components,
+    throttle_root: PathBuf,
+    hwmon_root: PathBuf,
+    last_throttle_count: Option<u64>,
+    current: ThermalInfo,
+}
+
+impl ThermalMonitor {
+    pub fn new() -> Self {
+        let throttle_root = PathBuf::from("/sys/devices/system/cpu");
+        ThermalMonitor {
+            components: Components::new_with_refreshed_list(),
+            last_throttle_count: read_throttle_count(&throttle_root),
+            throttle_root,
+            hwmon_root: PathBuf::from("/sys/class/hwmon"),
+            current: ThermalInfo::default(),
+        }
+    }
+
+    pub fn refresh(&mut self) {
+        self.components.refresh();
+
+        let count = read_throttle_count(&self.throttle_root);
+        let throttle_events = matches!(
+            (self.last_throttle_count, count),
+            (Some(before), Some(now)) if now > before
+        );
+        self.last_throttle_count = count;
+
+        self.current = ThermalInfo::from_readings(
+            self.components
+                .list()
+                .iter()
+                .map(|c| (c.label().to_string(), c.temperature(), c.critical())),
+            throttle_events,
+        );
+        self.current.fans = read_fans(&self.hwmon_root);
+    }
+
+    pub fn get_info(&self) -> ThermalInfo {
+        self.current.clone()
+    }
+}
+
+impl Default for ThermalMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::fixture::FixtureDir;
+
+    fn reading(label: &str, celsius: f32, critical: Option<f32>) -> (String, f32, Option<f32>) {
+        (label.to_string(), celsius, critical)
+    }
+
+    #[test]
+    fn test_sensor_classification() {
+        for (label, kind) in [
+            ("coretemp Package id 0", SensorKind::Package),
+            ("coretemp Core 3", SensorKind::Cpu),
+            ("k10temp Tctl", SensorKind::Package),
+            ("k10temp Tccd1", SensorKind::Cpu),
+            ("amdgpu edge", SensorKind::Gpu),
+            ("PMU tdie4", SensorKind::Cpu),
+            ("GPU MTR Temp Sensor1", SensorKind::Gpu),
+            ("SOC MTR Temp Sensor0", SensorKind::Package),
+            ("nvme Composite", SensorKind::Other),
+            ("acpitz temp1", SensorKind::Other),
+        ] {
+            assert_eq!(SensorKind::from_label(label), kind, "{}", label);
+        }
+    }
+
+    #[test]
+    fn test_summary_prefers_package_and_flags_near_critical() {
+        let info = ThermalInfo::from_readings(
+            [
+                reading("coretemp Core 0", 71.0, Some(100.0)),
+                reading("coretemp Package id 0", 68.0, Some(100.0)),
+                reading("amdgpu edge", 55.0, None),
+                reading("broken", f32::NAN, None),
+            ],
+            false,
+        );
+        assert_eq!(info.sensors.len(), 3);
+        assert_eq!(info.cpu().map(|s| s.celsius), Some(68.0));
+        assert_eq!(info.gpu().map(|s| s.celsius), Some(55.0));
+        assert!(!info.throttling);
+
+        let hot = ThermalInfo::from_readings([reading("PMU tdie1", 96.0, Some(100.0))], false);
+        assert!(hot.throttling, "within 5°C of the reported limit");
+        // Apple Silicon runs this hot under load and reports no limit
+        let unknown = ThermalInfo::from_readings([reading("PMU tdie1", 96.0, None)], false);
+        assert!(!unknown.throttling);
+        assert_eq!(unknown.sensors[0].headroom(), None);
+        // Hot drives do not throttle the CPU
+        let nvme = ThermalInfo::from_readings([reading("nvme Composite", 80.0, Some(82.0))], false);
+        assert!(!nvme.throttling);
+        assert!(ThermalInfo::from_readings([], true).throttling);
+    }
+
+    #[test]
+    fn test_read_fans() {
+        let fx = FixtureDir::new("hwmon-fans");
+        fx.write("hwmon0/name", "coretemp\n");
+        fx.write("hwmon0/temp1_input", "45000\n");
+        fx.write("hwmon1/name", "nct6798\n");
+        fx.write("hwmon1/fan1_input", "1200\n");
+        fx.write("hwmon1/fan1_label", "CPU Fan\n");
+        fx.write("hwmon1/fan2_input", "0\n");
+        fx.write("hwmon1/fan2_min", "300\n");
+        fx.write("hwmon2/fan1_input", "bogus\n");
+
+        let info = ThermalInfo {
+            fans: read_fans(fx.path()),
+            ..ThermalInfo::default()
+        };
+        let fans: Vec<(&str, u32)> = info
+            .fans
+            .iter()
+            .map(|f| (f.label.as_str(), f.rpm))
+            .collect();
+        assert_eq!(fans, [("CPU Fan", 1200), ("nct6798 fan2", 0)]);
+        assert_eq!(info.fastest_fan().map(|f| f.rpm), Some(1200));
+        assert!(!info.is_empty());
+        assert!(read_fans(&fx.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn test_read_throttle_count() {
+        let fx = FixtureDir::new("throttle");
+        fx.write("cpu0/thermal_throttle/core_throttle_count", "3\n");
+        fx.write("cpu0/thermal_throttle/package_throttle_count", "10\n");
+        fx.write("cpu1/thermal_throttle/core_throttle_count", "2\n");
+        fx.write("cpufreq/policy0/scaling_cur_freq", "2400000\n");
+        assert_eq!(read_throttle_count(fx.path()), Some(15));
+
+        let empty = FixtureDir::new("throttle-none");
+        empty.write("cpu0/online", "1\n");
+        assert_eq!(read_throttle_count(empty.path()), None);
+    }
+}
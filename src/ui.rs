@@ -1,6 +1,17 @@
-use crate::app::App;
+use crate::app::{App, TimelineTrace};
+use crate::broker::Response;
+use crate::cgroup::Workload;
+use crate::context::{CgroupEntry, ProcessContext};
+use crate::cpu::CpuBreakdown;
+use crate::events::format_date_time;
+use crate::fleet::{Fleet, HostState};
+use crate::inspect::{FdKind, InspectView, MapSort, MappedFile, OpenFile, ThreadInfo, ThreadSort};
+use crate::memory::MemoryInfo;
+use crate::power::BatteryState;
 use crate::process::{ConnectionState, PortInfo, ProcessDetails, ProcessInfo, SortMode};
+use crate::psi::PressureStall;
 use crate::theme::{THEME, TRAIL_TIERS, trail_tier};
+use crate::thermal::Sensor;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
@@ -8,11 +19,12 @@ use ratatui::{
     text::{Line, Span, Text},
     widgets::{Cell, Paragraph, Row, Table, Wrap},
 };
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
 
 const MAX_BREAKOUT_PORTS: usize = 6;
 
-fn format_uptime_short() -> String {
-    let secs = sysinfo::System::uptime();
+fn format_uptime_short(secs: u64) -> String {
     let d = secs / 86_400;
     let h = (secs % 86_400) / 3_600;
     let m = (secs % 3_600) / 60;
@@ -25,10 +37,6 @@ fn format_uptime_short() -> String {
     }
 }
 
-fn current_load_one() -> f64 {
-    sysinfo::System::load_average().one
-}
-
 fn wrap_to_width(s: &str, width: usize) -> Vec<String> {
     if width == 0 {
         return vec![s.to_string()];
@@ -89,6 +97,7 @@ fn format_port_line(port: &PortInfo) -> String {
 fn build_breakout_lines<'a>(
     proc: &ProcessInfo,
     details: Option<&ProcessDetails>,
+    context: Option<&ProcessContext>,
     width: usize,
 ) -> Vec<Line<'a>> {
     let mut lines: Vec<Line> = Vec::new();
@@ -172,7 +181,7 @@ fn build_breakout_lines<'a>(
         None => "…".to_string(),
     };
     let runtime = format_runtime(proc.run_time);
-    lines.push(Line::from(vec![
+    let mut stats = vec![
         Span::styled("threads: ", key_style),
         Span::raw(threads),
         Span::raw("    "),
@@ -181,7 +190,36 @@ fn build_breakout_lines<'a>(
         Span::raw("    "),
         Span::styled("runtime: ", key_style),
         Span::raw(runtime),
-    ]));
+    ];
+    // Only macOS reports a per-process energy figure
+    if let Some(impact) = proc.energy_impact {
+        stats.push(Span::raw("    "));
+        stats.push(Span::styled("energy impact: ", key_style));
+        stats.push(Span::raw(format!("{:.1}", impact)));
+    }
+    lines.push(Line::from(stats));
+
+    // memory: whatever the platform reports for this process
+    if let Some(memory) = details.and_then(|d| d.memory) {
+        let mut spans = vec![Span::styled("memory:", key_style)];
+        for (name, bytes) in [
+            ("rss", memory.resident),
+            ("virt", memory.virtual_size),
+            ("shared", memory.shared),
+            ("private", memory.private),
+            ("pss", memory.pss),
+            ("swap", memory.swap),
+        ] {
+            if let Some(bytes) = bytes {
+                spans.push(Span::raw(format!(" {} ", name)));
+                spans.push(Span::styled(
+                    format_memory_size(bytes),
+                    Style::default().fg(THEME.mem),
+                ));
+            }
+        }
+        lines.push(Line::from(spans));
+    }
 
     // ports
     if !proc.ports.is_empty() {
@@ -198,6 +236,125 @@ fn build_breakout_lines<'a>(
         }
     }
 
+    // environment, limits, cgroup, namespaces, start time (toggled with d)
+    if let Some(context) = context {
+        lines.extend(context_lines(proc, context, width));
+    }
+
+    lines
+}
+
+/// The breakout's optional context sections. Unreadable parts say so
+/// rather than vanishing, since that usually means another user's process.
+fn context_lines<'a>(proc: &ProcessInfo, context: &ProcessContext, width: usize) -> Vec<Line<'a>> {
+    let mut lines = Vec::new();
+    let dim = Style::default().fg(THEME.fg_faint);
+    let key_style = Style::default().fg(THEME.accent_warn);
+    let unreadable = |name: &str| {
+        Line::from(vec![
+            Span::styled(format!("{}: ", name), key_style),
+            Span::styled("unreadable", dim),
+        ])
+    };
+
+    // Only Linux reports the exact start; elsewhere derive it from the runtime
+    let started = context
+        .start_time
+        .unwrap_or_else(|| SystemTime::now() - Duration::from_secs(proc.run_time));
+    lines.push(Line::from(vec![
+        Span::styled("started: ", key_style),
+        Span::raw(format_date_time(started)),
+    ]));
+
+    match &context.cgroups {
+        Some(cgroups) => {
+            // The unified hierarchy says it all; v1 lists one path per controller
+            let unified: Vec<&CgroupEntry> = cgroups.iter().filter(|c| c.hierarchy == 0).collect();
+            let shown = if unified.is_empty() {
+                cgroups.iter().collect()
+            } else {
+                unified
+            };
+            for cgroup in shown {
+                let label = if cgroup.controllers.is_empty() {
+                    cgroup.path.clone()
+                } else {
+                    format!("{}:{}", cgroup.controllers.join(","), cgroup.path)
+                };
+                lines.push(Line::from(vec![
+                    Span::styled("cgroup: ", key_style),
+                    Span::raw(label),
+                ]));
+            }
+        }
+        None => lines.push(unreadable("cgroup")),
+    }
+
+    match &context.namespaces {
+        Some(namespaces) => {
+            let isolated: Vec<String> = namespaces
+                .iter()
+                .filter(|ns| ns.isolated == Some(true))
+                .map(|ns| format!("{}:[{}]", ns.kind, ns.inode))
+                .collect();
+            let mut spans = vec![Span::styled("namespaces: ", key_style)];
+            if namespaces.iter().all(|ns| ns.isolated.is_none()) {
+                let all: Vec<String> = namespaces
+                    .iter()
+                    .map(|ns| format!("{}:[{}]", ns.kind, ns.inode))
+                    .collect();
+                spans.push(Span::raw(all.join(" ")));
+            } else if isolated.is_empty() {
+                spans.push(Span::styled("all shared with oversee", dim));
+            } else {
+                spans.push(Span::styled(
+                    isolated.join(" "),
+                    Style::default().fg(THEME.accent_warn),
+                ));
+                spans.push(Span::styled(" (others shared with oversee)", dim));
+            }
+            lines.push(Line::from(spans));
+        }
+        None => lines.push(unreadable("namespaces")),
+    }
+
+    match &context.limits {
+        Some(limits) => {
+            lines.push(Line::from(Span::styled("limits:", key_style)));
+            for limit in limits.iter().filter(|l| !l.is_unlimited()) {
+                let name = limit.name.strip_prefix("Max ").unwrap_or(&limit.name);
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {:<20} {} / {}", name, limit.soft, limit.hard)),
+                    Span::styled(format!(" {}", limit.units), dim),
+                ]));
+            }
+        }
+        None => lines.push(unreadable("limits")),
+    }
+
+    match &context.environment {
+        Some(vars) => {
+            lines.push(Line::from(Span::styled(
+                format!("environment ({}):", vars.len()),
+                key_style,
+            )));
+            for var in vars {
+                if var.masked {
+                    lines.push(Line::from(vec![
+                        Span::raw(format!("  {}=", var.key)),
+                        Span::styled("•••••• (masked)", dim),
+                    ]));
+                    continue;
+                }
+                let text = format!("{}={}", var.key, var.value);
+                for part in wrap_to_width(&text, width.saturating_sub(4).max(10)) {
+                    lines.push(Line::from(format!("  {}", part)));
+                }
+            }
+        }
+        None => lines.push(unreadable("environment")),
+    }
+
     lines
 }
 
@@ -254,20 +411,24 @@ pub fn render(f: &mut Frame, app: &mut App) {
         height: size.height.saturating_sub(2),
     };
 
+    // The watch strip only takes a row when the user configured a watch list.
+    let watch_height = if app.watch.is_empty() { 0 } else { 1 };
+
     // Main layout: KPI header, per-core CPU/GPU lines, separator, timeline,
-    // spacing, memory, separator, process list
+    // spacing, memory, watch strip, separator, process list
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1),  // KPI header strip
-            Constraint::Length(1),  // Per-core CPU line
-            Constraint::Length(1),  // Per-core GPU line
-            Constraint::Length(1),  // Separator under header
-            Constraint::Length(22), // Timeline graph (full width)
-            Constraint::Length(1),  // Spacing
-            Constraint::Length(1),  // Memory stats (1 line)
-            Constraint::Length(1),  // Separator above process list
-            Constraint::Min(8),     // Process list
+            Constraint::Length(1),            // KPI header strip
+            Constraint::Length(1),            // Per-core CPU line
+            Constraint::Length(1),            // Per-core GPU line
+            Constraint::Length(1),            // Separator under header
+            Constraint::Length(22),           // Timeline graph (full width)
+            Constraint::Length(1),            // Spacing
+            Constraint::Length(2),            // Memory stats and breakdown bar
+            Constraint::Length(watch_height), // Watch list status strip
+            Constraint::Length(1),            // Separator above process list
+            Constraint::Min(8),               // Process list
         ])
         .split(margin_area);
 
@@ -287,20 +448,238 @@ pub fn render(f: &mut Frame, app: &mut App) {
     render_chart_timeline(f, app, main_chunks[4]);
 
     render_memory_section(f, app, main_chunks[6]);
-    render_separator(f, main_chunks[7]);
-    render_process_list(f, app, main_chunks[8]);
+    render_watch_strip(f, app, main_chunks[7]);
+    render_separator(f, main_chunks[8]);
+    render_process_list(f, app, main_chunks[9]);
 
     // Render kill confirmation dialog if active
     if app.kill_confirmation_mode {
         render_kill_confirmation(f, app, size);
     }
 
+    if app.events_mode {
+        render_events_popup(f, app);
+    }
+
+    if app.services_mode {
+        render_services_popup(f, app);
+    }
+
+    if let Some(view) = app.inspect_view {
+        render_inspect_popup(f, app, view);
+    }
+
     // Render help popup if active (render last so it appears on top)
     if app.help_mode {
         render_help_popup(f, app);
     }
 }
 
+/// Samples shown in each fleet sparkline.
+const SPARKLINE_SAMPLES: usize = 60;
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// The last `width` percentages of `history` as block characters.
+fn sparkline(history: &VecDeque<f32>, width: usize) -> String {
+    let skip = history.len().saturating_sub(width);
+    let mut line: String = history
+        .iter()
+        .skip(skip)
+        .map(|&v| {
+            let level = (v.clamp(0.0, 100.0) / 100.0 * (SPARK_LEVELS.len() - 1) as f32).round();
+            SPARK_LEVELS[level as usize]
+        })
+        .collect();
+    // Right-align so every host's "now" lines up
+    let pad = width.saturating_sub(history.len() - skip);
+    line.insert_str(0, &" ".repeat(pad));
+    line
+}
+
+/// The fleet table, or the full UI of the host being drilled into.
+pub fn render_fleet(f: &mut Frame, fleet: &mut Fleet) {
+    use crate::memory::MemoryPressure;
+
+    if let Some(app) = fleet.drilled_app() {
+        render(f, app);
+        return;
+    }
+
+    let size = f.area();
+    let area = Rect {
+        x: size.x + 1,
+        y: size.y + 1,
+        width: size.width.saturating_sub(2),
+        height: size.height.saturating_sub(2),
+    };
+    if area.height < 4 {
+        return;
+    }
+
+    let label = Style::default().fg(THEME.fg_dim);
+    let faint = Style::default().fg(THEME.fg_faint);
+    let up = fleet
+        .hosts
+        .iter()
+        .filter(|h| h.state == HostState::Up)
+        .count();
+    let title = Line::from(vec![
+        Span::styled(
+            "oversee",
+            Style::default().fg(THEME.fg).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" · ", faint),
+        Span::styled(format!("fleet ({}/{} up)", up, fleet.hosts.len()), label),
+    ]);
+    f.render_widget(Paragraph::new(title), Rect { height: 1, ..area });
+    render_separator(
+        f,
+        Rect {
+            y: area.y + 1,
+            height: 1,
+            ..area
+        },
+    );
+
+    // Whatever the KPI columns leave is shared by the three sparklines
+    const KPI_WIDTH: usize = 2 + 24 + 5 * 3 + 10 + 6;
+    let spark_width = ((area.width as usize).saturating_sub(KPI_WIDTH) / 3)
+        .saturating_sub(1)
+        .min(SPARKLINE_SAMPLES);
+
+    let header = Line::from(Span::styled(
+        format!(
+            "  {:<24}{:>5}{:>5}{:>5}  {:<8}{:>6} {:<w$} {:<w$} {:<w$}",
+            "HOST",
+            "CPU",
+            "GPU",
+            "MEM",
+            "PRESSURE",
+            "LOAD",
+            "CPU",
+            "GPU",
+            "MEM",
+            w = spark_width
+        ),
+        label,
+    ));
+
+    let list_height = area.height as usize - 4;
+    let scroll = fleet.selected.saturating_sub(list_height.saturating_sub(1));
+    let lines: Vec<Line> = fleet
+        .hosts
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(list_height)
+        .map(|(i, host)| {
+            let selected = i == fleet.selected;
+            let name_style = if selected {
+                Style::default().fg(THEME.cpu).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(THEME.fg)
+            };
+            let mut spans = vec![
+                Span::styled(if selected { "► " } else { "  " }, name_style),
+                Span::styled(
+                    format!("{:<24}", truncate_string(host.label(), 23)),
+                    name_style,
+                ),
+            ];
+
+            let Some(app) = &host.app else {
+                let state = match &host.state {
+                    HostState::Down(reason) => format!("down: {}", reason),
+                    _ => "connecting…".to_string(),
+                };
+                spans.push(Span::styled(state, faint));
+                return Line::from(spans);
+            };
+
+            // A host that went away keeps its last readings, dimmed
+            let live = host.state == HostState::Up;
+            let tint =
+                |color: Color| Style::default().fg(if live { color } else { THEME.fg_faint });
+            let latest = |history: &VecDeque<f32>| history.back().copied().unwrap_or(0.0);
+            let cpu = app.get_cpu_average_history();
+            let pressure = app.memory_info.map(|m| m.pressure);
+            let (pressure_label, pressure_color) = match pressure {
+                Some(MemoryPressure::Green) => ("normal", THEME.mem),
+                Some(MemoryPressure::Yellow) => ("warning", THEME.accent_warn),
+                Some(MemoryPressure::Red) => ("critical", THEME.accent_crit),
+                None => ("-", THEME.fg_faint),
+            };
+            let load = app.host_info.as_ref().map_or(0.0, |h| h.load_one);
+            // Hosts without a GPU have no history worth drawing
+            let (gpu, gpu_spark, gpu_color) = match app.gpu {
+                Some(_) => (
+                    format!("{:>4.0}%", latest(&app.gpu_overall_history)),
+                    sparkline(&app.gpu_overall_history, spark_width),
+                    THEME.gpu,
+                ),
+                None => (
+                    format!("{:>5}", "-"),
+                    format!("{:>1$}", "-", spark_width),
+                    THEME.fg_faint,
+                ),
+            };
+
+            spans.extend([
+                Span::styled(format!("{:>4.0}%", latest(cpu)), tint(THEME.cpu)),
+                Span::styled(gpu, tint(gpu_color)),
+                Span::styled(
+                    format!("{:>4.0}%", latest(&app.memory_usage_history)),
+                    tint(THEME.mem),
+                ),
+                Span::raw("  "),
+                Span::styled(format!("{:<8}", pressure_label), tint(pressure_color)),
+                Span::styled(format!("{:>6.2} ", load), tint(THEME.fg)),
+                Span::styled(format!("{} ", sparkline(cpu, spark_width)), tint(THEME.cpu)),
+                Span::styled(format!("{} ", gpu_spark), tint(gpu_color)),
+                Span::styled(
+                    sparkline(&app.memory_usage_history, spark_width),
+                    tint(THEME.mem),
+                ),
+            ]);
+            if let HostState::Down(reason) = &host.state {
+                spans.push(Span::styled(
+                    format!(" down: {}", reason),
+                    Style::default().fg(THEME.accent_crit),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    f.render_widget(
+        Paragraph::new(header),
+        Rect {
+            y: area.y + 2,
+            height: 1,
+            ..area
+        },
+    );
+    f.render_widget(
+        Paragraph::new(Text::from(lines)),
+        Rect {
+            y: area.y + 3,
+            height: list_height as u16,
+            ..area
+        },
+    );
+    f.render_widget(
+        Paragraph::new("↑↓ select · Enter open host (q returns) · q quit").style(faint),
+        Rect {
+            y: area.y + area.height - 1,
+            height: 1,
+            ..area
+        },
+    );
+}
+
+/// Wide enough for "docker:" and a short container id.
+const CONTAINER_COLUMN_WIDTH: u16 = 19;
+
 fn render_process_list(f: &mut Frame, app: &mut App, area: Rect) {
     let all_processes = app.get_all_processes();
     let processes = app.get_filtered_processes();
@@ -314,6 +693,12 @@ fn render_process_list(f: &mut Frame, app: &mut App, area: Rect) {
         ])
         .split(area);
 
+    if app.group_by_workload {
+        render_workload_groups(f, app, chunks[0]);
+        render_process_help(f, app, chunks[1]);
+        return;
+    }
+
     // Header with sort indicators (active sort gets underline + brighter fg).
     let sort_mode = app.get_sort_mode();
     let header_base = Style::default().fg(THEME.fg_dim);
@@ -327,7 +712,10 @@ fn render_process_list(f: &mut Frame, app: &mut App, area: Rect) {
             header_base
         }
     };
-    let header = Row::new(vec![
+    // CONTAINER only appears where some process belongs to a service or
+    // container, so hosts without cgroups keep the room for COMMAND.
+    let show_container = all_processes.iter().any(|p| p.workload().is_some());
+    let mut header_cells = vec![
         Cell::from(Span::styled(
             format!("{:>8}", "PID"),
             header_style_for(matches!(sort_mode, SortMode::Pid)),
@@ -340,19 +728,28 @@ fn render_process_list(f: &mut Frame, app: &mut App, area: Rect) {
         Cell::from(Span::styled(format!("{:>6}", "GPU%"), header_base)),
         Cell::from(Span::styled("PORTS", header_base)),
         Cell::from(Span::styled(
-            format!("{:>7}", "MEM"),
+            format!("{:>7}", app.memory_column.label()),
             header_style_for(matches!(sort_mode, SortMode::Memory)),
         )),
         Cell::from(Span::styled(
             "COMMAND",
             header_style_for(matches!(sort_mode, SortMode::Name)),
         )),
-    ])
-    .height(1);
+    ];
+    if show_container {
+        header_cells.insert(6, Cell::from(Span::styled("CONTAINER", header_base)));
+    }
+    let header = Row::new(header_cells).height(1);
 
     // Width available for the Command column's wrapped breakout content.
     // Fixed cols total 8+8+6+6+12+7 = 47, plus 6 column spacings, plus 2 for highlight symbol.
-    let cmd_col_width = (chunks[0].width as usize).saturating_sub(47 + 6 + 2);
+    let container_width = if show_container {
+        CONTAINER_COLUMN_WIDTH + 1
+    } else {
+        0
+    };
+    let cmd_col_width =
+        (chunks[0].width as usize).saturating_sub(47 + 6 + 2 + container_width as usize);
 
     // Process rows
     let rows: Vec<Row> = processes
@@ -373,8 +770,6 @@ fn render_process_list(f: &mut Frame, app: &mut App, area: Rect) {
                 Style::default().fg(THEME.fg)
             };
 
-            let mem_mb = proc.memory as f64 / (1024.0 * 1024.0);
-
             let cmd_display = proc.cmd.clone();
 
             let pid_display = if is_pinned {
@@ -392,49 +787,64 @@ fn render_process_list(f: &mut Frame, app: &mut App, area: Rect) {
                 };
                 Cell::from(Span::styled(format!("{:>1$.1}", value, width), style))
             };
-            let mem_cell = {
-                let style = if mem_mb < 1.0 {
-                    Style::default().fg(THEME.fg_faint)
-                } else {
-                    Style::default().fg(THEME.mem)
-                };
-                Cell::from(Span::styled(format!("{:>7.0}", mem_mb), style))
+            let gpu_cell = match proc.gpu_usage {
+                Some(gpu) => metric_cell(gpu, THEME.gpu, 6),
+                None => Cell::from(Span::styled(
+                    format!("{:>6}", "n/a"),
+                    Style::default().fg(THEME.fg_faint),
+                )),
+            };
+            let mem_cell = match proc.memory_for(app.memory_column) {
+                Some(bytes) => {
+                    let mem_mb = bytes as f64 / (1024.0 * 1024.0);
+                    let style = if mem_mb < 1.0 {
+                        Style::default().fg(THEME.fg_faint)
+                    } else {
+                        Style::default().fg(THEME.mem)
+                    };
+                    Cell::from(Span::styled(format!("{:>7.0}", mem_mb), style))
+                }
+                // PSS/USS not collected yet, or not readable for this process
+                None => Cell::from(Span::styled(
+                    format!("{:>7}", "n/a"),
+                    Style::default().fg(THEME.fg_faint),
+                )),
             };
             let pid_cell = Cell::from(Span::styled(
                 format!("{:>8}", pid_display),
                 Style::default().fg(THEME.fg_dim),
             ));
 
+            let mut cells = vec![
+                pid_cell,
+                Cell::from(truncate_string(&proc.user, 8)),
+                metric_cell(proc.cpu_usage, THEME.cpu, 6),
+                gpu_cell,
+                Cell::from(format_ports(&proc.ports)),
+                mem_cell,
+            ];
+            if show_container {
+                let label = proc.workload().map(Workload::label).unwrap_or_default();
+                cells.push(Cell::from(Span::styled(
+                    truncate_string(&label, CONTAINER_COLUMN_WIDTH as usize),
+                    Style::default().fg(THEME.fg_dim),
+                )));
+            }
+
             if is_expanded {
                 let mut cmd_lines: Vec<Line> = vec![Line::from(cmd_display)];
                 cmd_lines.extend(build_breakout_lines(
                     proc,
-                    app.selected_details.as_ref(),
+                    app.details(),
+                    app.context(),
                     cmd_col_width,
                 ));
                 let row_height = cmd_lines.len() as u16;
-                Row::new(vec![
-                    pid_cell,
-                    Cell::from(truncate_string(&proc.user, 8)),
-                    metric_cell(proc.cpu_usage, THEME.cpu, 6),
-                    metric_cell(proc.gpu_usage, THEME.gpu, 6),
-                    Cell::from(format_ports(&proc.ports)),
-                    mem_cell,
-                    Cell::from(Text::from(cmd_lines)),
-                ])
-                .height(row_height)
-                .style(row_style)
+                cells.push(Cell::from(Text::from(cmd_lines)));
+                Row::new(cells).height(row_height).style(row_style)
             } else {
-                Row::new(vec![
-                    pid_cell,
-                    Cell::from(truncate_string(&proc.user, 8)),
-                    metric_cell(proc.cpu_usage, THEME.cpu, 6),
-                    metric_cell(proc.gpu_usage, THEME.gpu, 6),
-                    Cell::from(format_ports(&proc.ports)),
-                    mem_cell,
-                    Cell::from(cmd_display),
-                ])
-                .style(row_style)
+                cells.push(Cell::from(cmd_display));
+                Row::new(cells).style(row_style)
             }
         })
         .collect();
@@ -474,46 +884,155 @@ fn render_process_list(f: &mut Frame, app: &mut App, area: Rect) {
         height: chunks[0].height.saturating_sub(1),
     };
 
+    let mut widths = vec![
+        Constraint::Length(8),  // PID
+        Constraint::Length(8),  // User
+        Constraint::Length(6),  // CPU%
+        Constraint::Length(6),  // GPU%
+        Constraint::Length(12), // Ports
+        Constraint::Length(7),  // MEM (in MB)
+    ];
+    if show_container {
+        widths.push(Constraint::Length(CONTAINER_COLUMN_WIDTH));
+    }
+    widths.push(Constraint::Min(30)); // Command (flexible)
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .row_highlight_style(Style::default().fg(THEME.cpu).add_modifier(Modifier::BOLD))
+        .highlight_symbol("► ");
+
+    f.render_stateful_widget(table, table_area, &mut app.table_state);
+
+    render_process_help(f, app, chunks[1]);
+}
+
+/// The process list summed per service, container or pod (key `c`), with
+/// each group's cgroup limits where it has one.
+fn render_workload_groups(f: &mut Frame, app: &App, area: Rect) {
+    let groups = app.workload_groups();
+    let mut title = format!("workloads ({} total)", groups.len());
+    if !app.filter_input.is_empty() {
+        title.push_str(&format!(" · filter: {}", app.filter_input));
+    }
+    let title_area = Rect { height: 1, ..area };
+    f.render_widget(
+        Paragraph::new(title).style(Style::default().fg(THEME.fg_dim)),
+        title_area,
+    );
+
+    let header_style = Style::default().fg(THEME.fg_dim);
+    let header = Row::new(vec![
+        Cell::from(Span::styled("WORKLOAD", header_style)),
+        Cell::from(Span::styled(format!("{:>5}", "PROCS"), header_style)),
+        Cell::from(Span::styled(format!("{:>6}", "CPU%"), header_style)),
+        Cell::from(Span::styled(format!("{:>9}", "CPU LIMIT"), header_style)),
+        Cell::from(Span::styled(format!("{:>9}", "MEM"), header_style)),
+        Cell::from(Span::styled("CGROUP MEM / LIMIT", header_style)),
+    ]);
+
+    let faint = Style::default().fg(THEME.fg_faint);
+    let rows: Vec<Row> = groups
+        .iter()
+        .map(|group| {
+            let limits = group.limits.unwrap_or_default();
+            // Within a tenth of a limit is where throttling and OOM kills start
+            let near = |used: f64, limit: f64| limit > 0.0 && used >= limit * 0.9;
+            let cpu_style = match limits.cpu_max {
+                Some(cores) if near(group.cpu_usage as f64, cores as f64 * 100.0) => {
+                    Style::default().fg(THEME.accent_warn)
+                }
+                _ => Style::default().fg(THEME.cpu),
+            };
+            let cpu_limit = limits
+                .cpu_max
+                .map_or_else(|| "—".to_string(), |cores| format!("{:.2}", cores));
+            let (cgroup_memory, memory_style) = match (limits.memory_current, limits.memory_max) {
+                (Some(current), Some(max)) => (
+                    format!(
+                        "{} / {} ({:.0}%)",
+                        format_memory_size(current),
+                        format_memory_size(max),
+                        current as f64 / max as f64 * 100.0
+                    ),
+                    if near(current as f64, max as f64) {
+                        Style::default().fg(THEME.accent_warn)
+                    } else {
+                        Style::default().fg(THEME.mem)
+                    },
+                ),
+                (Some(current), None) => (
+                    format!("{} / —", format_memory_size(current)),
+                    Style::default().fg(THEME.mem),
+                ),
+                _ => ("—".to_string(), faint),
+            };
+            let label_style = if group.workload.is_some() {
+                Style::default().fg(THEME.fg)
+            } else {
+                faint
+            };
+            Row::new(vec![
+                Cell::from(Span::styled(group.label(), label_style)),
+                Cell::from(format!("{:>5}", group.processes)),
+                Cell::from(Span::styled(format!("{:>6.1}", group.cpu_usage), cpu_style)),
+                Cell::from(Span::styled(format!("{:>9}", cpu_limit), faint)),
+                Cell::from(Span::styled(
+                    format!("{:>9}", format_memory_size(group.memory)),
+                    Style::default().fg(THEME.mem),
+                )),
+                Cell::from(Span::styled(cgroup_memory, memory_style)),
+            ])
+        })
+        .collect();
+
+    let table_area = Rect {
+        y: area.y + 1,
+        height: area.height.saturating_sub(1),
+        ..area
+    };
     let table = Table::new(
         rows,
         [
-            Constraint::Length(8),  // PID
-            Constraint::Length(8),  // User
+            Constraint::Min(24),    // Workload
+            Constraint::Length(5),  // Processes
             Constraint::Length(6),  // CPU%
-            Constraint::Length(6),  // GPU%
-            Constraint::Length(12), // Ports
-            Constraint::Length(7),  // MEM (in MB)
-            Constraint::Min(30),    // Command (flexible)
+            Constraint::Length(9),  // CPU limit (cores)
+            Constraint::Length(9),  // Summed RSS
+            Constraint::Length(28), // cgroup memory.current / memory.max
         ],
     )
-    .header(header)
-    .row_highlight_style(Style::default().fg(THEME.cpu).add_modifier(Modifier::BOLD))
-    .highlight_symbol("► ");
-
-    f.render_stateful_widget(table, table_area, &mut app.table_state);
+    .header(header);
+    f.render_widget(table, table_area);
+}
 
-    // Help text
+fn render_process_help(f: &mut Frame, app: &App, area: Rect) {
     let help_text = if app.kill_confirmation_mode {
         "confirm kill · [Y] yes · [N] no · esc cancel"
     } else if app.filter_mode {
         "type to filter · enter apply · esc cancel"
+    } else if app.group_by_workload {
+        "[grouped] c processes · q quit · space pause · s sort · / filter · u services · e events · ? help"
     } else if app.is_paused() {
-        "[paused] space resume · q quit · ↑↓ nav · enter pin · K kill · s sort · / filter · +/- time · g/G top/bot · ? help"
+        "[paused] space resume · q quit · ↑↓ nav · enter pin · K kill · s sort · / filter · c group · u services · e events · +/- time · g/G top/bot · ? help"
     } else {
-        "space pause · q quit · ↑↓ nav · enter pin · K kill · s sort · / filter · +/- time · g/G top/bot · ? help"
+        "space pause · q quit · ↑↓ nav · enter pin · K kill · s sort · / filter · c group · u services · e events · +/- time · g/G top/bot · ? help"
     };
 
     let help_style = if app.kill_confirmation_mode {
         Style::default().fg(THEME.accent_crit)
+    } else if app.signal_status.is_some() {
+        Style::default().fg(THEME.accent_warn)
     } else {
         Style::default().fg(THEME.fg_faint)
     };
+    let help_text = app.signal_status.as_deref().unwrap_or(help_text);
 
     let help = Paragraph::new(help_text)
         .style(help_style)
         .wrap(Wrap { trim: true });
 
-    f.render_widget(help, chunks[1]);
+    f.render_widget(help, area);
 }
 
 fn render_separator(f: &mut Frame, area: Rect) {
@@ -533,7 +1052,7 @@ fn render_kpi_header(f: &mut Frame, app: &App, area: Rect) {
     let label = Style::default().fg(THEME.fg_dim);
     let bullet = Span::styled(" · ", Style::default().fg(THEME.fg_faint));
 
-    let load = current_load_one();
+    let host = app.host_info.clone().unwrap_or_default();
     let cpu_avg = app
         .get_cpu_average_history()
         .iter()
@@ -562,77 +1081,432 @@ fn render_kpi_header(f: &mut Frame, app: &App, area: Rect) {
     let proc_count = app.get_all_processes().len();
     let position = app.get_timeline_position_text();
 
-    let mut spans: Vec<Span> = Vec::new();
-    spans.push(Span::styled(
+    // Segments in display order, each with the rank at which it is dropped
+    // when the line is too narrow; higher ranks go first, 0 never does.
+    let mut segments: Vec<(u8, Vec<Span>)> = Vec::new();
+    let mut title = vec![Span::styled(
         "oversee",
         Style::default().fg(THEME.fg).add_modifier(Modifier::BOLD),
+    )];
+    if app.remote.is_some() {
+        title.push(Span::styled(
+            format!(" @ {}", host.name),
+            Style::default().fg(THEME.accent_warn),
+        ));
+        if app.disconnected {
+            title.push(Span::styled(
+                " disconnected",
+                Style::default()
+                    .fg(THEME.accent_crit)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+    }
+    segments.push((0, title));
+    segments.push((
+        1,
+        vec![
+            bullet.clone(),
+            Span::styled("load ", label),
+            Span::styled(
+                format!("{:.2}", host.load_one),
+                Style::default().fg(THEME.fg),
+            ),
+        ],
     ));
-    spans.push(bullet.clone());
-    spans.push(Span::styled("load ", label));
-    spans.push(Span::styled(
-        format!("{:.2}", load),
-        Style::default().fg(THEME.fg),
+    segments.push((
+        1,
+        vec![
+            bullet.clone(),
+            Span::styled("cpu ", label),
+            Span::styled(format!("{:>3.0}%", cpu_avg), Style::default().fg(THEME.cpu)),
+        ],
     ));
-    spans.push(bullet.clone());
-    spans.push(Span::styled("cpu ", label));
-    spans.push(Span::styled(
-        format!("{:>3.0}%", cpu_avg),
-        Style::default().fg(THEME.cpu),
+    if let Some(breakdown) = app.cpu_breakdown_history.back().copied().flatten() {
+        segments.push((5, cpu_breakdown_spans(&breakdown)));
+    }
+    segments.push((
+        1,
+        vec![
+            bullet.clone(),
+            Span::styled("gpu ", label),
+            Span::styled(format!("{:>3.0}%", gpu_avg), Style::default().fg(THEME.gpu)),
+        ],
     ));
-    spans.push(bullet.clone());
-    spans.push(Span::styled("gpu ", label));
-    spans.push(Span::styled(
-        format!("{:>3.0}%", gpu_avg),
-        Style::default().fg(THEME.gpu),
+    segments.push((
+        1,
+        vec![
+            bullet.clone(),
+            Span::styled("mem ", label),
+            Span::styled(format!("{:>3.0}%", mem_pct), Style::default().fg(mem_color)),
+        ],
     ));
-    spans.push(bullet.clone());
-    spans.push(Span::styled("mem ", label));
-    spans.push(Span::styled(
-        format!("{:>3.0}%", mem_pct),
-        Style::default().fg(mem_color),
+    if let Some(power) = app.power_info {
+        if let Some(watts) = power.package_watts {
+            segments.push((
+                4,
+                vec![
+                    bullet.clone(),
+                    Span::styled("pwr ", label),
+                    Span::styled(format!("{:.1}W", watts), Style::default().fg(THEME.power)),
+                ],
+            ));
+        }
+        if let Some(battery) = power.battery {
+            let charge_color = if battery.state == BatteryState::Discharging {
+                if battery.charge_percent <= 10.0 {
+                    THEME.accent_crit
+                } else if battery.charge_percent <= 20.0 {
+                    THEME.accent_warn
+                } else {
+                    THEME.fg
+                }
+            } else {
+                THEME.fg
+            };
+            let detail = match battery.rate_watts {
+                Some(rate) if rate.abs() >= 0.05 => format!(" {:+.1}W", rate),
+                _ => match battery.state.label() {
+                    "" => String::new(),
+                    state => format!(" {}", state),
+                },
+            };
+            segments.push((
+                4,
+                vec![
+                    bullet.clone(),
+                    Span::styled("bat ", label),
+                    Span::styled(
+                        format!("{:.0}%", battery.charge_percent),
+                        Style::default().fg(charge_color),
+                    ),
+                    Span::styled(detail, Style::default().fg(THEME.fg_dim)),
+                ],
+            ));
+        }
+    }
+    if let Some(thermal) = &app.thermal_info {
+        let temperature = |s: &Sensor| (format!("{:.0}°C", s.celsius), temperature_color(s));
+        let readings = [
+            ("temp ", thermal.cpu().map(temperature)),
+            ("gpu ", thermal.gpu().map(temperature)),
+            (
+                "fan ",
+                thermal
+                    .fastest_fan()
+                    .map(|f| (format!("{}rpm", f.rpm), THEME.fg)),
+            ),
+        ];
+        // One bullet before whichever reading comes first
+        let shown = readings
+            .into_iter()
+            .filter_map(|(name, r)| Some((name, r?)));
+        let mut spans = Vec::new();
+        for (i, (name, (value, color))) in shown.enumerate() {
+            spans.push(if i == 0 {
+                bullet.clone()
+            } else {
+                Span::raw(" ")
+            });
+            spans.push(Span::styled(name, label));
+            spans.push(Span::styled(value, Style::default().fg(color)));
+        }
+        if thermal.throttling {
+            spans.push(Span::styled(
+                " ⚠ throttling",
+                Style::default()
+                    .fg(THEME.accent_crit)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        segments.push((2, spans));
+    }
+    if let Some(memory) = app.memory_info
+        && memory.stall.is_available()
+    {
+        let mut spans = vec![bullet.clone()];
+        spans.extend(psi_spans(&memory.stall));
+        segments.push((3, spans));
+    }
+    segments.push((
+        6,
+        vec![
+            bullet.clone(),
+            Span::styled(
+                format!("{} procs", proc_count),
+                Style::default().fg(THEME.fg_dim),
+            ),
+        ],
     ));
-    spans.push(bullet.clone());
-    spans.push(Span::styled(
-        format!("{} procs", proc_count),
-        Style::default().fg(THEME.fg_dim),
+    segments.push((
+        6,
+        vec![
+            bullet.clone(),
+            Span::styled("up ", label),
+            Span::styled(
+                format_uptime_short(host.uptime_secs),
+                Style::default().fg(THEME.fg_dim),
+            ),
+        ],
     ));
-    spans.push(bullet.clone());
-    spans.push(Span::styled("up ", label));
-    spans.push(Span::styled(
-        format_uptime_short(),
-        Style::default().fg(THEME.fg_dim),
+    segments.push((
+        7,
+        vec![
+            bullet,
+            Span::styled(position, Style::default().fg(THEME.fg_faint)),
+        ],
     ));
-    spans.push(bullet);
-    spans.push(Span::styled(position, Style::default().fg(THEME.fg_faint)));
+
+    let width = |segments: &[(u8, Vec<Span>)]| -> usize {
+        segments
+            .iter()
+            .flat_map(|(_, spans)| spans)
+            .map(|span| span.width())
+            .sum()
+    };
+    while width(&segments) > area.width as usize {
+        // The last of the highest-ranked segments goes first
+        let Some((index, _)) = segments
+            .iter()
+            .enumerate()
+            .filter(|(_, (rank, _))| *rank > 0)
+            .max_by_key(|(i, (rank, _))| (*rank, *i))
+        else {
+            break;
+        };
+        segments.remove(index);
+    }
+    let spans: Vec<Span> = segments.into_iter().flat_map(|(_, spans)| spans).collect();
 
     let header = Paragraph::new(Line::from(spans));
     f.render_widget(header, area);
 }
 
+/// Ten-cell stacked bar of CPU time by state followed by the notable
+/// shares, e.g. `■■▪▪▪▪▪▪▪▪ usr 12 sys 5 io 3`.
+fn cpu_breakdown_spans(breakdown: &CpuBreakdown) -> Vec<Span<'static>> {
+    const CELLS: usize = 10;
+    let segments = [
+        ("usr", breakdown.user + breakdown.nice, THEME.cpu_user),
+        ("sys", breakdown.system, THEME.cpu_system),
+        ("io", breakdown.iowait, THEME.cpu_iowait),
+        ("irq", breakdown.other(), THEME.cpu),
+    ];
+
+    let mut spans = vec![Span::raw(" ")];
+    let mut bounds = Vec::with_capacity(segments.len());
+    let mut cumulative = 0.0;
+    for (_, pct, color) in segments {
+        cumulative += pct;
+        bounds.push((cumulative, color));
+    }
+    for cell in 0..CELLS {
+        let midpoint = (cell as f32 + 0.5) * 100.0 / CELLS as f32;
+        spans.push(match bounds.iter().find(|(bound, _)| midpoint < *bound) {
+            Some((_, color)) => Span::styled("■", Style::default().fg(*color)),
+            None => Span::styled("▪", Style::default().fg(THEME.fg_faint)),
+        });
+    }
+    for (name, pct, color) in segments {
+        if pct >= 0.5 {
+            spans.push(Span::styled(
+                format!(" {} ", name),
+                Style::default().fg(THEME.fg_dim),
+            ));
+            spans.push(Span::styled(
+                format!("{:.0}", pct),
+                Style::default().fg(color),
+            ));
+        }
+    }
+    spans
+}
+
+/// Colour a temperature by its headroom to the sensor's critical threshold.
+fn temperature_color(sensor: &Sensor) -> Color {
+    let headroom = sensor.headroom();
+    if headroom.is_some_and(|h| h <= 5.0) {
+        THEME.accent_crit
+    } else if headroom.is_some_and(|h| h <= 15.0) {
+        THEME.accent_warn
+    } else {
+        THEME.thermal
+    }
+}
+
 fn render_chart_timeline(f: &mut Frame, app: &App, area: Rect) {
-    // Use the entire area for the graph; KPI header lives in its own row above.
-    let inner = area;
+    // The graph fills the area above a one-row memory pressure band; the KPI
+    // header lives in its own row above.
+    let band_height = u16::from(area.height >= 2);
+    let inner = Rect {
+        height: area.height - band_height,
+        ..area
+    };
+    let band = Rect {
+        y: inner.y + inner.height,
+        height: band_height,
+        ..area
+    };
+    render_pressure_band(f, app, band);
 
-    // Use cached CPU average data (computed in update_cpu_data)
     let cpu_history: Vec<f32> = app.get_cpu_average_history().iter().copied().collect();
-
-    // Get GPU history
     let gpu_history: Vec<f32> = app.gpu_overall_history.iter().copied().collect();
+    let memory_history: Vec<f32> = if app.memory_excludes_cache {
+        app.memory_app_history.iter().copied().collect()
+    } else {
+        app.memory_usage_history.iter().copied().collect()
+    };
 
-    // Get memory history
-    let memory_history: Vec<f32> = app.memory_usage_history.iter().copied().collect();
-
-    // Render oscilloscope-style timeline
-    render_oscilloscope_timeline(
-        f,
-        inner,
-        &cpu_history,
-        &gpu_history,
-        &memory_history,
-        app.is_gpu_visible(),
-        app.get_timeline_offset(),
-    );
-}
+    // Cumulative CPU time-in-state lines, bottom to top: user, +system,
+    // +iowait, +irq/steal (the last equals total busy time).
+    let stacked: Vec<Vec<f32>> = if app.timeline_trace == TimelineTrace::CpuTime
+        && app.cpu_breakdown_history.iter().any(Option::is_some)
+    {
+        let mut levels = vec![Vec::new(); 4];
+        for b in &app.cpu_breakdown_history {
+            let b = b.unwrap_or_default();
+            let user = b.user + b.nice;
+            let system = user + b.system;
+            let iowait = system + b.iowait;
+            for (level, value) in levels
+                .iter_mut()
+                .zip([user, system, iowait, iowait + b.other()])
+            {
+                level.push(value);
+            }
+        }
+        levels
+    } else {
+        Vec::new()
+    };
+
+    let mut traces = Vec::new();
+    if stacked.is_empty() {
+        traces.push(Trace {
+            history: &cpu_history,
+            color: CellColor::Cpu,
+            label: 'C',
+        });
+    } else {
+        // Drawn top-down so the lower bands win where lines coincide
+        for (level, color, label) in [
+            (3, CellColor::Cpu, 'C'),
+            (2, CellColor::CpuIowait, 'W'),
+            (1, CellColor::CpuSystem, 'S'),
+            (0, CellColor::CpuUser, 'U'),
+        ] {
+            traces.push(Trace {
+                history: &stacked[level],
+                color,
+                label,
+            });
+        }
+    }
+    if app.is_gpu_visible() {
+        traces.push(Trace {
+            history: &gpu_history,
+            color: CellColor::Gpu,
+            label: 'G',
+        });
+    }
+    traces.push(Trace {
+        history: &memory_history,
+        color: CellColor::Memory,
+        label: 'M',
+    });
+
+    // Optional trace. Power is scaled so its peak over the retained history
+    // fills the chart; temperature maps 0-100°C straight onto the axis.
+    let mut note: Vec<Span> = Vec::new();
+    let extra_history: Vec<f32>;
+    match app.timeline_trace {
+        TimelineTrace::Off => {}
+        TimelineTrace::Power => {
+            let peak = app.power_history.iter().copied().fold(0.0f32, f32::max);
+            extra_history = if peak > 0.0 {
+                app.power_history.iter().map(|w| w / peak * 100.0).collect()
+            } else {
+                Vec::new()
+            };
+            traces.push(Trace {
+                history: &extra_history,
+                color: CellColor::Power,
+                label: 'W',
+            });
+            note.push(if peak > 0.0 {
+                Span::styled(
+                    format!(" power · peak {:.1} W ", peak),
+                    Style::default().fg(THEME.power),
+                )
+            } else {
+                Span::styled(" power · unavailable ", Style::default().fg(THEME.fg_faint))
+            });
+        }
+        TimelineTrace::Thermal => {
+            let peak = app.thermal_history.iter().copied().fold(0.0f32, f32::max);
+            extra_history = if peak > 0.0 {
+                app.thermal_history.iter().copied().collect()
+            } else {
+                Vec::new()
+            };
+            traces.push(Trace {
+                history: &extra_history,
+                color: CellColor::Thermal,
+                label: 'T',
+            });
+            note.push(if peak > 0.0 {
+                Span::styled(
+                    format!(" temp · peak {:.0}°C ", peak),
+                    Style::default().fg(THEME.thermal),
+                )
+            } else {
+                Span::styled(" temp · unavailable ", Style::default().fg(THEME.fg_faint))
+            });
+        }
+        TimelineTrace::CpuTime if stacked.is_empty() => {
+            note.push(Span::styled(
+                " cpu time · unavailable ",
+                Style::default().fg(THEME.fg_faint),
+            ));
+        }
+        TimelineTrace::CpuTime => {
+            note.push(Span::styled(
+                " cpu time ·",
+                Style::default().fg(THEME.fg_dim),
+            ));
+            for (text, color) in [
+                (" U user", THEME.cpu_user),
+                (" S +sys", THEME.cpu_system),
+                (" W +iowait", THEME.cpu_iowait),
+                (" C +irq/steal ", THEME.cpu),
+            ] {
+                note.push(Span::styled(text, Style::default().fg(color)));
+            }
+        }
+    }
+
+    render_oscilloscope_timeline(f, inner, &traces, app.get_timeline_offset());
+
+    if app.memory_excludes_cache {
+        note.push(Span::styled(
+            " M excl. cache ",
+            Style::default().fg(THEME.mem),
+        ));
+    }
+
+    if !note.is_empty() {
+        let width = (note.iter().map(Span::width).sum::<usize>() as u16).min(inner.width);
+        f.render_widget(
+            Paragraph::new(Line::from(note)),
+            Rect {
+                x: inner.x + inner.width - width,
+                y: inner.y,
+                width,
+                height: inner.height.min(1),
+            },
+        );
+    }
+}
 
 /// Build a single full-width line of per-core usage cells in tight
 /// `label:percent` form (e.g. `cpu C0:10% C1:7% …`). Percentages are
@@ -655,26 +1529,188 @@ fn render_cores_line(
     ));
 
     for (i, (_name, usage)) in usages.iter().enumerate() {
-        spans.push(Span::styled(
-            format!("{}{}:", prefix, i),
-            Style::default().fg(THEME.fg_dim),
-        ));
-        spans.push(Span::styled(
-            format!("{:.0}% ", usage),
-            Style::default().fg(get_gradient_color(*usage)),
-        ));
+        push_core_cell(&mut spans, prefix, i, *usage);
     }
 
     let line = Paragraph::new(Line::from(spans));
     f.render_widget(line, area);
 }
 
+fn push_core_cell(spans: &mut Vec<Span>, prefix: char, index: usize, usage: f32) {
+    spans.push(Span::styled(
+        format!("{}{}:", prefix, index),
+        Style::default().fg(THEME.fg_dim),
+    ));
+    spans.push(Span::styled(
+        format!("{:.0}% ", usage),
+        Style::default().fg(get_gradient_color(usage)),
+    ));
+}
+
+/// Average clock of a set of CPUs in MHz. On Apple Silicon the per-core
+/// figure is nominal, so powermetrics cluster clocks win when present
+/// ("P" matches both "P0-Cluster" and "P1-Cluster").
+fn cluster_frequency(app: &App, name: Option<&str>, cpus: &[usize]) -> Option<f32> {
+    if let (Some(name), Some(soc)) = (name, &app.soc_power) {
+        let clocks: Vec<f32> = soc
+            .clusters
+            .iter()
+            .filter(|c| c.name.starts_with(name))
+            .map(|c| c.frequency_mhz)
+            .collect();
+        if !clocks.is_empty() {
+            return Some(clocks.iter().sum::<f32>() / clocks.len() as f32);
+        }
+    }
+    let clocks: Vec<u64> = cpus
+        .iter()
+        .filter_map(|&i| app.cpu_frequencies.get(i).copied())
+        .filter(|&mhz| mhz > 0)
+        .collect();
+    (!clocks.is_empty()).then(|| clocks.iter().sum::<u64>() as f32 / clocks.len() as f32)
+}
+
+/// Per-core usage grouped by core-type cluster, e.g.
+/// `cpu Apple M1 Pro 10c E 1.0GHz C0:5% C1:3% │ P 3.2GHz C2:40% …`.
 fn render_cpu_cores_line(f: &mut Frame, app: &App, area: Rect) {
-    render_cores_line(f, area, "cpu", 'C', &app.get_cpu_usages());
+    let usages = app.get_cpu_usages();
+    if area.width == 0 || area.height == 0 || usages.is_empty() {
+        return;
+    }
+    let dim = Style::default().fg(THEME.fg_dim);
+    let value = Style::default().fg(THEME.fg);
+
+    let mut spans: Vec<Span> = vec![Span::styled("cpu ", dim)];
+    let mut groups: Vec<(Option<&str>, Vec<usize>)> = Vec::new();
+    if let Some(topology) = &app.cpu_topology {
+        if !topology.brand.is_empty() {
+            spans.push(Span::styled(format!("{} ", topology.brand), value));
+        }
+        let counts = match topology.physical_cores {
+            Some(physical) if physical != topology.logical_cores => {
+                format!("{}c/{}t ", physical, topology.logical_cores)
+            }
+            _ => format!("{}c ", topology.logical_cores),
+        };
+        spans.push(Span::styled(counts, dim));
+        groups = topology
+            .clusters
+            .iter()
+            .map(|c| (Some(c.name.as_str()), c.cpus.clone()))
+            .collect();
+    }
+    if groups.is_empty() {
+        groups.push((None, (0..usages.len()).collect()));
+    }
+
+    for (n, (name, cpus)) in groups.iter().enumerate() {
+        if n > 0 {
+            spans.push(Span::styled("│ ", Style::default().fg(THEME.separator)));
+        }
+        if let Some(name) = name {
+            spans.push(Span::styled(format!("{} ", name), dim));
+        }
+        if let Some(mhz) = cluster_frequency(app, *name, cpus) {
+            spans.push(Span::styled(format!("{:.1}GHz ", mhz / 1000.0), value));
+        }
+        for &i in cpus {
+            if let Some((_, usage)) = usages.get(i) {
+                push_core_cell(&mut spans, 'C', i, *usage);
+            }
+        }
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
 }
 
 fn render_gpu_cores_line(f: &mut Frame, app: &App, area: Rect) {
-    render_cores_line(f, area, "gpu", 'G', &app.get_gpu_usages());
+    let usages = app.get_gpu_usages();
+    if !usages.is_empty() {
+        render_cores_line(f, area, "gpu", 'G', &usages);
+        return;
+    }
+
+    // No GPU exposes real per-core counters on these platforms, so show the
+    // aggregate with whatever clock, power and memory data the backend has.
+    if area.width == 0 || area.height == 0 {
+        return;
+    }
+    let dim = Style::default().fg(THEME.fg_dim);
+    let value = Style::default().fg(THEME.fg);
+    let overall = app.gpu_overall_history.back().copied().unwrap_or(0.0);
+    let mut spans = vec![
+        Span::styled("gpu ", dim),
+        Span::styled(
+            format!("{:.0}% ", overall),
+            Style::default().fg(get_gradient_color(overall)),
+        ),
+    ];
+
+    let core_count = app.gpu.as_ref().map_or(0, |(_, cores)| *cores);
+    if core_count > 0 {
+        spans.push(Span::styled(
+            format!("aggregate of {} cores ", core_count),
+            dim,
+        ));
+    }
+
+    if let Some(activity) = &app.gpu_activity {
+        if let Some(mhz) = activity.frequency_mhz {
+            spans.push(Span::styled(format!("{:.0} MHz ", mhz), value));
+        }
+        if let Some(mw) = activity.power_mw {
+            spans.push(Span::styled(format_milliwatts(mw), value));
+            spans.push(Span::raw(" "));
+        }
+        // Residency per DVFS step, skipping steps the GPU never visited.
+        let steps: Vec<&(u32, f32)> = activity
+            .frequency_residency
+            .iter()
+            .filter(|(_, pct)| *pct >= 0.1)
+            .collect();
+        if !steps.is_empty() {
+            spans.push(Span::styled("res ", dim));
+            for (mhz, pct) in steps {
+                spans.push(Span::styled(format!("{}:", mhz), dim));
+                spans.push(Span::styled(
+                    format!("{:.1}% ", pct),
+                    Style::default().fg(get_gradient_color(*pct)),
+                ));
+            }
+        }
+    }
+
+    if let Some(soc) = &app.soc_power {
+        if let Some(mw) = soc.ane_power_mw {
+            spans.push(Span::styled("ane ", dim));
+            spans.push(Span::styled(format_milliwatts(mw), value));
+            spans.push(Span::raw(" "));
+        }
+        if let Some(mw) = soc.package_power_mw {
+            spans.push(Span::styled("pkg ", dim));
+            spans.push(Span::styled(format_milliwatts(mw), value));
+            spans.push(Span::raw(" "));
+        }
+    }
+
+    if let Some((used, total)) = app.gpu_vram {
+        let gb = 1024.0 * 1024.0 * 1024.0;
+        spans.push(Span::styled("vram ", dim));
+        spans.push(Span::styled(
+            format!("{:.1}/{:.1} GB", used as f64 / gb, total as f64 / gb),
+            value,
+        ));
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// Format a power reading, switching to watts above 1 W.
+fn format_milliwatts(mw: f32) -> String {
+    if mw >= 1000.0 {
+        format!("{:.1} W", mw / 1000.0)
+    } else {
+        format!("{:.0} mW", mw)
+    }
 }
 
 /// Interpolate between data points to create denser visualization
@@ -716,17 +1752,48 @@ enum CellColor {
     Cpu,
     Gpu,
     Memory,
+    Power,
+    Thermal,
+    CpuUser,
+    CpuSystem,
+    CpuIowait,
+}
+
+impl CellColor {
+    fn trail(self) -> &'static [Color; TRAIL_TIERS] {
+        match self {
+            CellColor::Cpu | CellColor::None => &THEME.cpu_trail,
+            CellColor::Gpu => &THEME.gpu_trail,
+            CellColor::Memory => &THEME.mem_trail,
+            CellColor::Power => &THEME.power_trail,
+            CellColor::Thermal => &THEME.thermal_trail,
+            CellColor::CpuUser => &THEME.cpu_user_trail,
+            CellColor::CpuSystem => &THEME.cpu_system_trail,
+            CellColor::CpuIowait => &THEME.cpu_iowait_trail,
+        }
+    }
+}
+
+/// One series on the oscilloscope, with values already scaled to 0-100.
+struct Trace<'a> {
+    history: &'a [f32],
+    color: CellColor,
+    label: char,
 }
 
 /// Render oscilloscope-style timeline with waveform visualization
-/// Uses a buffered approach to batch character rendering and reduce widget allocations
+/// Uses a buffered approach to batch character rendering and reduce widget allocations.
+/// Later traces draw over earlier ones where they overlap.
+/// The timeline always spans this many seconds, ending `timeline_offset`
+/// seconds ago.
+const TIMELINE_SECONDS: usize = 300;
+/// Interpolated points per sample for a denser waveform.
+const TIMELINE_DENSITY: usize = 4;
+
 fn render_oscilloscope_timeline(
     f: &mut Frame,
     area: Rect,
-    cpu_history: &[f32],
-    gpu_history: &[f32],
-    memory_history: &[f32],
-    show_gpu: bool,
+    traces: &[Trace],
     timeline_offset: usize,
 ) {
     use ratatui::text::{Line, Span};
@@ -738,44 +1805,52 @@ fn render_oscilloscope_timeline(
         return;
     }
 
-    // Always display 300 seconds, but offset by timeline_offset
-    const DISPLAY_DURATION: usize = 300;
-
     // Calculate the range we want to display
     let end_offset = timeline_offset;
-    let start_offset = end_offset + DISPLAY_DURATION;
-
-    // Get data slices accounting for offset
-    let cpu_points = get_history_slice(cpu_history, start_offset, end_offset);
-    let gpu_points = get_history_slice(gpu_history, start_offset, end_offset);
-    let memory_points = get_history_slice(memory_history, start_offset, end_offset);
-
-    // Apply interpolation for denser visualization (4x density)
-    let interpolation_factor = 4;
-    let cpu_dense = interpolate_data(cpu_points, interpolation_factor);
-    let gpu_dense = interpolate_data(gpu_points, interpolation_factor);
-    let memory_dense = interpolate_data(memory_points, interpolation_factor);
-
-    // Limit display width to available screen space
-    // Each character cell has 2 braille columns, so we need 2 data points per character
-    let display_points = (available_width * 2).min(cpu_dense.len());
-    let cpu_display = get_display_slice(&cpu_dense, display_points);
-    let gpu_display = get_display_slice(&gpu_dense, display_points);
-    let memory_display = get_display_slice(&memory_dense, display_points);
+    let start_offset = end_offset + TIMELINE_SECONDS;
+
+    let dense: Vec<Vec<f32>> = traces
+        .iter()
+        .map(|trace| {
+            let points = get_history_slice(trace.history, start_offset, end_offset);
+            interpolate_data(points, TIMELINE_DENSITY)
+        })
+        .collect();
+
+    // Limit display width to available screen space. The first trace (CPU)
+    // sets the time base. Each character cell has 2 braille columns, so we
+    // need 2 data points per character.
+    let display_points = (available_width * 2).min(dense.first().map_or(0, Vec::len));
+    let displays: Vec<&[f32]> = dense
+        .iter()
+        .map(|d| get_display_slice(d, display_points))
+        .collect();
 
     let char_width = available_width;
     let char_height = available_height;
     let dot_height = char_height * 4;
 
+    // Character row and braille sub-row for every trace at every point
+    let positions: Vec<Vec<(usize, usize)>> = displays
+        .iter()
+        .map(|display| {
+            (0..display_points)
+                .map(|col| {
+                    let usage = display.get(col).copied().unwrap_or(0.0).clamp(0.0, 100.0);
+                    let dot_row = ((usage / 100.0) * (dot_height - 1) as f32).round() as usize;
+                    (
+                        char_height.saturating_sub(1 + dot_row / 4),
+                        3 - (dot_row % 4),
+                    )
+                })
+                .collect()
+        })
+        .collect();
+
     // Create buffers for characters and colours - one row at a time rendering
     // Buffer stores (braille_bits, color) for each character cell
     let mut row_buffer: Vec<(u32, CellColor)> = vec![(0, CellColor::None); char_width];
 
-    // Track previous positions for line connections
-    let mut prev_cpu_row: Option<usize> = None;
-    let mut prev_gpu_row: Option<usize> = None;
-    let mut prev_memory_row: Option<usize> = None;
-
     // Process each row from top to bottom
     for row_idx in 0..char_height {
         // Clear the row buffer
@@ -783,130 +1858,44 @@ fn render_oscilloscope_timeline(
             *cell = (0, CellColor::None);
         }
 
-        // Process each data point
-        for col in 0..display_points {
-            let char_col = col / 2;
-            let braille_col = col % 2;
-
-            if char_col >= char_width {
-                continue;
-            }
-
-            // Get usage values
-            let cpu_usage = cpu_display
-                .get(col)
-                .copied()
-                .unwrap_or(0.0)
-                .clamp(0.0, 100.0);
-            let gpu_usage = if show_gpu {
-                gpu_display
-                    .get(col)
-                    .copied()
-                    .unwrap_or(0.0)
-                    .clamp(0.0, 100.0)
-            } else {
-                0.0
-            };
-            let memory_usage = memory_display
-                .get(col)
-                .copied()
-                .unwrap_or(0.0)
-                .clamp(0.0, 100.0);
-
-            // Convert to dot rows
-            let cpu_dot_row = ((cpu_usage / 100.0) * (dot_height - 1) as f32).round() as usize;
-            let gpu_dot_row = ((gpu_usage / 100.0) * (dot_height - 1) as f32).round() as usize;
-            let memory_dot_row =
-                ((memory_usage / 100.0) * (dot_height - 1) as f32).round() as usize;
-
-            // Convert to character row and sub-row
-            let cpu_char_row = char_height.saturating_sub(1 + cpu_dot_row / 4);
-            let gpu_char_row = char_height.saturating_sub(1 + gpu_dot_row / 4);
-            let memory_char_row = char_height.saturating_sub(1 + memory_dot_row / 4);
-
-            let cpu_sub_row = 3 - (cpu_dot_row % 4);
-            let gpu_sub_row = 3 - (gpu_dot_row % 4);
-            let memory_sub_row = 3 - (memory_dot_row % 4);
-
-            // Check if this row contains CPU data
-            if cpu_char_row == row_idx {
-                let bits = get_braille_bits(braille_col, cpu_sub_row);
-                row_buffer[char_col].0 |= bits;
-                row_buffer[char_col].1 = CellColor::Cpu;
-            }
-
-            // Check if this row contains GPU data (GPU overwrites CPU if overlapping)
-            if show_gpu && gpu_char_row == row_idx {
-                let bits = get_braille_bits(braille_col, gpu_sub_row);
-                row_buffer[char_col].0 |= bits;
-                row_buffer[char_col].1 = CellColor::Gpu;
-            }
+        for (trace, points) in traces.iter().zip(&positions) {
+            // Previous character's row, for vertical line connections
+            let mut prev_row: Option<usize> = None;
 
-            // Check if this row contains memory data (Memory overwrites others if overlapping)
-            if memory_char_row == row_idx {
-                let bits = get_braille_bits(braille_col, memory_sub_row);
-                row_buffer[char_col].0 |= bits;
-                row_buffer[char_col].1 = CellColor::Memory;
-            }
+            for (col, &(char_row, sub_row)) in points.iter().enumerate() {
+                let char_col = col / 2;
+                let braille_col = col % 2;
 
-            // Handle vertical line connections for CPU
-            if let Some(prev_row) = prev_cpu_row
-                && prev_row != cpu_char_row
-            {
-                let (start, end) = if prev_row < cpu_char_row {
-                    (prev_row, cpu_char_row)
-                } else {
-                    (cpu_char_row, prev_row)
-                };
-                // Fill vertical line if this row is between start and end
-                if row_idx > start && row_idx < end {
-                    row_buffer[char_col].0 |= get_vertical_line_bits(braille_col);
-                    if row_buffer[char_col].1 == CellColor::None {
-                        row_buffer[char_col].1 = CellColor::Cpu;
-                    }
+                if char_col >= char_width {
+                    continue;
                 }
-            }
 
-            // Handle vertical line connections for GPU
-            if show_gpu
-                && let Some(prev_row) = prev_gpu_row
-                && prev_row != gpu_char_row
-            {
-                let (start, end) = if prev_row < gpu_char_row {
-                    (prev_row, gpu_char_row)
-                } else {
-                    (gpu_char_row, prev_row)
-                };
-                if row_idx > start && row_idx < end {
-                    row_buffer[char_col].0 |= get_vertical_line_bits(braille_col);
-                    if row_buffer[char_col].1 == CellColor::None {
-                        row_buffer[char_col].1 = CellColor::Gpu;
-                    }
+                if char_row == row_idx {
+                    row_buffer[char_col].0 |= get_braille_bits(braille_col, sub_row);
+                    row_buffer[char_col].1 = trace.color;
                 }
-            }
 
-            // Handle vertical line connections for memory
-            if let Some(prev_row) = prev_memory_row
-                && prev_row != memory_char_row
-            {
-                let (start, end) = if prev_row < memory_char_row {
-                    (prev_row, memory_char_row)
-                } else {
-                    (memory_char_row, prev_row)
-                };
-                if row_idx > start && row_idx < end {
-                    row_buffer[char_col].0 |= get_vertical_line_bits(braille_col);
-                    if row_buffer[char_col].1 == CellColor::None {
-                        row_buffer[char_col].1 = CellColor::Memory;
+                // Fill vertical line if this row is between the previous and current rows
+                if let Some(prev) = prev_row
+                    && prev != char_row
+                {
+                    let (start, end) = if prev < char_row {
+                        (prev, char_row)
+                    } else {
+                        (char_row, prev)
+                    };
+                    if row_idx > start && row_idx < end {
+                        row_buffer[char_col].0 |= get_vertical_line_bits(braille_col);
+                        if row_buffer[char_col].1 == CellColor::None {
+                            row_buffer[char_col].1 = trace.color;
+                        }
                     }
                 }
-            }
 
-            // Update previous row tracking at the end of each character (braille_col == 1)
-            if braille_col == 1 {
-                prev_cpu_row = Some(cpu_char_row);
-                prev_gpu_row = Some(gpu_char_row);
-                prev_memory_row = Some(memory_char_row);
+                // Update previous row tracking at the end of each character (braille_col == 1)
+                if braille_col == 1 {
+                    prev_row = Some(char_row);
+                }
             }
         }
 
@@ -932,14 +1921,8 @@ fn render_oscilloscope_timeline(
             let (ch, style) = if *bits != 0 {
                 let braille = std::char::from_u32(0x2800 + bits).unwrap_or(' ');
                 let tier = trail_tier(col, char_width);
-                let palette = match color {
-                    CellColor::Cpu => &THEME.cpu_trail,
-                    CellColor::Gpu => &THEME.gpu_trail,
-                    CellColor::Memory => &THEME.mem_trail,
-                    CellColor::None => &THEME.cpu_trail,
-                };
                 let idx = tier.min(TRAIL_TIERS - 1);
-                (braille, Style::default().fg(palette[idx]))
+                (braille, Style::default().fg(color.trail()[idx]))
             } else if cursor_col == Some(col) {
                 ('│', Style::default().fg(THEME.cursor))
             } else {
@@ -988,16 +1971,7 @@ fn render_oscilloscope_timeline(
     }
 
     // Render signal labels on the left side of the graph
-    render_signal_labels(
-        f,
-        area,
-        cpu_display,
-        gpu_display,
-        memory_display,
-        show_gpu,
-        dot_height,
-        char_height,
-    );
+    render_signal_labels(f, area, traces, &displays, dot_height, char_height);
 }
 
 /// Helper to get braille bit value for a position
@@ -1025,7 +1999,7 @@ fn get_vertical_line_bits(col: usize) -> u32 {
 }
 
 /// Helper to slice history data with offset
-fn get_history_slice(history: &[f32], start_offset: usize, end_offset: usize) -> &[f32] {
+fn get_history_slice<T>(history: &[T], start_offset: usize, end_offset: usize) -> &[T] {
     if history.len() > start_offset {
         let start_idx = history.len() - start_offset;
         let end_idx = history.len() - end_offset;
@@ -1038,6 +2012,51 @@ fn get_history_slice(history: &[f32], start_offset: usize, end_offset: usize) ->
     }
 }
 
+/// Sample index (into a `TIMELINE_SECONDS` history slice of `len` samples)
+/// shown in each character column, using the same interpolation and
+/// right-alignment as the oscilloscope so per-tick markers line up with it.
+fn timeline_columns(len: usize, width: usize) -> Vec<usize> {
+    let dense_len = if len < 2 {
+        len
+    } else {
+        (len - 1) * TIMELINE_DENSITY + 1
+    };
+    let display_points = (width * 2).min(dense_len);
+    let first = dense_len - display_points;
+    // Each column covers two braille dots; take the later so the newest
+    // sample lands in the last column.
+    (0..display_points)
+        .step_by(2)
+        .map(|point| (first + (point + 1).min(display_points - 1)) / TIMELINE_DENSITY)
+        .collect()
+}
+
+/// One-row band under the timeline coloured by memory pressure at each tick,
+/// so escalations line up with the waveform above.
+fn render_pressure_band(f: &mut Frame, app: &App, area: Rect) {
+    use crate::memory::MemoryPressure;
+
+    if area.height == 0 || area.width == 0 {
+        return;
+    }
+    let history: Vec<MemoryPressure> = app.memory_pressure_history.iter().copied().collect();
+    let end_offset = app.get_timeline_offset();
+    let slice = get_history_slice(&history, end_offset + TIMELINE_SECONDS, end_offset);
+
+    let spans: Vec<Span> = timeline_columns(slice.len(), area.width as usize)
+        .into_iter()
+        .map(|sample| {
+            let color = match slice[sample] {
+                MemoryPressure::Green => THEME.mem,
+                MemoryPressure::Yellow => THEME.accent_warn,
+                MemoryPressure::Red => THEME.accent_crit,
+            };
+            Span::styled("▀", Style::default().fg(color))
+        })
+        .collect();
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
 /// Helper to get display slice from interpolated data
 fn get_display_slice(data: &[f32], display_points: usize) -> &[f32] {
     if data.len() > display_points {
@@ -1047,69 +2066,33 @@ fn get_display_slice(data: &[f32], display_points: usize) -> &[f32] {
     }
 }
 
-/// Render signal labels (C, G, M) at their average positions
-#[allow(clippy::too_many_arguments)]
+/// Render signal labels (C, G, M, ...) at their average positions
 fn render_signal_labels(
     f: &mut Frame,
     area: Rect,
-    cpu_display: &[f32],
-    gpu_display: &[f32],
-    memory_display: &[f32],
-    show_gpu: bool,
+    traces: &[Trace],
+    displays: &[&[f32]],
     dot_height: usize,
     char_height: usize,
 ) {
-    if !cpu_display.is_empty() {
-        let cpu_avg = cpu_display.iter().sum::<f32>() / cpu_display.len() as f32;
-        let cpu_avg_dot_row = ((cpu_avg / 100.0) * (dot_height - 1) as f32).round() as usize;
-        let cpu_avg_char_row = char_height.saturating_sub(1 + cpu_avg_dot_row / 4);
-        let cpu_label_y = area.y + cpu_avg_char_row as u16;
-
-        let cpu_label =
-            Paragraph::new("C").style(Style::default().fg(THEME.cpu).add_modifier(Modifier::BOLD));
-        f.render_widget(
-            cpu_label,
-            Rect {
-                x: area.x,
-                y: cpu_label_y,
-                width: 1,
-                height: 1,
-            },
-        );
-    }
-
-    if show_gpu && !gpu_display.is_empty() {
-        let gpu_avg = gpu_display.iter().sum::<f32>() / gpu_display.len() as f32;
-        let gpu_avg_dot_row = ((gpu_avg / 100.0) * (dot_height - 1) as f32).round() as usize;
-        let gpu_avg_char_row = char_height.saturating_sub(1 + gpu_avg_dot_row / 4);
-        let gpu_label_y = area.y + gpu_avg_char_row as u16;
+    for (trace, display) in traces.iter().zip(displays) {
+        if display.is_empty() {
+            continue;
+        }
+        let avg = display.iter().sum::<f32>() / display.len() as f32;
+        let avg_dot_row = ((avg / 100.0) * (dot_height - 1) as f32).round() as usize;
+        let avg_char_row = char_height.saturating_sub(1 + avg_dot_row / 4);
 
-        let gpu_label =
-            Paragraph::new("G").style(Style::default().fg(THEME.gpu).add_modifier(Modifier::BOLD));
-        f.render_widget(
-            gpu_label,
-            Rect {
-                x: area.x,
-                y: gpu_label_y,
-                width: 1,
-                height: 1,
-            },
+        let label = Paragraph::new(trace.label.to_string()).style(
+            Style::default()
+                .fg(trace.color.trail()[0])
+                .add_modifier(Modifier::BOLD),
         );
-    }
-
-    if !memory_display.is_empty() {
-        let memory_avg = memory_display.iter().sum::<f32>() / memory_display.len() as f32;
-        let memory_avg_dot_row = ((memory_avg / 100.0) * (dot_height - 1) as f32).round() as usize;
-        let memory_avg_char_row = char_height.saturating_sub(1 + memory_avg_dot_row / 4);
-        let memory_label_y = area.y + memory_avg_char_row as u16;
-
-        let memory_label =
-            Paragraph::new("M").style(Style::default().fg(THEME.mem).add_modifier(Modifier::BOLD));
         f.render_widget(
-            memory_label,
+            label,
             Rect {
                 x: area.x,
-                y: memory_label_y,
+                y: area.y + avg_char_row as u16,
                 width: 1,
                 height: 1,
             },
@@ -1132,6 +2115,33 @@ fn get_gradient_color(usage: f32) -> Color {
     }
 }
 
+/// "psi cpu 4.0 mem 1.5 io 2.5": share of time some task stalled (avg10).
+fn psi_spans(stall: &PressureStall) -> Vec<Span<'static>> {
+    let mut spans = vec![Span::styled("psi", Style::default().fg(THEME.fg_dim))];
+    for (name, psi) in [("cpu", stall.cpu), ("mem", stall.memory), ("io", stall.io)] {
+        let Some(psi) = psi else { continue };
+        spans.push(Span::styled(
+            format!(" {} ", name),
+            Style::default().fg(THEME.fg_dim),
+        ));
+        spans.push(Span::styled(
+            format!("{:.1}", psi.some.avg10),
+            Style::default().fg(psi_color(psi.some.avg10)),
+        ));
+    }
+    spans
+}
+
+fn psi_color(avg10: f32) -> Color {
+    if avg10 >= 25.0 {
+        THEME.accent_crit
+    } else if avg10 >= 10.0 {
+        THEME.accent_warn
+    } else {
+        THEME.fg
+    }
+}
+
 fn render_memory_section(f: &mut Frame, app: &App, area: Rect) {
     use crate::memory::MemoryPressure;
 
@@ -1168,10 +2178,22 @@ fn render_memory_section(f: &mut Frame, app: &App, area: Rect) {
             memory_info.pressure.color_name().to_string(),
             Style::default().fg(pressure_color),
         ),
+    ];
+    if let Some(psi) = memory_info.stall.memory {
+        let full = psi.full.map_or(0.0, |f| f.avg10);
+        spans.push(Span::styled(
+            format!(
+                " (stalled {:.1}%, full {:.1}%)",
+                memory_info.pressure_percentage, full
+            ),
+            label,
+        ));
+    }
+    spans.extend([
         bullet.clone(),
         Span::styled("free ", label),
         Span::styled(format!("{:.1} GB", free_gb), value),
-    ];
+    ]);
 
     if memory_info.total_swap > 0 {
         let used_swap = memory_info.used_swap as f64 / (1024.0 * 1024.0 * 1024.0);
@@ -1191,10 +2213,129 @@ fn render_memory_section(f: &mut Frame, app: &App, area: Rect) {
         ]);
     }
 
-    let stats = Paragraph::new(Line::from(spans));
+    let stats = Paragraph::new(vec![
+        Line::from(spans),
+        Line::from(memory_bar_spans(&memory_info)),
+    ]);
     f.render_widget(stats, area);
 }
 
+/// Stacked bar of where memory went followed by the segment sizes, e.g.
+/// `■■■■■■▪▪▪▪ app 6.0 cache 2.0 GB · avail 10.0 GB · dirty 12 MB`.
+/// Segments the platform does not report are left out.
+fn memory_bar_spans(memory: &MemoryInfo) -> Vec<Span<'static>> {
+    const CELLS: usize = 24;
+    const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+    let breakdown = &memory.breakdown;
+    let label = Style::default().fg(THEME.fg_dim);
+    let value = Style::default().fg(THEME.fg);
+
+    let wired = breakdown.wired.unwrap_or(0);
+    let compressed = breakdown.compressed.unwrap_or(0);
+    let in_use = memory.used_excluding_cache();
+    let segments = [
+        (
+            "app",
+            Some(in_use.saturating_sub(wired + compressed)),
+            THEME.mem,
+        ),
+        ("wired", breakdown.wired, THEME.mem_wired),
+        ("comp", breakdown.compressed, THEME.mem_compressed),
+        ("cache", breakdown.cache(), THEME.mem_cache),
+    ];
+
+    let mut spans = vec![Span::styled("       ", label)];
+    let total = memory.total_memory.max(1) as f64;
+    let mut bounds = Vec::with_capacity(segments.len());
+    let mut cumulative = 0.0;
+    for (_, bytes, color) in segments {
+        cumulative += bytes.unwrap_or(0) as f64 / total * CELLS as f64;
+        bounds.push((cumulative, color));
+    }
+    for cell in 0..CELLS {
+        let midpoint = cell as f64 + 0.5;
+        spans.push(match bounds.iter().find(|(bound, _)| midpoint < *bound) {
+            Some((_, color)) => Span::styled("■", Style::default().fg(*color)),
+            None => Span::styled("▪", Style::default().fg(THEME.fg_faint)),
+        });
+    }
+    for (name, bytes, color) in segments {
+        if let Some(bytes) = bytes {
+            spans.push(Span::styled(format!(" {} ", name), label));
+            spans.push(Span::styled(
+                format!("{:.1}", bytes as f64 / GB),
+                Style::default().fg(color),
+            ));
+        }
+    }
+    spans.push(Span::styled(" GB", label));
+
+    let extras = [
+        ("avail", breakdown.available),
+        ("shared", breakdown.shared),
+        ("slab", breakdown.slab),
+        ("purgeable", breakdown.purgeable),
+        ("dirty", breakdown.dirty),
+    ];
+    for (name, bytes) in extras {
+        let Some(bytes) = bytes else { continue };
+        spans.push(Span::styled(" · ", Style::default().fg(THEME.fg_faint)));
+        spans.push(Span::styled(format!("{} ", name), label));
+        spans.push(Span::styled(format_memory_size(bytes), value));
+    }
+    spans
+}
+
+/// GB with one decimal, whole MB below 1 GB, or whole KB below 1 MB.
+fn format_memory_size(bytes: u64) -> String {
+    const MB: f64 = 1024.0 * 1024.0;
+    let mb = bytes as f64 / MB;
+    if mb >= 1024.0 {
+        format!("{:.1} GB", mb / 1024.0)
+    } else if mb >= 1.0 {
+        format!("{:.0} MB", mb)
+    } else {
+        format!("{} KB", bytes / 1024)
+    }
+}
+
+/// Compact up/down strip for the configured watch list. Entries that were
+/// running earlier but have since disappeared are highlighted.
+fn render_watch_strip(f: &mut Frame, app: &App, area: Rect) {
+    use crate::watch::WatchState;
+
+    if area.width == 0 || area.height == 0 || app.watch.is_empty() {
+        return;
+    }
+
+    let mut spans: Vec<Span> = vec![Span::styled("watch ", Style::default().fg(THEME.fg_dim))];
+    for entry in app.watch.entries() {
+        let (marker, style) = match entry.state {
+            WatchState::Up => ("●", Style::default().fg(THEME.mem)),
+            WatchState::Missing => ("○", Style::default().fg(THEME.fg_faint)),
+            WatchState::Lost => (
+                "✕",
+                Style::default()
+                    .fg(THEME.accent_crit)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        };
+        spans.push(Span::styled(format!(" {} ", marker), style));
+        spans.push(Span::styled(entry.target.label(), style));
+        if entry.matches > 1 {
+            spans.push(Span::styled(
+                format!("×{}", entry.matches),
+                Style::default().fg(THEME.fg_faint),
+            ));
+        }
+        if entry.state == WatchState::Lost {
+            spans.push(Span::styled(" (gone)", style));
+        }
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
 fn render_kill_confirmation(f: &mut Frame, app: &App, screen_area: Rect) {
     // Create a centered dialog box
     let dialog_width = 50;
@@ -1318,8 +2459,16 @@ fn render_help_popup(f: &mut Frame, _app: &App) {
         Line::from("  Enter         Pin/Unpin process (shows full command)"),
         Line::from("  s             Cycle through sort modes"),
         Line::from("  v             Toggle GPU visibility"),
+        Line::from("  t             Cycle extra timeline trace (power, temp, cpu time)"),
+        Line::from("  m             Toggle memory trace: used / used excluding cache"),
+        Line::from("  M             Cycle MEM column: resident, proportional (PSS), unique (USS)"),
+        Line::from("  i             Inspect the selected process: maps, files, threads (Tab)"),
+        Line::from("  d             Show environment, limits, cgroup and namespaces in breakout"),
+        Line::from("  c             Group by service, container or pod with cgroup limits"),
         Line::from("  K             Kill selected process (with confirmation)"),
         Line::from("  /             Enter filter mode"),
+        Line::from("  e             Process event log (x exports it)"),
+        Line::from("  u             systemd services with main pid, usage and restarts"),
         Line::from("  +/=           Scroll timeline forward (newer data)"),
         Line::from("  -             Scroll timeline backward (older data, up to 15 min)"),
         Line::from("  ?             Toggle this help popup"),
@@ -1428,6 +2577,515 @@ fn render_help_popup(f: &mut Frame, _app: &App) {
     f.render_widget(paragraph, popup_area);
 }
 
+/// Scrollable process lifecycle log, newest event first.
+fn render_events_popup(f: &mut Frame, app: &App) {
+    use crate::events::{EventKind, format_clock};
+    use ratatui::widgets::{Block, Borders, Clear};
+
+    let popup_area = {
+        let area = f.area();
+        let horizontal_margin = area.width / 10;
+        let vertical_margin = area.height / 10;
+        Rect {
+            x: horizontal_margin,
+            y: vertical_margin,
+            width: area.width.saturating_sub(horizontal_margin * 2),
+            height: area.height.saturating_sub(vertical_margin * 2),
+        }
+    };
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(" events · {} ", app.events.len()))
+        .title_style(Style::default().fg(THEME.fg).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(THEME.separator));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    if inner.height < 2 {
+        return;
+    }
+    let list_height = inner.height as usize - 1;
+
+    let lines: Vec<Line> = if app.events.is_empty() {
+        vec![Line::from(Span::styled(
+            "no process starts or exits since oversee started",
+            Style::default().fg(THEME.fg_faint),
+        ))]
+    } else {
+        app.events
+            .events()
+            .iter()
+            .rev()
+            .skip(app.events_scroll)
+            .take(list_height)
+            .map(|event| {
+                let kind_style = match event.kind {
+                    EventKind::Spawned => Style::default().fg(THEME.mem),
+                    EventKind::Exited { .. } => Style::default().fg(THEME.accent_warn),
+                    EventKind::Restarted { .. } => Style::default()
+                        .fg(THEME.accent_crit)
+                        .add_modifier(Modifier::BOLD),
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} ", format_clock(event.at)),
+                        Style::default().fg(THEME.fg_faint),
+                    ),
+                    Span::styled(format!("{:<8}", event.kind.label()), kind_style),
+                    Span::raw(event.describe()),
+                ])
+            })
+            .collect()
+    };
+    f.render_widget(
+        Paragraph::new(Text::from(lines)),
+        Rect {
+            height: list_height as u16,
+            ..inner
+        },
+    );
+
+    let footer = app
+        .events_status
+        .clone()
+        .unwrap_or_else(|| "↑↓ scroll · g/G newest/oldest · x export · e close".to_string());
+    f.render_widget(
+        Paragraph::new(footer).style(Style::default().fg(THEME.fg_faint)),
+        Rect {
+            y: inner.y + list_height as u16,
+            height: 1,
+            ..inner
+        },
+    );
+}
+
+/// systemd units with the processes beneath each, scrollable.
+fn render_services_popup(f: &mut Frame, app: &App) {
+    use crate::events::format_clock;
+    use crate::services::UnitState;
+    use ratatui::widgets::{Block, Borders, Clear};
+
+    let popup_area = {
+        let area = f.area();
+        let horizontal_margin = area.width / 10;
+        let vertical_margin = area.height / 10;
+        Rect {
+            x: horizontal_margin,
+            y: vertical_margin,
+            width: area.width.saturating_sub(horizontal_margin * 2),
+            height: area.height.saturating_sub(vertical_margin * 2),
+        }
+    };
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(" services · {} ", app.services.len()))
+        .title_style(Style::default().fg(THEME.fg).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(THEME.separator));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    if inner.height < 3 {
+        return;
+    }
+    let list_height = inner.height as usize - 2;
+    let faint = Style::default().fg(THEME.fg_faint);
+
+    let header = Line::from(Span::styled(
+        format!(
+            "{:<32} {:<8} {:>8} {:>5} {:>6} {:>9} {:>8}",
+            "UNIT", "STATE", "MAIN PID", "PROCS", "CPU%", "MEM", "RESTARTS"
+        ),
+        Style::default().fg(THEME.fg_dim),
+    ));
+
+    let lines: Vec<Line> = if app.services.is_empty() {
+        vec![Line::from(Span::styled(
+            "no systemd services found in process cgroups",
+            faint,
+        ))]
+    } else {
+        app.services
+            .services()
+            .into_iter()
+            .flat_map(|service| {
+                let active = service.state == UnitState::Active;
+                let state_style = if active {
+                    Style::default().fg(THEME.mem)
+                } else {
+                    Style::default().fg(THEME.accent_warn)
+                };
+                let restart_style = if service.restarts > 0 {
+                    Style::default()
+                        .fg(THEME.accent_crit)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    faint
+                };
+                let mut restarts = service.restarts.to_string();
+                if let Some(at) = service.last_restart {
+                    restarts.push_str(&format!(" (last {})", format_clock(at)));
+                }
+                let unit = Line::from(vec![
+                    Span::styled(
+                        format!("{:<32} ", truncate_string(&service.name, 32)),
+                        Style::default().fg(THEME.fg).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!("{:<8} ", service.state.label()), state_style),
+                    Span::styled(
+                        format!(
+                            "{:>8} ",
+                            service.main_pid.map_or("-".to_string(), |p| p.to_string())
+                        ),
+                        Style::default().fg(THEME.fg_dim),
+                    ),
+                    Span::raw(format!("{:>5} ", service.processes.len())),
+                    Span::styled(
+                        format!("{:>6.1} ", service.cpu_usage),
+                        Style::default().fg(THEME.cpu),
+                    ),
+                    Span::styled(
+                        format!("{:>9} ", format_memory_size(service.memory)),
+                        Style::default().fg(THEME.mem),
+                    ),
+                    Span::styled(format!("{:>8}", restarts), restart_style),
+                ]);
+                let last = service.processes.len().saturating_sub(1);
+                let children = service.processes.iter().enumerate().map(move |(i, p)| {
+                    let branch = if i == last { "└" } else { "├" };
+                    Line::from(vec![
+                        Span::styled(format!("  {} ", branch), faint),
+                        Span::styled(
+                            format!("{:<28} ", truncate_string(&p.name, 28)),
+                            Style::default().fg(THEME.fg),
+                        ),
+                        Span::raw(" ".repeat(9)),
+                        Span::styled(format!("{:>8} ", p.pid), Style::default().fg(THEME.fg_dim)),
+                        Span::raw(" ".repeat(6)),
+                        Span::styled(
+                            format!("{:>6.1} ", p.cpu_usage),
+                            Style::default().fg(THEME.cpu),
+                        ),
+                        Span::styled(
+                            format!("{:>9}", format_memory_size(p.memory)),
+                            Style::default().fg(THEME.mem),
+                        ),
+                    ])
+                });
+                std::iter::once(unit).chain(children)
+            })
+            .skip(app.services_scroll)
+            .take(list_height)
+            .collect()
+    };
+    f.render_widget(Paragraph::new(header), Rect { height: 1, ..inner });
+    f.render_widget(
+        Paragraph::new(Text::from(lines)),
+        Rect {
+            y: inner.y + 1,
+            height: list_height as u16,
+            ..inner
+        },
+    );
+
+    f.render_widget(
+        Paragraph::new("↑↓ scroll · g/G top/bottom · u close").style(faint),
+        Rect {
+            y: inner.y + 1 + list_height as u16,
+            height: 1,
+            ..inner
+        },
+    );
+}
+
+/// Scrollable inspector over the expanded process.
+fn render_inspect_popup(f: &mut Frame, app: &App, view: InspectView) {
+    use ratatui::widgets::{Block, Borders, Clear};
+
+    let popup_area = {
+        let area = f.area();
+        let horizontal_margin = area.width / 10;
+        let vertical_margin = area.height / 10;
+        Rect {
+            x: horizontal_margin,
+            y: vertical_margin,
+            width: area.width.saturating_sub(horizontal_margin * 2),
+            height: area.height.saturating_sub(vertical_margin * 2),
+        }
+    };
+    f.render_widget(Clear, popup_area);
+
+    let process = app
+        .expanded_pid
+        .and_then(|pid| app.get_all_processes().iter().find(|p| p.pid == pid));
+    let title = match process {
+        Some(p) => format!(" {} · {} ({}) ", view.title(), p.name, p.pid),
+        None => format!(" {} ", view.title()),
+    };
+    let block = Block::default()
+        .title(title)
+        .title_style(Style::default().fg(THEME.fg).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(THEME.separator));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    if inner.height < 3 {
+        return;
+    }
+    let list_height = inner.height as usize - 2;
+
+    // An answer without data means the view is unreadable
+    let answer = app.inspection();
+    let fetched = answer.is_some();
+    let filter = app.inspect_filter.as_str();
+    let (header, rows, readable, sort_hint) = match view {
+        InspectView::Maps => {
+            let maps = match answer {
+                Some(Response::Maps(maps)) => maps.as_deref(),
+                _ => None,
+            };
+            let (header, rows) = map_rows(maps, app.map_sort, filter);
+            let hint = format!(" · s sort: {}", app.map_sort.label());
+            (header, rows, maps.is_some(), hint)
+        }
+        InspectView::Files => {
+            let files = match answer {
+                Some(Response::Files(files)) => files.as_deref(),
+                _ => None,
+            };
+            let (header, rows) = file_rows(files, filter);
+            (header, rows, files.is_some(), String::new())
+        }
+        InspectView::Threads => {
+            let threads = match answer {
+                Some(Response::Threads(threads)) => threads.as_deref(),
+                _ => None,
+            };
+            let (header, rows) = thread_rows(threads, app.thread_sort, filter);
+            let hint = format!(" · s sort: {}", app.thread_sort.label());
+            (header, rows, threads.is_some(), hint)
+        }
+    };
+    let footer = if app.inspect_filter_mode {
+        format!("filter: {}▏ · Enter keep · Esc clear", filter)
+    } else if filter.is_empty() {
+        format!("↑↓ scroll · Tab view · / filter{} · i close", sort_hint)
+    } else {
+        format!(
+            "↑↓ scroll · Tab view · / filter: {}{} · i close",
+            filter, sort_hint
+        )
+    };
+
+    let faint = Style::default().fg(THEME.fg_faint);
+    let lines: Vec<Line> = if rows.is_empty() {
+        let message = if !fetched {
+            "loading…"
+        } else if readable {
+            "nothing matches the filter"
+        } else {
+            match view {
+                InspectView::Maps => {
+                    "memory map unreadable: Linux only, and other users' processes need root"
+                }
+                InspectView::Files => "open files unreadable: other users' processes need root",
+                InspectView::Threads => "threads unreadable: Linux only",
+            }
+        };
+        vec![Line::from(Span::styled(message, faint))]
+    } else {
+        rows.into_iter()
+            .skip(app.inspect_scroll)
+            .take(list_height)
+            .collect()
+    };
+    f.render_widget(Paragraph::new(header), Rect { height: 1, ..inner });
+    f.render_widget(
+        Paragraph::new(Text::from(lines)),
+        Rect {
+            y: inner.y + 1,
+            height: list_height as u16,
+            ..inner
+        },
+    );
+    f.render_widget(
+        Paragraph::new(footer).style(faint),
+        Rect {
+            y: inner.y + 1 + list_height as u16,
+            height: 1,
+            ..inner
+        },
+    );
+}
+
+/// Header and one row per backing file, sorted by `sort`.
+fn map_rows(
+    maps: Option<&[MappedFile]>,
+    sort: MapSort,
+    filter: &str,
+) -> (Line<'static>, Vec<Line<'static>>) {
+    let base = Style::default().fg(THEME.fg_dim);
+    let active = base.fg(THEME.fg).add_modifier(Modifier::UNDERLINED);
+    let style_for = |column: MapSort| if column == sort { active } else { base };
+    let header = Line::from(vec![
+        Span::styled(format!("{:>9}", "RSS"), style_for(MapSort::Rss)),
+        Span::styled(format!(" {:>9}", "PSS"), style_for(MapSort::Pss)),
+        Span::styled(format!(" {:>9}", "SWAP"), base),
+        Span::styled(format!(" {:>9}", "SIZE"), style_for(MapSort::Size)),
+        Span::styled(format!(" {:>4}", "MAPS"), base),
+        Span::raw(" "),
+        Span::styled("FILE", style_for(MapSort::Name)),
+    ]);
+
+    let Some(maps) = maps else {
+        return (header, Vec::new());
+    };
+    let mut maps: Vec<MappedFile> = maps.iter().filter(|m| m.matches(filter)).cloned().collect();
+    sort.sort(&mut maps);
+    let size = |bytes: Option<u64>| bytes.map_or("-".to_string(), format_memory_size);
+    let rows = maps
+        .into_iter()
+        .map(|m| {
+            let name_style = if m.name.starts_with('[') {
+                Style::default().fg(THEME.fg_dim)
+            } else {
+                Style::default().fg(THEME.fg)
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!("{:>9}", size(m.rss)),
+                    Style::default().fg(THEME.mem),
+                ),
+                Span::raw(format!(" {:>9}", size(m.pss))),
+                Span::raw(format!(" {:>9}", size(m.swap))),
+                Span::styled(
+                    format!(" {:>9}", format_memory_size(m.size)),
+                    Style::default().fg(THEME.fg_dim),
+                ),
+                Span::styled(
+                    format!(" {:>4}", m.regions),
+                    Style::default().fg(THEME.fg_dim),
+                ),
+                Span::raw(" "),
+                Span::styled(m.name, name_style),
+            ])
+        })
+        .collect();
+    (header, rows)
+}
+
+/// Header and one row per thread, in `sort` order.
+fn thread_rows(
+    threads: Option<&[ThreadInfo]>,
+    sort: ThreadSort,
+    filter: &str,
+) -> (Line<'static>, Vec<Line<'static>>) {
+    let base = Style::default().fg(THEME.fg_dim);
+    let active = base.fg(THEME.fg).add_modifier(Modifier::UNDERLINED);
+    let style_for = |column: ThreadSort| if column == sort { active } else { base };
+    let header = Line::from(vec![
+        Span::styled(format!("{:>8}", "TID"), style_for(ThreadSort::Tid)),
+        Span::styled(" S", base),
+        Span::styled(format!(" {:>6}", "CPU%"), style_for(ThreadSort::Cpu)),
+        Span::styled(format!(" {:>10}", "USER"), style_for(ThreadSort::Time)),
+        Span::styled(format!(" {:>10}", "SYS"), style_for(ThreadSort::Time)),
+        Span::raw(" "),
+        Span::styled("NAME", style_for(ThreadSort::Name)),
+    ]);
+
+    let Some(threads) = threads else {
+        return (header, Vec::new());
+    };
+    let mut threads: Vec<ThreadInfo> = threads
+        .iter()
+        .filter(|t| t.matches(filter))
+        .cloned()
+        .collect();
+    sort.sort(&mut threads);
+    let rows = threads
+        .into_iter()
+        .map(|t| {
+            let state_color = match t.state {
+                'R' => THEME.cpu,
+                'D' => THEME.accent_warn,
+                _ => THEME.fg_dim,
+            };
+            Line::from(vec![
+                Span::styled(format!("{:>8}", t.tid), base),
+                Span::styled(format!(" {}", t.state), Style::default().fg(state_color)),
+                Span::styled(
+                    format!(
+                        " {:>6}",
+                        t.cpu_percent
+                            .map_or("-".to_string(), |c| format!("{:.1}", c))
+                    ),
+                    Style::default().fg(THEME.cpu),
+                ),
+                Span::raw(format!(" {:>10}", format_cpu_time(t.user_time))),
+                Span::styled(format!(" {:>10}", format_cpu_time(t.system_time)), base),
+                Span::raw(" "),
+                Span::raw(t.name),
+            ])
+        })
+        .collect();
+    (header, rows)
+}
+
+/// CPU time as `m:ss.cc`, like top's TIME+.
+fn format_cpu_time(time: Duration) -> String {
+    let secs = time.as_secs_f64();
+    format!("{}:{:05.2}", (secs / 60.0) as u64, secs % 60.0)
+}
+
+/// Header and one row per open descriptor, in fd order.
+fn file_rows(files: Option<&[OpenFile]>, filter: &str) -> (Line<'static>, Vec<Line<'static>>) {
+    let base = Style::default().fg(THEME.fg_dim);
+    let header = Line::from(Span::styled(
+        format!(
+            "{:>6} {:<8} {:<4} {:>12} PATH",
+            "FD", "TYPE", "MODE", "OFFSET"
+        ),
+        base,
+    ));
+
+    let Some(files) = files else {
+        return (header, Vec::new());
+    };
+    let rows = files
+        .iter()
+        .filter(|f| f.matches(filter))
+        .map(|f| {
+            let kind_color = match f.kind {
+                FdKind::File | FdKind::Directory => THEME.fg,
+                FdKind::Socket => THEME.cpu,
+                FdKind::Pipe | FdKind::EventFd | FdKind::AnonInode => THEME.gpu,
+                FdKind::Device | FdKind::Other => THEME.fg_dim,
+            };
+            Line::from(vec![
+                Span::styled(format!("{:>6} ", f.fd), base),
+                Span::styled(
+                    format!("{:<8} ", f.kind.label()),
+                    Style::default().fg(kind_color),
+                ),
+                Span::raw(format!("{:<4} ", f.mode.map_or("-", |m| m.label()))),
+                Span::styled(
+                    format!(
+                        "{:>12} ",
+                        f.offset.map_or("-".to_string(), |o| o.to_string())
+                    ),
+                    base,
+                ),
+                Span::raw(f.path.clone()),
+            ])
+        })
+        .collect();
+    (header, rows)
+}
+
 fn truncate_string(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         s.to_string()
@@ -1439,6 +3097,13 @@ fn truncate_string(s: &str, max_len: usize) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::DataCommand;
+    use crate::broker::Request;
+    use crate::source::fixture;
+    use ratatui::{Terminal, backend::TestBackend};
+    use std::sync::mpsc::Receiver;
+
+    const MB: u64 = 1024 * 1024;
 
     #[test]
     fn test_dot_pattern_generation() {
@@ -1629,6 +3294,50 @@ mod tests {
         assert_eq!(format_ports(&many), "80,81,82...");
     }
 
+    #[test]
+    fn test_pressure_band_aligns_with_timeline() {
+        use crate::memory::MemoryPressure;
+
+        assert!(timeline_columns(0, 10).is_empty());
+        // Short history: left-aligned like the waveform, one column per two dots
+        assert_eq!(timeline_columns(3, 10), vec![0, 0, 1, 1, 2]);
+        // Full history: the newest sample fills the last column
+        assert_eq!(
+            timeline_columns(300, 10),
+            vec![294, 295, 295, 296, 296, 297, 297, 298, 298, 299]
+        );
+
+        let (mut app, _rx) = fixture::app();
+        app.memory_pressure_history.extend(
+            std::iter::repeat_n(MemoryPressure::Green, 295)
+                .chain(std::iter::repeat_n(MemoryPressure::Yellow, 3))
+                .chain(std::iter::repeat_n(MemoryPressure::Red, 2)),
+        );
+        let mut terminal = Terminal::new(TestBackend::new(10, 1)).unwrap();
+        terminal
+            .draw(|f| render_pressure_band(f, &app, f.area()))
+            .unwrap();
+        let colors: Vec<Color> = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.fg)
+            .collect();
+        // Samples 294..=299: one green, three yellow, two red
+        assert_eq!(colors[0], THEME.mem);
+        assert_eq!(colors[1..7], [THEME.accent_warn; 6]);
+        assert_eq!(colors[7..], [THEME.accent_crit; 3]);
+    }
+
+    #[test]
+    fn test_sparkline_right_aligned_and_clamped() {
+        let history: VecDeque<f32> = [0.0, 50.0, 100.0, 150.0].into_iter().collect();
+        assert_eq!(sparkline(&history, 6), "  ▁▅██");
+        assert_eq!(sparkline(&history, 2), "██");
+        assert_eq!(sparkline(&VecDeque::new(), 3), "   ");
+    }
+
     // Helper functions for tests
     fn generate_dot_pattern(usage: f32) -> (usize, usize) {
         let filled_dots = (usage / 10.0).round() as usize;
@@ -1644,4 +3353,379 @@ mod tests {
         let dots = format!("{}{}", filled_str, empty_str);
         format!("{:<6}: {} {:>3.0}%", name, dots, usage)
     }
+
+    /// Fixture app after one collector tick over postgres and nginx.
+    fn snapshot_app() -> (App, Receiver<DataCommand>) {
+        let (mut app, rx) = fixture::app();
+        fixture::collect(
+            &mut app,
+            fixture::sources(vec![
+                fixture::process(4242, "postgres", 12.5, 512),
+                fixture::process(4243, "nginx", 0.5, 64),
+            ]),
+        );
+        (app, rx)
+    }
+
+    /// Draw `app` on a full-size test terminal.
+    fn draw(app: &mut App) -> String {
+        draw_width(app, 160)
+    }
+
+    fn draw_width(app: &mut App, width: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, 60)).unwrap();
+        terminal.draw(|f| render(f, app)).unwrap();
+        fixture::screen_text(&terminal)
+    }
+
+    #[test]
+    fn test_render_fixture_snapshot() {
+        let (mut app, _rx) = snapshot_app();
+        let screen = draw(&mut app);
+        assert!(screen.contains("postgres"));
+        assert!(screen.contains("4243"));
+        assert!(screen.contains("n/a"), "unmeasured GPU renders as n/a");
+        assert!(screen.contains("cpu Fixture CPU 2c E 2.4GHz C0:10% │ P 2.5GHz C1:30%"));
+        assert!(screen.contains("pwr 12.5W"));
+        assert!(screen.contains("bat 80% -12.5W"));
+        assert!(screen.contains("temp 64°C gpu 51°C fan 1800rpm"));
+        assert!(screen.contains("psi cpu 4.0 mem 1.5 io 2.5"));
+        assert!(screen.contains("load 1.50"));
+        assert!(!screen.contains("@ fixture-host"));
+        assert!(screen.contains("Normal (stalled 1.5%, full 0.2%)"));
+        assert!(screen.contains(
+            "▪ app 6.0 cache 2.0 GB · avail 10.0 GB · shared 256 MB · slab 512 MB · dirty 12 MB"
+        ));
+    }
+
+    #[test]
+    fn test_kpi_header_drops_segments_when_narrow() {
+        let (mut app, _rx) = snapshot_app();
+        let header = |app: &mut App, width| {
+            let screen = draw_width(app, width);
+            screen
+                .lines()
+                .find(|l| l.contains("oversee"))
+                .unwrap()
+                .to_string()
+        };
+
+        let wide = header(&mut app, 240);
+        assert!(wide.contains(" usr 12 sys 5 io 3 · gpu  40%"));
+        assert!(wide.contains("· 2 procs · up 3h05m · Live"));
+
+        // Uptime, process count and the CPU split go before the readings
+        let snapshot = header(&mut app, 160);
+        assert!(!snapshot.contains("up 3h05m"));
+        assert!(!snapshot.contains("usr 12"));
+        assert!(snapshot.contains("temp 64°C gpu 51°C fan 1800rpm · psi cpu 4.0"));
+
+        let narrow = header(&mut app, 60);
+        assert_eq!(
+            narrow.trim_end(),
+            " oversee · load 1.50 · cpu  20% · gpu  40% · mem  50%"
+        );
+    }
+
+    #[test]
+    fn test_render_remote_title() {
+        let (mut app, _rx) = snapshot_app();
+        app.remote = Some("fixture-host:7373".to_string());
+        assert!(draw(&mut app).contains("oversee @ fixture-host · load"));
+        app.disconnected = true;
+        assert!(draw(&mut app).contains("oversee @ fixture-host disconnected · load"));
+    }
+
+    #[test]
+    fn test_render_timeline_traces() {
+        let (mut app, _rx) = snapshot_app();
+        app.timeline_trace = TimelineTrace::Power;
+        assert!(draw(&mut app).contains("peak 12.5 W"));
+        app.timeline_trace = TimelineTrace::CpuTime;
+        assert!(draw(&mut app).contains("cpu time · U user S +sys W +iowait"));
+    }
+
+    #[test]
+    fn test_render_uss_column_and_breakout() {
+        use crate::process::{MemoryColumn, ProcessMemory};
+
+        // USS column before it has been collected, with the breakout open
+        let (mut app, _rx) = snapshot_app();
+        app.memory_column = MemoryColumn::Unique;
+        app.expanded_pid = Some(4242);
+        app.broker.insert(
+            Request::Details(4242),
+            Response::Details(ProcessDetails {
+                fd_count: Some(12),
+                thread_count_macos: None,
+                memory: Some(ProcessMemory {
+                    resident: Some(512 * MB),
+                    virtual_size: Some(4096 * MB),
+                    private: Some(200 * MB),
+                    pss: Some(300 * MB),
+                    ..Default::default()
+                }),
+            }),
+        );
+        let screen = draw(&mut app);
+        assert!(screen.contains("    USS COMMAND"));
+        assert!(screen.contains("    n/a /usr/bin/postgres"));
+        assert!(screen.contains("memory: rss 512 MB virt 4.0 GB private 200 MB pss 300 MB"));
+    }
+
+    /// Snapshot app with the inspector open on postgres.
+    fn inspecting(view: InspectView) -> (App, Receiver<DataCommand>) {
+        let (mut app, rx) = snapshot_app();
+        app.expanded_pid = Some(4242);
+        app.inspect_view = Some(view);
+        app.inspect_scroll = 0;
+        (app, rx)
+    }
+
+    #[test]
+    fn test_render_memory_map_inspector() {
+        // Sorted by RSS
+        let (mut app, _rx) = inspecting(InspectView::Maps);
+        let map = |name: &str, rss_mb: u64| MappedFile {
+            name: name.to_string(),
+            regions: 2,
+            size: 2 * rss_mb * MB,
+            rss: Some(rss_mb * MB),
+            pss: Some(rss_mb * MB / 2),
+            swap: None,
+        };
+        app.broker.insert(
+            Request::Maps(4242),
+            Response::Maps(Some(vec![map("/usr/lib/libc.so.6", 2), map("[heap]", 180)])),
+        );
+        let screen = draw(&mut app);
+        assert!(screen.contains(" memory map · postgres (4242) "));
+        assert!(screen.contains("      RSS       PSS      SWAP      SIZE MAPS FILE"));
+        assert!(screen.contains("   180 MB     90 MB         -    360 MB    2 [heap]"));
+        let heap = screen.find("[heap]").unwrap();
+        assert!(heap < screen.find("/usr/lib/libc.so.6").unwrap());
+        assert!(screen.contains("/ filter · s sort: rss · i close"));
+    }
+
+    #[test]
+    fn test_render_open_files_inspector() {
+        use crate::inspect::{FdMode, OpenFile};
+
+        // Filtered to sockets
+        let (mut app, _rx) = inspecting(InspectView::Files);
+        let file = |fd: u32, kind: FdKind, path: &str, offset: Option<u64>| OpenFile {
+            fd,
+            kind,
+            path: path.to_string(),
+            mode: Some(FdMode::ReadWrite),
+            offset,
+        };
+        app.broker.insert(
+            Request::Files(4242),
+            Response::Files(Some(vec![
+                file(
+                    3,
+                    FdKind::File,
+                    "/var/lib/postgresql/base/1/2619",
+                    Some(8192),
+                ),
+                file(7, FdKind::Socket, "socket:[81234]", None),
+            ])),
+        );
+        app.inspect_filter = "so".to_string();
+        let screen = draw(&mut app);
+        assert!(screen.contains(" open files · postgres (4242) "));
+        assert!(screen.contains("    FD TYPE     MODE       OFFSET PATH"));
+        assert!(screen.contains("     7 socket   rw              - socket:[81234]"));
+        assert!(!screen.contains("/var/lib/postgresql"));
+        assert!(screen.contains("/ filter: so · i close"));
+    }
+
+    #[test]
+    fn test_render_threads_inspector() {
+        // Busiest first
+        let (mut app, _rx) = inspecting(InspectView::Threads);
+        let thread = |tid: u32, name: &str, cpu: Option<f32>, user_ms: u64| ThreadInfo {
+            tid,
+            name: name.to_string(),
+            state: 'S',
+            cpu_percent: cpu,
+            user_time: Duration::from_millis(user_ms),
+            system_time: Duration::from_millis(250),
+        };
+        app.broker.insert(
+            Request::Threads(4242),
+            Response::Threads(Some(vec![
+                thread(4242, "postgres", Some(0.5), 1_500),
+                thread(4250, "bgworker", Some(42.0), 83_250),
+                thread(4251, "walwriter", None, 10),
+            ])),
+        );
+        let screen = draw(&mut app);
+        assert!(screen.contains(" threads · postgres (4242) "));
+        assert!(screen.contains("     TID S   CPU%       USER        SYS NAME"));
+        assert!(screen.contains("    4250 S   42.0    1:23.25    0:00.25 bgworker"));
+        assert!(screen.contains("    4251 S      -    0:00.01    0:00.25 walwriter"));
+        let busiest = screen.find("bgworker").unwrap();
+        assert!(busiest < screen.find("4242 S").unwrap());
+        assert!(screen.contains("/ filter · s sort: cpu · i close"));
+    }
+
+    #[test]
+    fn test_context_lines() {
+        use crate::context::{EnvVar, Namespace, ResourceLimit};
+        let proc = crate::source::fixture::process(4242, "postgres", 1.0, 100);
+        let limit = |name: &str, soft: &str, hard: &str| ResourceLimit {
+            name: name.to_string(),
+            soft: soft.to_string(),
+            hard: hard.to_string(),
+            units: "files".to_string(),
+        };
+        let namespace = |kind: &str, inode: u64, isolated: bool| Namespace {
+            kind: kind.to_string(),
+            inode,
+            isolated: Some(isolated),
+        };
+        let context = ProcessContext {
+            environment: Some(vec![
+                EnvVar {
+                    key: "PGDATA".to_string(),
+                    value: "/var/lib/postgresql/data".to_string(),
+                    masked: false,
+                },
+                EnvVar {
+                    key: "PGPASSWORD".to_string(),
+                    value: String::new(),
+                    masked: true,
+                },
+            ]),
+            limits: Some(vec![
+                limit("Max cpu time", "unlimited", "unlimited"),
+                limit("Max open files", "1024", "524288"),
+            ]),
+            cgroups: Some(vec![CgroupEntry {
+                hierarchy: 0,
+                controllers: Vec::new(),
+                path: "/system.slice/postgresql.service".to_string(),
+            }]),
+            namespaces: Some(vec![
+                namespace("mnt", 4026532281, true),
+                namespace("net", 4026531840, false),
+            ]),
+            start_time: None,
+        };
+        let text: Vec<String> = context_lines(&proc, &context, 80)
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(text[0].starts_with("started: "));
+        assert_eq!(text[1], "cgroup: /system.slice/postgresql.service");
+        assert_eq!(
+            text[2],
+            "namespaces: mnt:[4026532281] (others shared with oversee)"
+        );
+        assert_eq!(text[3], "limits:");
+        assert_eq!(text[4], "  open files           1024 / 524288 files");
+        assert_eq!(text[5], "environment (2):");
+        assert_eq!(text[6], "  PGDATA=/var/lib/postgresql/data");
+        assert_eq!(text[7], "  PGPASSWORD=•••••• (masked)");
+        assert!(!text.iter().any(|l| l.contains("cpu time")));
+
+        let unreadable = ProcessContext::default();
+        let text: Vec<String> = context_lines(&proc, &unreadable, 80)
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(
+            text[1..],
+            [
+                "cgroup: unreadable",
+                "namespaces: unreadable",
+                "limits: unreadable",
+                "environment: unreadable"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_workload_column_and_groups() {
+        use crate::cgroup::{CgroupLimits, ProcessCgroup, classify};
+
+        let in_cgroup = |pid, name, cpu, mem_mb, path: &str, limits| ProcessInfo {
+            cgroup: Some(ProcessCgroup {
+                path: path.to_string(),
+                workload: classify(path),
+                limits,
+            }),
+            ..fixture::process(pid, name, cpu, mem_mb)
+        };
+        let scope = "/system.slice/docker-0123456789abcdef0123456789abcdef.scope";
+        let limits = CgroupLimits {
+            memory_max: Some(512 * 1024 * 1024),
+            memory_current: Some(480 * 1024 * 1024),
+            cpu_max: Some(0.5),
+        };
+        let (mut app, _rx) = fixture::app();
+        fixture::collect(
+            &mut app,
+            fixture::sources(vec![
+                in_cgroup(
+                    4242,
+                    "postgres",
+                    12.5,
+                    512,
+                    "/system.slice/postgresql.service",
+                    CgroupLimits::default(),
+                ),
+                in_cgroup(4300, "node", 30.0, 200, scope, limits),
+                in_cgroup(4301, "node", 18.0, 100, scope, limits),
+                fixture::process(4243, "bash", 0.5, 64),
+            ]),
+        );
+
+        let screen = |app: &mut App| draw(app).lines().map(String::from).collect::<Vec<_>>();
+
+        let rows = screen(&mut app);
+        assert!(
+            rows.iter()
+                .any(|r| r.contains("CONTAINER") && r.contains("COMMAND"))
+        );
+        assert!(
+            rows.iter()
+                .any(|r| r.contains("4300") && r.contains("docker:0123456789ab"))
+        );
+        assert!(
+            rows.iter()
+                .any(|r| r.contains("4242") && r.contains("postgresql.service"))
+        );
+
+        app.group_by_workload = true;
+        let rows = screen(&mut app);
+        assert!(rows.iter().any(|r| r.contains("workloads (3 total)")));
+        let docker = rows
+            .iter()
+            .find(|r| r.contains("docker:0123456789ab"))
+            .unwrap();
+        assert!(docker.contains("    2   48.0      0.50    300 MB 480 MB / 512 MB (94%)"));
+        let postgres = rows.iter().position(|r| r.contains("postgresql.service"));
+        let host = rows.iter().position(|r| r.contains("(host)"));
+        assert!(postgres < host, "busiest groups first");
+
+        // The filter narrows the groups too
+        app.filter_input = "docker".to_string();
+        app.update_filtered_indices();
+        let rows = screen(&mut app);
+        assert!(rows.iter().any(|r| r.contains("workloads (1 total)")));
+
+        // Containers are not services; the postgres unit is
+        app.services_mode = true;
+        let rows = screen(&mut app);
+        assert!(rows.iter().any(|r| r.contains(" services · 1 ")));
+        assert!(rows.iter().any(|r| {
+            r.contains("postgresql.service               active       4242     1   12.5    512 MB        0")
+        }));
+        assert!(rows.iter().any(|r| {
+            r.contains("  └ postgres                                  4242         12.5    512 MB")
+        }));
+    }
 }
@@ -0,0 +1,180 @@
+//! Watch list of processes and listening ports the user expects to be up.
+//!
+//! Status is derived from the regular process snapshot on every tick, so
+//! the watch list adds no collection cost of its own.
+
+use crate::process::{ConnectionState, ProcessInfo};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchTarget {
+    /// Case-insensitive substring matched against process name and command line.
+    Process(String),
+    /// TCP/UDP port that some process should be listening on.
+    Port(u16),
+}
+
+impl WatchTarget {
+    pub fn label(&self) -> String {
+        match self {
+            WatchTarget::Process(pattern) => pattern.clone(),
+            WatchTarget::Port(port) => format!(":{}", port),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchState {
+    /// Matched on the latest snapshot.
+    Up,
+    /// Never matched since oversee started.
+    Missing,
+    /// Matched at some point since start but not on the latest snapshot.
+    Lost,
+}
+
+#[derive(Debug, Clone)]
+pub struct WatchEntry {
+    pub target: WatchTarget,
+    pub state: WatchState,
+    /// Number of processes currently matching (owners of the port for ports).
+    pub matches: usize,
+    seen: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WatchList {
+    entries: Vec<WatchEntry>,
+}
+
+impl WatchList {
+    pub fn new(targets: Vec<WatchTarget>) -> Self {
+        WatchList {
+            entries: targets
+                .into_iter()
+                .map(|target| WatchEntry {
+                    target,
+                    state: WatchState::Missing,
+                    matches: 0,
+                    seen: false,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[WatchEntry] {
+        &self.entries
+    }
+
+    /// Re-evaluate every entry against a fresh process snapshot.
+    pub fn update(&mut self, processes: &[ProcessInfo]) {
+        for entry in &mut self.entries {
+            entry.matches = match &entry.target {
+                WatchTarget::Process(pattern) => {
+                    let pattern = pattern.to_lowercase();
+                    processes
+                        .iter()
+                        .filter(|p| {
+                            p.name.to_lowercase().contains(&pattern)
+                                || p.cmd.to_lowercase().contains(&pattern)
+                        })
+                        .count()
+                }
+                WatchTarget::Port(port) => processes
+                    .iter()
+                    .filter(|p| {
+                        p.ports.iter().any(|info| {
+                            info.port == *port && matches!(info.state, ConnectionState::Listen)
+                        })
+                    })
+                    .count(),
+            };
+
+            if entry.matches > 0 {
+                entry.seen = true;
+                entry.state = WatchState::Up;
+            } else if entry.seen {
+                entry.state = WatchState::Lost;
+            } else {
+                entry.state = WatchState::Missing;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::{PortInfo, Protocol};
+
+    fn proc(pid: u32, name: &str, cmd: &str, listen: &[u16]) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            name: name.to_string(),
+            cmd: cmd.to_string(),
+            user: "dev".to_string(),
+            cpu_usage: 0.0,
+            gpu_usage: None,
+            energy_impact: None,
+            memory: 0,
+            ports: listen
+                .iter()
+                .map(|&port| PortInfo {
+                    port,
+                    protocol: Protocol::Tcp,
+                    state: ConnectionState::Listen,
+                    local_address: None,
+                    remote_address: None,
+                })
+                .collect(),
+            cwd: None,
+            exe: None,
+            run_time: 0,
+            thread_count: 0,
+            pss: None,
+            uss: None,
+            cgroup: None,
+        }
+    }
+
+    #[test]
+    fn test_watch_transitions_missing_up_lost() {
+        let mut watch = WatchList::new(vec![
+            WatchTarget::Process("Postgres".to_string()),
+            WatchTarget::Port(6379),
+        ]);
+
+        watch.update(&[]);
+        assert!(
+            watch
+                .entries()
+                .iter()
+                .all(|e| e.state == WatchState::Missing)
+        );
+
+        watch.update(&[
+            proc(10, "postgres", "/usr/bin/postgres -D data", &[5432]),
+            proc(11, "redis-server", "redis-server *:6379", &[6379]),
+        ]);
+        assert_eq!(watch.entries()[0].state, WatchState::Up);
+        assert_eq!(watch.entries()[1].state, WatchState::Up);
+
+        watch.update(&[proc(10, "postgres", "postgres", &[5432])]);
+        assert_eq!(watch.entries()[0].state, WatchState::Up);
+        assert_eq!(watch.entries()[1].state, WatchState::Lost);
+        assert_eq!(watch.entries()[1].matches, 0);
+    }
+
+    #[test]
+    fn test_watch_process_matches_command_line() {
+        let mut watch = WatchList::new(vec![WatchTarget::Process("server.js".to_string())]);
+        watch.update(&[
+            proc(1, "node", "node server.js", &[]),
+            proc(2, "node", "node worker.js", &[]),
+        ]);
+        assert_eq!(watch.entries()[0].matches, 1);
+    }
+}